@@ -0,0 +1,18 @@
+-- Create dashboard templates table (user-published; built-in templates live in code)
+CREATE TABLE IF NOT EXISTS dashboard_templates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    slug TEXT NOT NULL,
+    version INTEGER NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    variables JSONB NOT NULL DEFAULT '[]'::jsonb,
+    widget_types TEXT[] NOT NULL DEFAULT '{}',
+    layout_json JSONB NOT NULL DEFAULT '[]'::jsonb,
+    settings_json JSONB NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (slug, version)
+);
+
+-- Create index on widget_types for filtering the gallery
+CREATE INDEX IF NOT EXISTS idx_dashboard_templates_widget_types ON dashboard_templates USING GIN (widget_types);
@@ -0,0 +1,6 @@
+-- Record which dashboard a clone was made from
+ALTER TABLE dashboards
+    ADD COLUMN IF NOT EXISTS source_dashboard_id UUID REFERENCES dashboards(id) ON DELETE SET NULL;
+
+-- Create index on source_dashboard_id for lineage lookups
+CREATE INDEX IF NOT EXISTS idx_dashboards_source_dashboard_id ON dashboards(source_dashboard_id);
@@ -0,0 +1,13 @@
+-- Enable trigram matching for dashboard name search
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- Create trigram index on name so ILIKE '%term%' searches can use an index
+CREATE INDEX IF NOT EXISTS idx_dashboards_name_trgm ON dashboards USING GIN (name gin_trgm_ops);
+
+-- Create index on layout_json for widget type containment filters
+CREATE INDEX IF NOT EXISTS idx_dashboards_layout_widgets ON dashboards USING GIN ((layout_json -> 'widgets') jsonb_path_ops);
+
+-- Create composite indexes backing keyset pagination
+CREATE INDEX IF NOT EXISTS idx_dashboards_user_name ON dashboards(user_id, name, id);
+CREATE INDEX IF NOT EXISTS idx_dashboards_user_created ON dashboards(user_id, created_at, id);
+CREATE INDEX IF NOT EXISTS idx_dashboards_user_updated ON dashboards(user_id, updated_at, id);
@@ -0,0 +1,5 @@
+-- Soft delete: trashed dashboards keep their row until purged
+ALTER TABLE dashboards ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- Create partial index used by the trash listing and the purge job
+CREATE INDEX IF NOT EXISTS idx_dashboards_deleted_at ON dashboards(deleted_at) WHERE deleted_at IS NOT NULL;
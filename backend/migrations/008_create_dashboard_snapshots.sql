@@ -0,0 +1,17 @@
+-- Create dashboard snapshots table: frozen layout plus the widget data it showed
+CREATE TABLE IF NOT EXISTS dashboard_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    dashboard_id UUID NOT NULL REFERENCES dashboards(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    layout_json JSONB NOT NULL,
+    settings_json JSONB NOT NULL,
+    variables JSONB NOT NULL DEFAULT '{}'::jsonb,
+    widgets JSONB NOT NULL DEFAULT '[]'::jsonb,
+    share_token TEXT UNIQUE,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_dashboard_snapshots_dashboard_id ON dashboard_snapshots(dashboard_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_dashboard_snapshots_expires_at ON dashboard_snapshots(expires_at) WHERE expires_at IS NOT NULL;
@@ -0,0 +1,27 @@
+-- Create raw widget metrics table (one row per numeric value per upstream fetch)
+CREATE TABLE IF NOT EXISTS widget_metrics (
+    id BIGSERIAL PRIMARY KEY,
+    widget_type TEXT NOT NULL,
+    series TEXT NOT NULL,
+    metric TEXT NOT NULL,
+    value DOUBLE PRECISION NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_widget_metrics_series ON widget_metrics(widget_type, series, metric, recorded_at);
+CREATE INDEX IF NOT EXISTS idx_widget_metrics_recorded_at ON widget_metrics(recorded_at);
+
+-- Create hourly rollups that raw points are folded into once they age out
+CREATE TABLE IF NOT EXISTS widget_metric_rollups (
+    widget_type TEXT NOT NULL,
+    series TEXT NOT NULL,
+    metric TEXT NOT NULL,
+    bucket_start TIMESTAMPTZ NOT NULL,
+    min_value DOUBLE PRECISION NOT NULL,
+    max_value DOUBLE PRECISION NOT NULL,
+    avg_value DOUBLE PRECISION NOT NULL,
+    sample_count BIGINT NOT NULL,
+    PRIMARY KEY (widget_type, series, metric, bucket_start)
+);
+
+CREATE INDEX IF NOT EXISTS idx_widget_metric_rollups_bucket_start ON widget_metric_rollups(bucket_start);
@@ -0,0 +1,47 @@
+-- Create uptime monitors table; the worker checks monitors whose next_check_at has passed
+CREATE TABLE IF NOT EXISTS monitors (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    url TEXT NOT NULL,
+    interval_seconds INTEGER NOT NULL,
+    expected_status INTEGER,
+    timeout_ms INTEGER NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    last_is_up BOOLEAN,
+    last_checked_at TIMESTAMPTZ,
+    next_check_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_monitors_user_id ON monitors(user_id);
+CREATE INDEX IF NOT EXISTS idx_monitors_next_check_at ON monitors(next_check_at) WHERE enabled;
+
+-- Create check history table
+CREATE TABLE IF NOT EXISTS monitor_checks (
+    id BIGSERIAL PRIMARY KEY,
+    monitor_id UUID NOT NULL REFERENCES monitors(id) ON DELETE CASCADE,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    is_up BOOLEAN NOT NULL,
+    status_code INTEGER,
+    response_time_ms INTEGER,
+    error TEXT
+);
+
+CREATE INDEX IF NOT EXISTS idx_monitor_checks_monitor_id ON monitor_checks(monitor_id, checked_at DESC);
+CREATE INDEX IF NOT EXISTS idx_monitor_checks_checked_at ON monitor_checks(checked_at);
+
+-- Create incidents table: an incident opens on a failed check and closes on the next successful one
+CREATE TABLE IF NOT EXISTS monitor_incidents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    monitor_id UUID NOT NULL REFERENCES monitors(id) ON DELETE CASCADE,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    cause TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_monitor_incidents_monitor_id ON monitor_incidents(monitor_id, started_at DESC);
+
+-- At most one open incident per monitor
+CREATE UNIQUE INDEX IF NOT EXISTS idx_monitor_incidents_open ON monitor_incidents(monitor_id) WHERE resolved_at IS NULL;
@@ -0,0 +1,24 @@
+-- Create push sources: external systems POST signed JSON to /api/ingest/:id
+CREATE TABLE IF NOT EXISTS push_sources (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    keep_history BOOLEAN NOT NULL DEFAULT TRUE,
+    latest JSONB,
+    latest_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_push_sources_user_id ON push_sources(user_id);
+
+-- Create pushed value history
+CREATE TABLE IF NOT EXISTS push_points (
+    id BIGSERIAL PRIMARY KEY,
+    source_id UUID NOT NULL REFERENCES push_sources(id) ON DELETE CASCADE,
+    value JSONB NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_push_points_source_id ON push_points(source_id, recorded_at DESC);
@@ -0,0 +1,13 @@
+-- Create per-user forge (GitHub, GitLab, Gitea) credentials used by activity widgets
+CREATE TABLE IF NOT EXISTS forge_accounts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    forge TEXT NOT NULL,
+    base_url TEXT NOT NULL,
+    token_encrypted BYTEA NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_forge_accounts_user_id ON forge_accounts(user_id);
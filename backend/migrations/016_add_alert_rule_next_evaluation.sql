@@ -0,0 +1,4 @@
+-- Track when each alert rule is next due so evaluation instances can claim rules with SKIP LOCKED
+ALTER TABLE alert_rules ADD COLUMN IF NOT EXISTS next_evaluation_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE INDEX IF NOT EXISTS idx_alert_rules_next_evaluation_at ON alert_rules(next_evaluation_at) WHERE enabled;
@@ -5,7 +5,7 @@ use argon2::{
 use axum::{
     async_trait,
     extract::FromRequestParts,
-    http::{request::Parts, StatusCode},
+    http::request::Parts,
     RequestPartsExt,
 };
 use axum_extra::{
@@ -31,9 +31,16 @@ pub struct Claims {
 #[derive(Debug, Clone)]
 pub struct UserCtx {
     pub user_id: Uuid,
+    #[allow(dead_code)]
     pub email: String,
 }
 
+/// Context of an authenticated user with the admin flag set
+#[derive(Debug, Clone)]
+pub struct AdminCtx {
+    pub user_id: Uuid,
+}
+
 /// Hash a password using Argon2
 pub fn hash_password(password: &str) -> Result<String> {
     let salt = SaltString::generate(&mut OsRng);
@@ -120,3 +127,26 @@ impl FromRequestParts<AppState> for UserCtx {
         })
     }
 }
+
+/// Extract an admin context; non-admins are rejected with 403
+#[async_trait]
+impl FromRequestParts<AppState> for AdminCtx {
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> std::result::Result<Self, Self::Rejection> {
+        let user_ctx = UserCtx::from_request_parts(parts, state).await?;
+
+        let is_admin: Option<(bool,)> = sqlx::query_as("SELECT is_admin FROM users WHERE id = $1")
+            .bind(user_ctx.user_id)
+            .fetch_optional(state.db.pool())
+            .await?;
+
+        match is_admin {
+            Some((true,)) => Ok(AdminCtx { user_id: user_ctx.user_id }),
+            _ => Err(AppError::Forbidden),
+        }
+    }
+}
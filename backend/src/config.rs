@@ -9,8 +9,30 @@ pub struct Config {
     pub jwt_secret: String,
     pub github_api_token: Option<String>,
     pub openweather_api_key: Option<String>,
+    /// Default weather provider (`openweather` or `open-meteo`); chosen by key availability when unset
+    pub weather_provider: Option<String>,
     pub newsapi_api_key: Option<String>,
     pub coinmarketcap_api_key: Option<String>,
+    /// Days a trashed dashboard is kept before being purged
+    pub trash_retention_days: i32,
+    /// Days raw widget metrics are kept before being rolled up into hourly buckets
+    pub metrics_raw_retention_days: i32,
+    /// Days hourly metric rollups are kept
+    pub metrics_rollup_retention_days: i32,
+    /// Days uptime monitor check results are kept
+    pub monitor_check_retention_days: i32,
+    /// SMTP server for email notifications; email channels fail when unset
+    pub smtp_url: Option<String>,
+    /// Sender address for outgoing email
+    pub smtp_from: String,
+    /// Hosts, addresses or CIDR ranges user-supplied URLs (widgets, webhooks, monitors) may reach even when private
+    pub egress_allowed_hosts: Vec<String>,
+    /// Base URL of the Prometheus-compatible API queried by Prometheus widgets
+    pub prometheus_url: Option<String>,
+    /// Bearer token sent to the Prometheus API
+    pub prometheus_token: Option<String>,
+    /// Base64-encoded 32-byte key encrypting stored credentials (data sources, forge tokens)
+    pub data_source_key: Option<String>,
 }
 
 impl Config {
@@ -28,8 +50,62 @@ impl Config {
                 .expect("JWT_SECRET must be set"),
             github_api_token: env::var("GITHUB_API_TOKEN").ok(),
             openweather_api_key: env::var("OPENWEATHER_API_KEY").ok(),
+            weather_provider: env::var("WEATHER_PROVIDER").ok(),
             newsapi_api_key: env::var("NEWSAPI_API_KEY").ok(),
             coinmarketcap_api_key: env::var("COINMARKETCAP_API_KEY").ok(),
+            trash_retention_days: env::var("TRASH_RETENTION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()?,
+            metrics_raw_retention_days: env::var("METRICS_RAW_RETENTION_DAYS")
+                .unwrap_or_else(|_| "7".to_string())
+                .parse()?,
+            metrics_rollup_retention_days: env::var("METRICS_ROLLUP_RETENTION_DAYS")
+                .unwrap_or_else(|_| "365".to_string())
+                .parse()?,
+            monitor_check_retention_days: env::var("MONITOR_CHECK_RETENTION_DAYS")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()?,
+            smtp_url: env::var("SMTP_URL").ok(),
+            smtp_from: env::var("SMTP_FROM")
+                .unwrap_or_else(|_| "InsightBoard <noreply@insightboard.local>".to_string()),
+            egress_allowed_hosts: env::var("EGRESS_ALLOWED_HOSTS")
+                .map(|hosts| {
+                    hosts
+                        .split(',')
+                        .map(|host| host.trim().to_string())
+                        .filter(|host| !host.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            prometheus_url: env::var("PROMETHEUS_URL").ok(),
+            prometheus_token: env::var("PROMETHEUS_TOKEN").ok(),
+            data_source_key: env::var("DATA_SOURCE_KEY").ok(),
         })
     }
+
+    /// Configuration for unit tests: no external services and an empty egress allow-list
+    #[cfg(test)]
+    pub fn for_tests() -> Self {
+        Self {
+            app_port: 8080,
+            database_url: String::new(),
+            redis_url: String::new(),
+            jwt_secret: "test".to_string(),
+            github_api_token: None,
+            openweather_api_key: None,
+            weather_provider: None,
+            newsapi_api_key: None,
+            coinmarketcap_api_key: None,
+            trash_retention_days: 30,
+            metrics_raw_retention_days: 7,
+            metrics_rollup_retention_days: 365,
+            monitor_check_retention_days: 90,
+            smtp_url: None,
+            smtp_from: "InsightBoard <noreply@insightboard.local>".to_string(),
+            egress_allowed_hosts: Vec::new(),
+            prometheus_url: None,
+            prometheus_token: None,
+            data_source_key: None,
+        }
+    }
 }
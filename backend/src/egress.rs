@@ -0,0 +1,308 @@
+use ipnet::IpNet;
+use reqwest::redirect::Policy;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use url::Url;
+
+use crate::{
+    config::Config,
+    error::{AppError, Result},
+};
+
+/// Largest response body read from a user-supplied URL
+pub const MAX_RESPONSE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Most redirects followed by `guarded_get`
+const MAX_REDIRECTS: usize = 5;
+
+/// Build an HTTP client for a user-supplied URL, guarding against SSRF.
+///
+/// The host is resolved once and every address must be public unless the host is listed in
+/// `EGRESS_ALLOWED_HOSTS`; the client is pinned to those addresses (so a second DNS answer
+/// can't point elsewhere) and does not follow redirects.
+pub async fn guarded_client(config: &Config, url: &Url, timeout: Duration) -> Result<reqwest::Client> {
+    let addrs = guarded_addrs(config, url).await?;
+
+    let mut builder = reqwest::Client::builder()
+        .timeout(timeout)
+        .redirect(Policy::none())
+        .user_agent("InsightBoard");
+    if let Some(url::Host::Domain(domain)) = url.host() {
+        builder = builder.resolve_to_addrs(domain, &addrs);
+    }
+
+    builder
+        .build()
+        .map_err(|e| AppError::Internal(format!("Failed to build HTTP client: {}", e)))
+}
+
+/// Check a user-supplied URL against the same rules as `guarded_client` without building a client
+pub async fn check_url(config: &Config, url: &Url) -> Result<()> {
+    guarded_addrs(config, url).await.map(|_| ())
+}
+
+/// Resolve a URL's host, failing unless every address is public or allow-listed
+async fn guarded_addrs(config: &Config, url: &Url) -> Result<Vec<SocketAddr>> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(AppError::Validation("Only http(s) URLs are allowed".to_string()));
+    }
+
+    let host = url
+        .host_str()
+        .ok_or_else(|| AppError::Validation("URL has no host".to_string()))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| AppError::Validation("URL has no port".to_string()))?;
+
+    let addrs: Vec<SocketAddr> = match url.host() {
+        Some(url::Host::Ipv4(ip)) => vec![SocketAddr::new(IpAddr::V4(ip), port)],
+        Some(url::Host::Ipv6(ip)) => vec![SocketAddr::new(IpAddr::V6(ip), port)],
+        _ => tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| AppError::ExternalApi(format!("Failed to resolve {}: {}", host, e)))?
+            .collect(),
+    };
+
+    if addrs.is_empty() {
+        return Err(AppError::ExternalApi(format!("{} did not resolve", host)));
+    }
+
+    for addr in &addrs {
+        if is_reserved(addr.ip()) && !is_allowed(config, host, addr.ip()) {
+            return Err(AppError::Validation(format!(
+                "{} resolves to a private or reserved address",
+                host
+            )));
+        }
+    }
+
+    Ok(addrs)
+}
+
+/// GET a user-supplied URL, following redirects and re-checking every hop with `guarded_client`
+pub async fn guarded_get(
+    config: &Config,
+    url: &Url,
+    headers: reqwest::header::HeaderMap,
+    timeout: Duration,
+) -> Result<reqwest::Response> {
+    let mut url = url.clone();
+
+    for _ in 0..=MAX_REDIRECTS {
+        let client = guarded_client(config, &url, timeout).await?;
+        let response = client
+            .get(url.clone())
+            .headers(headers.clone())
+            .send()
+            .await
+            .map_err(|e| AppError::ExternalApi(format!("Request to {} failed: {}", url, e)))?;
+
+        if !response.status().is_redirection() || response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(response);
+        }
+
+        let location = response
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| AppError::ExternalApi(format!("{} redirected without a location", url)))?;
+        url = url
+            .join(location)
+            .map_err(|e| AppError::ExternalApi(format!("Invalid redirect from {}: {}", url, e)))?;
+    }
+
+    Err(AppError::ExternalApi(format!("Too many redirects from {}", url)))
+}
+
+/// Read a response body, failing once it grows past `limit` bytes
+pub async fn read_limited(mut response: reqwest::Response, limit: usize) -> Result<Vec<u8>> {
+    if response.content_length().is_some_and(|length| length as usize > limit) {
+        return Err(AppError::ExternalApi(format!("Response is larger than {} bytes", limit)));
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to read response: {}", e)))?
+    {
+        if body.len() + chunk.len() > limit {
+            return Err(AppError::ExternalApi(format!("Response is larger than {} bytes", limit)));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Whether `EGRESS_ALLOWED_HOSTS` lets this host/address through.
+///
+/// Entries are host names (`*.corp.example` matches subdomains), addresses or CIDR ranges.
+fn is_allowed(config: &Config, host: &str, ip: IpAddr) -> bool {
+    let host = host.to_ascii_lowercase();
+
+    config.egress_allowed_hosts.iter().any(|entry| {
+        if let Ok(net) = entry.parse::<IpNet>() {
+            net.contains(&ip)
+        } else if let Ok(allowed) = entry.parse::<IpAddr>() {
+            allowed == ip
+        } else if let Some(suffix) = entry.strip_prefix("*.") {
+            host.ends_with(&format!(".{}", suffix.to_ascii_lowercase()))
+        } else {
+            host == entry.to_ascii_lowercase()
+        }
+    })
+}
+
+/// Loopback, private, link-local and other non-public addresses
+fn is_reserved(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_reserved_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(mapped) => is_reserved_v4(mapped),
+            None => is_reserved_v6(ip),
+        },
+    }
+}
+
+fn is_reserved_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+
+    ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || a == 0
+        // Carrier-grade NAT 100.64.0.0/10
+        || (a == 100 && (64..128).contains(&b))
+        // IETF protocol assignments 192.0.0.0/24
+        || (a == 192 && b == 0 && c == 0)
+        // Benchmarking 198.18.0.0/15
+        || (a == 198 && (b == 18 || b == 19))
+        // Reserved 240.0.0.0/4
+        || a >= 240
+}
+
+fn is_reserved_v6(ip: Ipv6Addr) -> bool {
+    let segments = ip.segments();
+    let first = segments[0];
+
+    // NAT64 64:ff9b::/96 and IPv4-compatible ::a.b.c.d reach the embedded IPv4 address
+    let embedded_v4 = match segments {
+        [0x64, 0xff9b, 0, 0, 0, 0, _, _] | [0, 0, 0, 0, 0, 0, _, _] => {
+            let [.., a, b, c, d] = ip.octets();
+            Some(Ipv4Addr::new(a, b, c, d))
+        }
+        _ => None,
+    };
+
+    ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || embedded_v4.is_some_and(is_reserved_v4)
+        // Unique local fc00::/7
+        || (first & 0xfe00) == 0xfc00
+        // Link-local fe80::/10
+        || (first & 0xffc0) == 0xfe80
+        // Documentation 2001:db8::/32
+        || (first == 0x2001 && segments[1] == 0x0db8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reserved(ip: &str) -> bool {
+        is_reserved(ip.parse().unwrap())
+    }
+
+    #[test]
+    fn reserved_addresses_are_detected() {
+        for ip in [
+            "127.0.0.1",
+            "::1",
+            "169.254.169.254",
+            "fe80::1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "fd00::1",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::",
+            "::ffff:127.0.0.1",
+            "::ffff:169.254.169.254",
+            "64:ff9b::7f00:1",
+            "64:ff9b::a9fe:a9fe",
+            "64:ff9b::10.0.0.1",
+            "::127.0.0.1",
+            "::10.0.0.1",
+        ] {
+            assert!(reserved(ip), "{} should be reserved", ip);
+        }
+
+        for ip in ["93.184.216.34", "2606:2800:220:1::1", "::ffff:93.184.216.34", "64:ff9b::93.184.216.34"] {
+            assert!(!reserved(ip), "{} should be public", ip);
+        }
+    }
+
+    #[test]
+    fn allow_list_matches_hosts_addresses_and_ranges() {
+        let config = Config {
+            egress_allowed_hosts: vec![
+                "api.internal".to_string(),
+                "*.corp.example".to_string(),
+                "10.20.0.0/16".to_string(),
+                "192.168.1.5".to_string(),
+            ],
+            ..Config::for_tests()
+        };
+        let private: IpAddr = "10.9.9.9".parse().unwrap();
+
+        assert!(is_allowed(&config, "API.internal", private));
+        assert!(is_allowed(&config, "grafana.corp.example", private));
+        assert!(!is_allowed(&config, "corp.example", private));
+        assert!(is_allowed(&config, "db", "10.20.3.4".parse().unwrap()));
+        assert!(is_allowed(&config, "nas", "192.168.1.5".parse().unwrap()));
+        assert!(!is_allowed(&config, "nas", "192.168.1.6".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn private_hosts_need_the_allow_list() {
+        let url = Url::parse("http://127.0.0.1:8080/metrics").unwrap();
+
+        let refused = check_url(&Config::for_tests(), &url).await;
+        assert!(matches!(refused, Err(AppError::Validation(_))));
+
+        let config = Config { egress_allowed_hosts: vec!["127.0.0.1".to_string()], ..Config::for_tests() };
+        assert!(check_url(&config, &url).await.is_ok());
+
+        let url = Url::parse("http://[::ffff:169.254.169.254]/latest/meta-data").unwrap();
+        assert!(matches!(check_url(&config, &url).await, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn redirects_to_private_addresses_are_refused() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/moved")
+            .with_status(302)
+            .with_header("location", "http://169.254.169.254/latest/meta-data")
+            .create_async()
+            .await;
+        let config = Config { egress_allowed_hosts: vec!["127.0.0.1".to_string()], ..Config::for_tests() };
+        let url = Url::parse(&format!("{}/moved", server.url())).unwrap();
+
+        // The client itself never follows the redirect
+        let client = guarded_client(&config, &url, Duration::from_secs(5)).await.unwrap();
+        let response = client.get(url.clone()).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::FOUND);
+
+        // guarded_get re-checks the target and refuses it
+        let result = guarded_get(&config, &url, reqwest::header::HeaderMap::new(), Duration::from_secs(5)).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+}
@@ -3,8 +3,33 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
 
+/// A validation problem tied to a specific request field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Prefix the field path, e.g. `username` -> `layout_json.widgets[0].config.username`
+    pub fn nested(self, prefix: &str) -> Self {
+        Self {
+            field: format!("{}.{}", prefix, self.field),
+            message: self.message,
+        }
+    }
+}
+
 /// Custom error type for the application
 #[derive(Debug, thiserror::Error)]
 pub enum AppError {
@@ -20,6 +45,9 @@ pub enum AppError {
     #[error("Validation error: {0}")]
     Validation(String),
     
+    #[error("Validation failed for {} field(s)", .0.len())]
+    InvalidFields(Vec<FieldError>),
+    
     #[error("Not found: {0}")]
     NotFound(String),
     
@@ -29,6 +57,9 @@ pub enum AppError {
     #[error("Forbidden")]
     Forbidden,
     
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+    
     #[error("External API error: {0}")]
     ExternalApi(String),
     
@@ -36,35 +67,65 @@ pub enum AppError {
     Internal(String),
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match self {
-            AppError::Database(ref e) => {
-                tracing::error!("Database error: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Database error occurred")
-            }
-            AppError::Redis(ref e) => {
-                tracing::error!("Redis error: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Cache error occurred")
-            }
-            AppError::Auth(ref msg) => (StatusCode::UNAUTHORIZED, msg.as_str()),
-            AppError::Validation(ref msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
-            AppError::NotFound(ref msg) => (StatusCode::NOT_FOUND, msg.as_str()),
+impl AppError {
+    /// Status and client-safe message; internal details are only ever logged
+    fn public_parts(&self) -> (StatusCode, &str) {
+        match self {
+            AppError::Database(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Database error occurred"),
+            AppError::Redis(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Cache error occurred"),
+            AppError::Auth(msg) => (StatusCode::UNAUTHORIZED, msg.as_str()),
+            AppError::Validation(msg) => (StatusCode::BAD_REQUEST, msg.as_str()),
+            AppError::InvalidFields(_) => (StatusCode::BAD_REQUEST, "Validation failed"),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.as_str()),
             AppError::Unauthorized => (StatusCode::UNAUTHORIZED, "Unauthorized"),
             AppError::Forbidden => (StatusCode::FORBIDDEN, "Forbidden"),
-            AppError::ExternalApi(ref msg) => {
-                tracing::error!("External API error: {}", msg);
-                (StatusCode::BAD_GATEWAY, "External service error")
-            }
-            AppError::Internal(ref msg) => {
-                tracing::error!("Internal error: {}", msg);
-                (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+            AppError::RateLimited(msg) => (StatusCode::TOO_MANY_REQUESTS, msg.as_str()),
+            AppError::ExternalApi(_) => (StatusCode::BAD_GATEWAY, "External service error"),
+            AppError::Internal(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
+        }
+    }
+
+    /// The message a client may see, as sent in error responses
+    pub fn client_message(&self) -> String {
+        match self {
+            AppError::InvalidFields(errors) => {
+                let fields: Vec<String> = errors
+                    .iter()
+                    .map(|error| format!("{}: {}", error.field, error.message))
+                    .collect();
+                format!("Validation failed: {}", fields.join("; "))
             }
+            _ => self.public_parts().1.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let details = match &self {
+            AppError::InvalidFields(errors) => Some(errors.clone()),
+            _ => None,
         };
 
-        let body = Json(json!({
-            "error": error_message,
-        }));
+        match &self {
+            AppError::Database(e) => tracing::error!("Database error: {:?}", e),
+            AppError::Redis(e) => tracing::error!("Redis error: {:?}", e),
+            AppError::ExternalApi(msg) => tracing::error!("External API error: {}", msg),
+            AppError::Internal(msg) => tracing::error!("Internal error: {}", msg),
+            _ => {}
+        }
+
+        let (status, error_message) = self.public_parts();
+
+        let body = match details {
+            Some(details) => Json(json!({
+                "error": error_message,
+                "details": details,
+            })),
+            None => Json(json!({
+                "error": error_message,
+            })),
+        };
 
         (status, body).into_response()
     }
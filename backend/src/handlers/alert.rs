@@ -0,0 +1,198 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, FieldError, Result},
+    models::{AlertEvent, AlertRule, AlertRuleRequest, MAX_PAGE_SIZE},
+    AppState,
+};
+
+pub(crate) const ALERT_RULE_COLUMNS: &str = "id, user_id, name, widget_type, series, metric, comparison, \
+     threshold, for_seconds, cooldown_seconds, channel_ids, enabled, state, pending_since, \
+     state_changed_at, last_value, last_evaluated_at, last_notified_at, created_at, updated_at";
+
+/// List all alert rules for the authenticated user
+pub async fn list_alert_rules(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let rules: Vec<AlertRule> = sqlx::query_as(&format!(
+        "SELECT {} FROM alert_rules WHERE user_id = $1 ORDER BY name",
+        ALERT_RULE_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(rules))
+}
+
+/// Get an alert rule with its current state
+pub async fn get_alert_rule(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let rule = find_alert_rule(&state, user_ctx.user_id, rule_id).await?;
+    Ok(Json(rule))
+}
+
+/// Create an alert rule; it starts in the `ok` state
+pub async fn create_alert_rule(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<AlertRuleRequest>,
+) -> Result<impl IntoResponse> {
+    payload.validate()?;
+    ensure_channels_owned(&state, user_ctx.user_id, &payload.channel_ids).await?;
+
+    let rule: AlertRule = sqlx::query_as(&format!(
+        "INSERT INTO alert_rules
+         (user_id, name, widget_type, series, metric, comparison, threshold, for_seconds, cooldown_seconds, channel_ids, enabled)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+         RETURNING {}",
+        ALERT_RULE_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .bind(payload.name.trim())
+    .bind(payload.widget_type)
+    .bind(payload.series.trim())
+    .bind(&payload.metric)
+    .bind(payload.comparison)
+    .bind(payload.threshold)
+    .bind(payload.for_seconds)
+    .bind(payload.cooldown_seconds)
+    .bind(&payload.channel_ids)
+    .bind(payload.enabled)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+/// Replace an alert rule's definition; its state is reset to `ok`
+pub async fn update_alert_rule(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+    Json(payload): Json<AlertRuleRequest>,
+) -> Result<impl IntoResponse> {
+    payload.validate()?;
+    ensure_channels_owned(&state, user_ctx.user_id, &payload.channel_ids).await?;
+
+    let rule: Option<AlertRule> = sqlx::query_as(&format!(
+        "UPDATE alert_rules
+         SET name = $1, widget_type = $2, series = $3, metric = $4, comparison = $5, threshold = $6,
+             for_seconds = $7, cooldown_seconds = $8, channel_ids = $9, enabled = $10,
+             state = 'ok', pending_since = NULL, state_changed_at = NOW(), updated_at = NOW()
+         WHERE id = $11 AND user_id = $12
+         RETURNING {}",
+        ALERT_RULE_COLUMNS
+    ))
+    .bind(payload.name.trim())
+    .bind(payload.widget_type)
+    .bind(payload.series.trim())
+    .bind(&payload.metric)
+    .bind(payload.comparison)
+    .bind(payload.threshold)
+    .bind(payload.for_seconds)
+    .bind(payload.cooldown_seconds)
+    .bind(&payload.channel_ids)
+    .bind(payload.enabled)
+    .bind(rule_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let rule = rule.ok_or_else(|| AppError::NotFound("Alert rule not found".to_string()))?;
+
+    Ok(Json(rule))
+}
+
+/// Delete an alert rule
+pub async fn delete_alert_rule(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "DELETE FROM alert_rules WHERE id = $1 AND user_id = $2"
+    )
+    .bind(rule_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Alert rule not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List an alert rule's recent state changes, newest first
+pub async fn list_alert_events(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(rule_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    find_alert_rule(&state, user_ctx.user_id, rule_id).await?;
+
+    let events: Vec<AlertEvent> = sqlx::query_as(
+        "SELECT id, from_state, to_state, value, notified, created_at
+         FROM alert_events
+         WHERE rule_id = $1
+         ORDER BY created_at DESC
+         LIMIT $2"
+    )
+    .bind(rule_id)
+    .bind(MAX_PAGE_SIZE)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(events))
+}
+
+/// Load an alert rule owned by the user
+async fn find_alert_rule(state: &AppState, user_id: Uuid, rule_id: Uuid) -> Result<AlertRule> {
+    let rule: Option<AlertRule> = sqlx::query_as(&format!(
+        "SELECT {} FROM alert_rules WHERE id = $1 AND user_id = $2",
+        ALERT_RULE_COLUMNS
+    ))
+    .bind(rule_id)
+    .bind(user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    rule.ok_or_else(|| AppError::NotFound("Alert rule not found".to_string()))
+}
+
+/// Fail unless every channel exists and belongs to the user
+pub(crate) async fn ensure_channels_owned(state: &AppState, user_id: Uuid, channel_ids: &[Uuid]) -> Result<()> {
+    let (owned,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM notification_channels WHERE user_id = $1 AND id = ANY($2)"
+    )
+    .bind(user_id)
+    .bind(channel_ids)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    let mut unique = channel_ids.to_vec();
+    unique.sort();
+    unique.dedup();
+
+    if owned as usize != unique.len() {
+        return Err(AppError::InvalidFields(vec![FieldError::new(
+            "channel_ids",
+            "Unknown notification channel",
+        )]));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde_json::json;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    config::Config,
+    egress::check_url,
+    error::{AppError, FieldError, Result},
+    models::{ChannelRequest, NotificationChannel},
+    notify::{deliver, Notification},
+    AppState,
+};
+
+/// List all notification channels for the authenticated user
+pub async fn list_channels(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let channels: Vec<NotificationChannel> = sqlx::query_as(
+        "SELECT id, user_id, name, config, created_at, updated_at
+         FROM notification_channels
+         WHERE user_id = $1
+         ORDER BY name"
+    )
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(channels))
+}
+
+/// Create a notification channel
+pub async fn create_channel(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<ChannelRequest>,
+) -> Result<impl IntoResponse> {
+    validate_channel(&state.config, &payload).await?;
+
+    let channel: NotificationChannel = sqlx::query_as(
+        "INSERT INTO notification_channels (user_id, name, kind, config)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id, user_id, name, config, created_at, updated_at"
+    )
+    .bind(user_ctx.user_id)
+    .bind(payload.name.trim())
+    .bind(payload.config.kind())
+    .bind(sqlx::types::Json(&payload.config))
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(channel)))
+}
+
+/// Replace a notification channel's name and target
+pub async fn update_channel(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(channel_id): Path<Uuid>,
+    Json(payload): Json<ChannelRequest>,
+) -> Result<impl IntoResponse> {
+    validate_channel(&state.config, &payload).await?;
+
+    let channel: Option<NotificationChannel> = sqlx::query_as(
+        "UPDATE notification_channels
+         SET name = $1, kind = $2, config = $3, updated_at = NOW()
+         WHERE id = $4 AND user_id = $5
+         RETURNING id, user_id, name, config, created_at, updated_at"
+    )
+    .bind(payload.name.trim())
+    .bind(payload.config.kind())
+    .bind(sqlx::types::Json(&payload.config))
+    .bind(channel_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let channel = channel.ok_or_else(|| AppError::NotFound("Channel not found".to_string()))?;
+
+    Ok(Json(channel))
+}
+
+/// Delete a notification channel, detaching it from alert rules
+pub async fn delete_channel(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(channel_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let mut tx = state.db.pool().begin().await?;
+
+    let result = sqlx::query(
+        "DELETE FROM notification_channels WHERE id = $1 AND user_id = $2"
+    )
+    .bind(channel_id)
+    .bind(user_ctx.user_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Channel not found".to_string()));
+    }
+
+    sqlx::query(
+        "UPDATE alert_rules SET channel_ids = array_remove(channel_ids, $1) WHERE user_id = $2"
+    )
+    .bind(channel_id)
+    .bind(user_ctx.user_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Send a test notification through a channel, surfacing any delivery error
+pub async fn test_channel(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(channel_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let channel: Option<NotificationChannel> = sqlx::query_as(
+        "SELECT id, user_id, name, config, created_at, updated_at
+         FROM notification_channels
+         WHERE id = $1 AND user_id = $2"
+    )
+    .bind(channel_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let channel = channel.ok_or_else(|| AppError::NotFound("Channel not found".to_string()))?;
+
+    let notification = Notification {
+        title: format!("InsightBoard test notification for '{}'", channel.name),
+        text: "If you can read this, the channel works.".to_string(),
+        html: None,
+        payload: json!({ "event": "test", "channel_id": channel.id, "channel": channel.name }),
+    };
+
+    deliver(&state.config, state.mailer.as_ref(), &channel.config, &notification).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn validate_channel(config: &Config, payload: &ChannelRequest) -> Result<()> {
+    let mut errors = payload.config.validate();
+    if payload.name.trim().is_empty() {
+        errors.push(FieldError::new("name", "Channel name is required"));
+    }
+
+    // Webhook targets must pass the egress guard deliveries go through
+    if let Some(url) = payload.config.url().and_then(|url| Url::parse(url).ok()) {
+        match check_url(config, &url).await {
+            Ok(()) => {}
+            Err(AppError::Validation(message)) => errors.push(FieldError::new("url", message)),
+            Err(_) => errors.push(FieldError::new("url", "Host could not be resolved")),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::InvalidFields(errors))
+    }
+}
@@ -1,60 +1,251 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
     Json,
 };
+use serde_json::{json, Value as JsonValue};
+use sqlx::{Postgres, QueryBuilder};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 use crate::{
     auth::UserCtx,
     error::{AppError, Result},
-    models::{CreateDashboardRequest, Dashboard, DashboardResponse, UpdateDashboardRequest},
+    handlers::{organization::member_role, share::for_reader},
+    models::{
+        regenerate_widget_ids, validate_dashboard, CloneDashboardRequest,
+        CreateDashboardRequest, Dashboard, DashboardCursor, DashboardPage, DashboardResponse,
+        DashboardSort, ListDashboardsQuery, SortOrder, UpdateDashboardRequest,
+    },
     AppState,
 };
 
-/// List all dashboards for the authenticated user
+/// List the authenticated user's dashboards, one page at a time
 pub async fn list_dashboards(
     user_ctx: UserCtx,
     State(state): State<AppState>,
+    Query(query): Query<ListDashboardsQuery>,
 ) -> Result<impl IntoResponse> {
-    let dashboards: Vec<Dashboard> = sqlx::query_as(
-        "SELECT id, user_id, name, layout_json, settings_json, created_at, updated_at 
-         FROM dashboards 
-         WHERE user_id = $1 
-         ORDER BY updated_at DESC"
-    )
-    .bind(user_ctx.user_id)
-    .fetch_all(state.db.pool())
-    .await?;
+    let sort = query.sort;
+    let order = query.order();
+    let limit = query.limit();
 
-    let response: Vec<DashboardResponse> = dashboards
+    let cursor = query.cursor.as_deref().map(DashboardCursor::decode).transpose()?;
+    if let Some(cursor) = &cursor {
+        if cursor.sort != sort || cursor.order != order {
+            return Err(AppError::Validation(
+                "Cursor does not match the requested sort order".to_string(),
+            ));
+        }
+    }
+
+    let mut count_query = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM dashboards");
+    push_list_filters(&mut count_query, user_ctx.user_id, &query)?;
+    let (total,): (i64,) = count_query
+        .build_query_as()
+        .fetch_one(state.db.pool())
+        .await?;
+
+    let mut page_query = QueryBuilder::<Postgres>::new(
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at 
+         FROM dashboards",
+    );
+    push_list_filters(&mut page_query, user_ctx.user_id, &query)?;
+
+    let (comparison, direction) = match order {
+        SortOrder::Asc => (">", "ASC"),
+        SortOrder::Desc => ("<", "DESC"),
+    };
+
+    if let Some(cursor) = &cursor {
+        page_query.push(format!(" AND ({}, id) {} (", sort.column(), comparison));
+        match sort {
+            DashboardSort::Name => page_query.push_bind(cursor.value.clone()),
+            DashboardSort::Created | DashboardSort::Updated => {
+                page_query.push_bind(cursor.timestamp()?)
+            }
+        };
+        page_query.push(", ").push_bind(cursor.id).push(")");
+    }
+
+    // Fetch one extra row to learn whether another page exists
+    page_query
+        .push(format!(" ORDER BY {} {}, id {} LIMIT ", sort.column(), direction, direction))
+        .push_bind(limit + 1);
+
+    let mut dashboards: Vec<Dashboard> = page_query
+        .build_query_as()
+        .fetch_all(state.db.pool())
+        .await?;
+
+    let has_more = dashboards.len() as i64 > limit;
+    dashboards.truncate(limit as usize);
+
+    let mut items: Vec<DashboardResponse> = dashboards
         .into_iter()
         .map(DashboardResponse::from)
         .collect();
+    annotate_dashboards(&state, user_ctx.user_id, &mut items).await?;
 
-    Ok(Json(response))
+    let next_cursor = if has_more {
+        items
+            .last()
+            .map(|last| DashboardCursor::after(last, sort, order).encode())
+    } else {
+        None
+    };
+
+    Ok(Json(DashboardPage {
+        items,
+        total,
+        next_cursor,
+    }))
 }
 
-/// Get a specific dashboard
+/// Append the WHERE clause shared by the listing's count and page queries
+fn push_list_filters(
+    builder: &mut QueryBuilder<'_, Postgres>,
+    user_id: Uuid,
+    query: &ListDashboardsQuery,
+) -> Result<()> {
+    builder
+        .push(" WHERE deleted_at IS NULL AND user_id = ")
+        .push_bind(user_id);
+
+    if let Some(search) = query.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        // Escape LIKE wildcards so the search is a plain substring match
+        let pattern = format!(
+            "%{}%",
+            search
+                .replace('\\', "\\\\")
+                .replace('%', "\\%")
+                .replace('_', "\\_")
+        );
+        builder.push(" AND name ILIKE ").push_bind(pattern);
+    }
+
+    if let Some(widget_type) = query.widget_type {
+        // Layouts are stored as {"widgets": [...]}; older rows may be a bare array
+        let filter = json!([{ "type": widget_type.as_str() }]);
+        builder
+            .push(" AND (layout_json -> 'widgets' @> ")
+            .push_bind(filter.clone())
+            .push(" OR layout_json @> ")
+            .push_bind(filter)
+            .push(")");
+    }
+
+    if let Some(tag) = query.tag.as_deref().map(str::trim).filter(|tag| !tag.is_empty()) {
+        builder
+            .push(
+                " AND EXISTS (SELECT 1 FROM dashboard_tags dt JOIN tags t ON t.id = dt.tag_id \
+                 WHERE dt.dashboard_id = dashboards.id AND t.name = ",
+            )
+            .push_bind(tag.to_string())
+            .push(")");
+    }
+
+    match query.folder()? {
+        Some(Some(folder_id)) => {
+            builder.push(" AND folder_id = ").push_bind(folder_id);
+        }
+        Some(None) => {
+            builder.push(" AND folder_id IS NULL");
+        }
+        None => {}
+    }
+
+    if let Some(favorite) = query.favorite {
+        builder
+            .push(if favorite { " AND EXISTS" } else { " AND NOT EXISTS" })
+            .push(" (SELECT 1 FROM dashboard_favorites f WHERE f.dashboard_id = dashboards.id AND f.user_id = ")
+            .push_bind(user_id)
+            .push(")");
+    }
+
+    if let Some(pinned) = query.pinned {
+        builder
+            .push(if pinned { " AND EXISTS" } else { " AND NOT EXISTS" })
+            .push(
+                " (SELECT 1 FROM dashboard_favorites f \
+                 WHERE f.dashboard_id = dashboards.id AND f.pinned AND f.user_id = ",
+            )
+            .push_bind(user_id)
+            .push(")");
+    }
+
+    if let Some(shared) = query.shared {
+        builder
+            .push(if shared { " AND (" } else { " AND NOT (" })
+            .push(
+                "organization_id IS NOT NULL OR EXISTS \
+                 (SELECT 1 FROM dashboard_shares s WHERE s.dashboard_id = dashboards.id))",
+            );
+    }
+
+    Ok(())
+}
+
+/// Fill in the tags and favorite/pinned flags of dashboards about to be returned
+async fn annotate_dashboards(
+    state: &AppState,
+    user_id: Uuid,
+    dashboards: &mut [DashboardResponse],
+) -> Result<()> {
+    let ids: Vec<Uuid> = dashboards.iter().map(|dashboard| dashboard.id).collect();
+
+    let tags: Vec<(Uuid, String)> = sqlx::query_as(
+        "SELECT dt.dashboard_id, t.name 
+         FROM dashboard_tags dt 
+         JOIN tags t ON t.id = dt.tag_id 
+         WHERE dt.dashboard_id = ANY($1) 
+         ORDER BY t.name"
+    )
+    .bind(&ids)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let favorites: Vec<(Uuid, bool)> = sqlx::query_as(
+        "SELECT dashboard_id, pinned 
+         FROM dashboard_favorites 
+         WHERE user_id = $1 AND dashboard_id = ANY($2)"
+    )
+    .bind(user_id)
+    .bind(&ids)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let mut tags_by_dashboard: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (dashboard_id, name) in tags {
+        tags_by_dashboard.entry(dashboard_id).or_default().push(name);
+    }
+    let favorites: HashMap<Uuid, bool> = favorites.into_iter().collect();
+
+    for dashboard in dashboards.iter_mut() {
+        let pinned = favorites.get(&dashboard.id).copied();
+        dashboard.tags = Some(tags_by_dashboard.remove(&dashboard.id).unwrap_or_default());
+        dashboard.favorite = Some(pinned.is_some());
+        dashboard.pinned = Some(pinned.unwrap_or(false));
+    }
+
+    Ok(())
+}
+
+/// Get a specific dashboard the user owns or that is shared with them
 pub async fn get_dashboard(
     user_ctx: UserCtx,
     State(state): State<AppState>,
     Path(dashboard_id): Path<Uuid>,
 ) -> Result<impl IntoResponse> {
-    let dashboard: Option<Dashboard> = sqlx::query_as(
-        "SELECT id, user_id, name, layout_json, settings_json, created_at, updated_at 
-         FROM dashboards 
-         WHERE id = $1 AND user_id = $2"
-    )
-    .bind(dashboard_id)
-    .bind(user_ctx.user_id)
-    .fetch_optional(state.db.pool())
-    .await?;
+    let dashboard = find_readable_dashboard(&state, user_ctx.user_id, dashboard_id).await?;
 
-    let dashboard = dashboard.ok_or_else(|| AppError::NotFound("Dashboard not found".to_string()))?;
+    let mut response = [DashboardResponse::from(for_reader(dashboard, user_ctx.user_id))];
+    annotate_dashboards(&state, user_ctx.user_id, &mut response).await?;
+    let [response] = response;
 
-    Ok(Json(DashboardResponse::from(dashboard)))
+    Ok(Json(response))
 }
 
 /// Create a new dashboard
@@ -68,15 +259,17 @@ pub async fn create_dashboard(
         return Err(AppError::Validation("Dashboard name is required".to_string()));
     }
 
+    let (layout_json, settings_json) = validate_dashboard(payload.layout_json, payload.settings_json)?;
+
     let dashboard: Dashboard = sqlx::query_as(
         "INSERT INTO dashboards (user_id, name, layout_json, settings_json) 
          VALUES ($1, $2, $3, $4) 
-         RETURNING id, user_id, name, layout_json, settings_json, created_at, updated_at"
+         RETURNING id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at"
     )
     .bind(user_ctx.user_id)
     .bind(payload.name.trim())
-    .bind(payload.layout_json)
-    .bind(payload.settings_json)
+    .bind(layout_json)
+    .bind(settings_json)
     .fetch_one(state.db.pool())
     .await?;
 
@@ -95,9 +288,9 @@ pub async fn update_dashboard(
 ) -> Result<impl IntoResponse> {
     // Check if dashboard exists and belongs to user
     let existing: Option<Dashboard> = sqlx::query_as(
-        "SELECT id, user_id, name, layout_json, settings_json, created_at, updated_at 
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at 
          FROM dashboards 
-         WHERE id = $1 AND user_id = $2"
+         WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL"
     )
     .bind(dashboard_id)
     .bind(user_ctx.user_id)
@@ -106,16 +299,22 @@ pub async fn update_dashboard(
 
     let existing = existing.ok_or_else(|| AppError::NotFound("Dashboard not found".to_string()))?;
 
-    // Build update query dynamically
+    // Build update query dynamically; layout and settings are validated together
+    // since widget configs may reference variables defined in the settings
     let name = payload.name.unwrap_or(existing.name);
-    let layout_json = payload.layout_json.unwrap_or(existing.layout_json);
-    let settings_json = payload.settings_json.unwrap_or(existing.settings_json);
+    let (layout_json, settings_json) = match (payload.layout_json, payload.settings_json) {
+        (None, None) => (existing.layout_json, existing.settings_json),
+        (layout_json, settings_json) => validate_dashboard(
+            layout_json.unwrap_or(existing.layout_json),
+            settings_json.unwrap_or(existing.settings_json),
+        )?,
+    };
 
     let dashboard: Dashboard = sqlx::query_as(
         "UPDATE dashboards 
          SET name = $1, layout_json = $2, settings_json = $3, updated_at = NOW() 
          WHERE id = $4 
-         RETURNING id, user_id, name, layout_json, settings_json, created_at, updated_at"
+         RETURNING id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at"
     )
     .bind(name)
     .bind(layout_json)
@@ -127,14 +326,211 @@ pub async fn update_dashboard(
     Ok(Json(DashboardResponse::from(dashboard)))
 }
 
-/// Delete a dashboard
+/// Partially update a dashboard's layout and settings
+///
+/// Accepts either an RFC 6902 JSON Patch (`application/json-patch+json`) or an
+/// RFC 7386 Merge Patch (`application/merge-patch+json`). Both are applied to the
+/// document `{"layout_json": ..., "settings_json": ...}`, so paths look like
+/// `/layout_json/widgets/0/x`.
+pub async fn patch_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("")
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+
+    let mut tx = state.db.pool().begin().await?;
+
+    // Lock the row so concurrent patches are applied one after another
+    let existing: Option<Dashboard> = sqlx::query_as(
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at 
+         FROM dashboards 
+         WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL 
+         FOR UPDATE"
+    )
+    .bind(dashboard_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let existing = existing.ok_or_else(|| AppError::NotFound("Dashboard not found".to_string()))?;
+
+    let mut document = json!({
+        "layout_json": existing.layout_json,
+        "settings_json": existing.settings_json,
+    });
+
+    match content_type.as_str() {
+        "application/json-patch+json" => {
+            let patch: json_patch::Patch = serde_json::from_slice(&body)
+                .map_err(|e| AppError::Validation(format!("Invalid JSON Patch document: {}", e)))?;
+            json_patch::patch(&mut document, &patch)
+                .map_err(|e| AppError::Validation(format!("JSON Patch {}", e)))?;
+        }
+        "application/merge-patch+json" => {
+            let patch: JsonValue = serde_json::from_slice(&body)
+                .map_err(|e| AppError::Validation(format!("Invalid Merge Patch document: {}", e)))?;
+            json_patch::merge(&mut document, &patch);
+        }
+        _ => {
+            return Err(AppError::Validation(
+                "Content-Type must be application/json-patch+json or application/merge-patch+json".to_string(),
+            ));
+        }
+    }
+
+    let (layout_json, settings_json) = split_patched_document(document)?;
+    let (layout_json, settings_json) = validate_dashboard(layout_json, settings_json)?;
+
+    let dashboard: Dashboard = sqlx::query_as(
+        "UPDATE dashboards 
+         SET layout_json = $1, settings_json = $2, updated_at = NOW() 
+         WHERE id = $3 
+         RETURNING id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at"
+    )
+    .bind(layout_json)
+    .bind(settings_json)
+    .bind(dashboard_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(DashboardResponse::from(dashboard)))
+}
+
+/// Split a patched `{"layout_json", "settings_json"}` document back into its parts
+fn split_patched_document(document: JsonValue) -> Result<(JsonValue, JsonValue)> {
+    let JsonValue::Object(mut fields) = document else {
+        return Err(AppError::Validation("Patch must not replace the whole document".to_string()));
+    };
+
+    let layout_json = fields
+        .remove("layout_json")
+        .filter(|value| !value.is_null())
+        .ok_or_else(|| AppError::Validation("Patch must not remove layout_json".to_string()))?;
+    let settings_json = fields
+        .remove("settings_json")
+        .filter(|value| !value.is_null())
+        .ok_or_else(|| AppError::Validation("Patch must not remove settings_json".to_string()))?;
+
+    if let Some(key) = fields.keys().next() {
+        return Err(AppError::Validation(format!(
+            "Patch may only modify layout_json and settings_json, not '{}'",
+            key
+        )));
+    }
+
+    Ok((layout_json, settings_json))
+}
+
+/// Clone a dashboard the user owns or that is shared with them, giving the copy fresh
+/// widget ids and recording its source; the copy may be placed in one of the user's organizations
+pub async fn clone_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    payload: Option<Json<CloneDashboardRequest>>,
+) -> Result<impl IntoResponse> {
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+
+    let source = find_readable_dashboard(&state, user_ctx.user_id, dashboard_id).await?;
+    // Copies of someone else's dashboard don't carry over their credentials
+    let source = for_reader(source, user_ctx.user_id);
+
+    if let Some(organization_id) = payload.organization_id {
+        if member_role(&state, organization_id, user_ctx.user_id).await?.is_none() {
+            return Err(AppError::NotFound("Organization not found".to_string()));
+        }
+    }
+
+    let name = payload
+        .name
+        .unwrap_or_else(|| format!("{} (copy)", source.name));
+    if name.trim().is_empty() {
+        return Err(AppError::Validation("Dashboard name is required".to_string()));
+    }
+
+    let mut layout_json = source.layout_json;
+    regenerate_widget_ids(&mut layout_json);
+
+    let dashboard: Dashboard = sqlx::query_as(
+"INSERT INTO dashboards (user_id, name, layout_json, settings_json, source_dashboard_id, organization_id) 
+         VALUES ($1, $2, $3, $4, $5, $6) 
+         RETURNING id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at"
+    )
+    .bind(user_ctx.user_id)
+    .bind(name.trim())
+    .bind(layout_json)
+    .bind(source.settings_json)
+    .bind(source.id)
+    .bind(payload.organization_id)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(DashboardResponse::from(dashboard)),
+    ))
+}
+
+/// Load a dashboard owned by the user, excluding trashed dashboards
+pub(crate) async fn find_dashboard(state: &AppState, user_id: Uuid, dashboard_id: Uuid) -> Result<Dashboard> {
+    let dashboard: Option<Dashboard> = sqlx::query_as(
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at 
+         FROM dashboards 
+         WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL"
+    )
+    .bind(dashboard_id)
+    .bind(user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    dashboard.ok_or_else(|| AppError::NotFound("Dashboard not found".to_string()))
+}
+
+/// Load a dashboard the user owns, or that is shared with them directly or through an
+/// organization they belong to, excluding trashed dashboards
+pub(crate) async fn find_readable_dashboard(
+    state: &AppState,
+    user_id: Uuid,
+    dashboard_id: Uuid,
+) -> Result<Dashboard> {
+    let dashboard: Option<Dashboard> = sqlx::query_as(
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at 
+         FROM dashboards 
+         WHERE id = $1 AND deleted_at IS NULL 
+           AND (user_id = $2
+                OR EXISTS (SELECT 1 FROM dashboard_shares s WHERE s.dashboard_id = dashboards.id AND s.user_id = $2)
+                OR organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = $2))"
+    )
+    .bind(dashboard_id)
+    .bind(user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    dashboard.ok_or_else(|| AppError::NotFound("Dashboard not found".to_string()))
+}
+
+/// Move a dashboard to the trash
 pub async fn delete_dashboard(
     user_ctx: UserCtx,
     State(state): State<AppState>,
     Path(dashboard_id): Path<Uuid>,
 ) -> Result<impl IntoResponse> {
     let result = sqlx::query(
-        "DELETE FROM dashboards WHERE id = $1 AND user_id = $2"
+        "UPDATE dashboards SET deleted_at = NOW() 
+         WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL"
     )
     .bind(dashboard_id)
     .bind(user_ctx.user_id)
@@ -147,3 +543,68 @@ pub async fn delete_dashboard(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+/// List dashboards in the trash, most recently deleted first
+pub async fn list_trash(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let dashboards: Vec<Dashboard> = sqlx::query_as(
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at 
+         FROM dashboards 
+         WHERE user_id = $1 AND deleted_at IS NOT NULL 
+         ORDER BY deleted_at DESC"
+    )
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let response: Vec<DashboardResponse> = dashboards
+        .into_iter()
+        .map(DashboardResponse::from)
+        .collect();
+
+    Ok(Json(response))
+}
+
+/// Restore a dashboard from the trash
+pub async fn restore_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let dashboard: Option<Dashboard> = sqlx::query_as(
+        "UPDATE dashboards SET deleted_at = NULL 
+         WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL 
+         RETURNING id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at"
+    )
+    .bind(dashboard_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let dashboard = dashboard.ok_or_else(|| AppError::NotFound("Dashboard not found in trash".to_string()))?;
+
+    Ok(Json(DashboardResponse::from(dashboard)))
+}
+
+/// Permanently delete a dashboard that is already in the trash
+pub async fn purge_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "DELETE FROM dashboards WHERE id = $1 AND user_id = $2 AND deleted_at IS NOT NULL"
+    )
+    .bind(dashboard_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Dashboard not found in trash".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,174 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use sqlx::Connection;
+use uuid::Uuid;
+
+use crate::{
+    auth::{AdminCtx, UserCtx},
+    error::{AppError, Result},
+    models::{validate_connection_string, CreateDataSourceRequest, DataSource, UpdateDataSourceRequest},
+    secrets,
+    widgets::sql::connect,
+    AppState,
+};
+
+pub(crate) const DATA_SOURCE_COLUMNS: &str =
+    "id, name, description, connection_encrypted, created_by, created_at, updated_at";
+
+/// List registered data sources (connection strings are never returned)
+pub async fn list_data_sources(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let sources: Vec<DataSource> = sqlx::query_as(&format!(
+        "SELECT {} FROM data_sources ORDER BY name",
+        DATA_SOURCE_COLUMNS
+    ))
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(sources))
+}
+
+/// Register a data source (admins only)
+pub async fn create_data_source(
+    admin_ctx: AdminCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateDataSourceRequest>,
+) -> Result<impl IntoResponse> {
+    let name = payload.name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Data source name is required".to_string()));
+    }
+
+    validate_connection_string(&payload.connection_string)?;
+    let connection_encrypted = secrets::encrypt(&state.config, &payload.connection_string)?;
+
+    let source: Option<DataSource> = sqlx::query_as(&format!(
+        "INSERT INTO data_sources (name, description, connection_encrypted, created_by)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (name) DO NOTHING
+         RETURNING {}",
+        DATA_SOURCE_COLUMNS
+    ))
+    .bind(name)
+    .bind(&payload.description)
+    .bind(connection_encrypted)
+    .bind(admin_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let source = source.ok_or_else(|| {
+        AppError::Validation(format!("Data source '{}' already exists", name))
+    })?;
+
+    Ok((StatusCode::CREATED, Json(source)))
+}
+
+/// Rename a data source, change its description or replace its connection string (admins only)
+pub async fn update_data_source(
+    _admin_ctx: AdminCtx,
+    State(state): State<AppState>,
+    Path(source_id): Path<Uuid>,
+    Json(payload): Json<UpdateDataSourceRequest>,
+) -> Result<impl IntoResponse> {
+    let existing = find_data_source(&state, source_id).await?;
+
+    let name = match payload.name {
+        Some(name) if name.trim().is_empty() => {
+            return Err(AppError::Validation("Data source name is required".to_string()));
+        }
+        Some(name) => name.trim().to_string(),
+        None => existing.name,
+    };
+
+    let description = payload.description.unwrap_or(existing.description);
+
+    let connection_encrypted = match payload.connection_string {
+        Some(connection_string) => {
+            validate_connection_string(&connection_string)?;
+            secrets::encrypt(&state.config, &connection_string)?
+        }
+        None => existing.connection_encrypted,
+    };
+
+    let (taken,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM data_sources WHERE name = $1 AND id <> $2)"
+    )
+    .bind(&name)
+    .bind(source_id)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    if taken {
+        return Err(AppError::Validation(format!("Data source '{}' already exists", name)));
+    }
+
+    let source: DataSource = sqlx::query_as(&format!(
+        "UPDATE data_sources
+         SET name = $1, description = $2, connection_encrypted = $3, updated_at = NOW()
+         WHERE id = $4
+         RETURNING {}",
+        DATA_SOURCE_COLUMNS
+    ))
+    .bind(&name)
+    .bind(description)
+    .bind(connection_encrypted)
+    .bind(source_id)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok(Json(source))
+}
+
+/// Delete a data source; SQL widgets using it start failing (admins only)
+pub async fn delete_data_source(
+    _admin_ctx: AdminCtx,
+    State(state): State<AppState>,
+    Path(source_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query("DELETE FROM data_sources WHERE id = $1")
+        .bind(source_id)
+        .execute(state.db.pool())
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Data source not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Check that a data source accepts connections (admins only)
+pub async fn test_data_source(
+    _admin_ctx: AdminCtx,
+    State(state): State<AppState>,
+    Path(source_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let source = find_data_source(&state, source_id).await?;
+
+    let mut conn = connect(&state, &source).await?;
+    conn.ping()
+        .await
+        .map_err(|e| AppError::ExternalApi(format!("Data source '{}' did not respond: {}", source.name, e)))?;
+    let _ = conn.close().await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Look up a data source by ID
+pub(crate) async fn find_data_source(state: &AppState, source_id: Uuid) -> Result<DataSource> {
+    let source: Option<DataSource> = sqlx::query_as(&format!(
+        "SELECT {} FROM data_sources WHERE id = $1",
+        DATA_SOURCE_COLUMNS
+    ))
+    .bind(source_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    source.ok_or_else(|| AppError::NotFound("Data source not found".to_string()))
+}
@@ -0,0 +1,161 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use std::io::{Cursor, Write};
+use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    models::{
+        validate_dashboard, Dashboard, DashboardDocument, DashboardResponse,
+        ExportQuery, ImportQuery,
+    },
+    AppState,
+};
+
+/// Export a dashboard as a portable JSON or YAML document
+pub async fn export_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse> {
+    let dashboard: Option<Dashboard> = sqlx::query_as(
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at
+         FROM dashboards
+         WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL"
+    )
+    .bind(dashboard_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let dashboard = dashboard.ok_or_else(|| AppError::NotFound("Dashboard not found".to_string()))?;
+
+    let filename = format!("{}.{}", file_stem(&dashboard), query.format.extension());
+    let body = DashboardDocument::from_dashboard(dashboard).render(query.format)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, query.format.content_type().to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    ))
+}
+
+/// Export every dashboard the user owns as a zip archive of documents
+pub async fn export_all_dashboards(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Query(query): Query<ExportQuery>,
+) -> Result<impl IntoResponse> {
+    let dashboards: Vec<Dashboard> = sqlx::query_as(
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at
+         FROM dashboards
+         WHERE user_id = $1 AND deleted_at IS NULL
+         ORDER BY created_at"
+    )
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let mut archive = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default();
+
+    for dashboard in dashboards {
+        let filename = format!("{}.{}", file_stem(&dashboard), query.format.extension());
+        let body = DashboardDocument::from_dashboard(dashboard).render(query.format)?;
+
+        archive
+            .start_file(filename, options)
+            .and_then(|_| archive.write_all(body.as_bytes()).map_err(Into::into))
+            .map_err(|e| AppError::Internal(format!("Failed to write export archive: {}", e)))?;
+    }
+
+    let bytes = archive
+        .finish()
+        .map_err(|e| AppError::Internal(format!("Failed to write export archive: {}", e)))?
+        .into_inner();
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/zip".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"dashboards.zip\"".to_string(),
+            ),
+        ],
+        bytes,
+    ))
+}
+
+/// Import a dashboard document (JSON or YAML) as a new dashboard
+pub async fn import_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Query(query): Query<ImportQuery>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    let is_yaml = content_type.contains("yaml");
+
+    let document = DashboardDocument::parse(&body, is_yaml)?;
+
+    let name = query.name.unwrap_or(document.dashboard.name);
+    if name.trim().is_empty() {
+        return Err(AppError::Validation("Dashboard name is required".to_string()));
+    }
+
+    let (layout_json, settings_json) =
+        validate_dashboard(document.dashboard.layout, document.dashboard.settings)?;
+
+    let dashboard: Dashboard = sqlx::query_as(
+        "INSERT INTO dashboards (user_id, name, layout_json, settings_json)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at"
+    )
+    .bind(user_ctx.user_id)
+    .bind(name.trim())
+    .bind(layout_json)
+    .bind(settings_json)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(DashboardResponse::from(dashboard)),
+    ))
+}
+
+/// File name for an exported dashboard: a slug of its name plus its id
+fn file_stem(dashboard: &Dashboard) -> String {
+    let slug: String = dashboard
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if slug.is_empty() {
+        dashboard.id.to_string()
+    } else {
+        format!("{}-{}", slug, dashboard.id)
+    }
+}
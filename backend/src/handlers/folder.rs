@@ -0,0 +1,202 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    models::{
+        CreateFolderRequest, Dashboard, DashboardResponse, Folder, MoveDashboardRequest,
+        UpdateFolderRequest,
+    },
+    AppState,
+};
+
+/// List all folders for the authenticated user (flat; nest them by `parent_id`)
+pub async fn list_folders(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let folders: Vec<Folder> = sqlx::query_as(
+        "SELECT id, user_id, parent_id, name, created_at, updated_at
+         FROM folders
+         WHERE user_id = $1
+         ORDER BY name"
+    )
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(folders))
+}
+
+/// Create a new folder
+pub async fn create_folder(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateFolderRequest>,
+) -> Result<impl IntoResponse> {
+    if payload.name.trim().is_empty() {
+        return Err(AppError::Validation("Folder name is required".to_string()));
+    }
+
+    if let Some(parent_id) = payload.parent_id {
+        ensure_folder_owned(&state, user_ctx.user_id, parent_id).await?;
+    }
+
+    let folder: Folder = sqlx::query_as(
+        "INSERT INTO folders (user_id, parent_id, name)
+         VALUES ($1, $2, $3)
+         RETURNING id, user_id, parent_id, name, created_at, updated_at"
+    )
+    .bind(user_ctx.user_id)
+    .bind(payload.parent_id)
+    .bind(payload.name.trim())
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(folder)))
+}
+
+/// Rename a folder or move it under another parent
+pub async fn update_folder(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(folder_id): Path<Uuid>,
+    Json(payload): Json<UpdateFolderRequest>,
+) -> Result<impl IntoResponse> {
+    let existing: Option<Folder> = sqlx::query_as(
+        "SELECT id, user_id, parent_id, name, created_at, updated_at
+         FROM folders
+         WHERE id = $1 AND user_id = $2"
+    )
+    .bind(folder_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let existing = existing.ok_or_else(|| AppError::NotFound("Folder not found".to_string()))?;
+
+    let name = match payload.name {
+        Some(name) if name.trim().is_empty() => {
+            return Err(AppError::Validation("Folder name is required".to_string()));
+        }
+        Some(name) => name.trim().to_string(),
+        None => existing.name,
+    };
+
+    let parent_id = match payload.parent_id {
+        Some(Some(parent_id)) => {
+            ensure_folder_owned(&state, user_ctx.user_id, parent_id).await?;
+
+            // Refuse to move a folder below itself or one of its descendants
+            let (creates_cycle,): (bool,) = sqlx::query_as(
+                "WITH RECURSIVE ancestors AS (
+                     SELECT id, parent_id FROM folders WHERE id = $1
+                     UNION ALL
+                     SELECT f.id, f.parent_id FROM folders f JOIN ancestors a ON f.id = a.parent_id
+                 )
+                 SELECT EXISTS (SELECT 1 FROM ancestors WHERE id = $2)"
+            )
+            .bind(parent_id)
+            .bind(folder_id)
+            .fetch_one(state.db.pool())
+            .await?;
+
+            if creates_cycle {
+                return Err(AppError::Validation(
+                    "A folder cannot be moved into itself or one of its subfolders".to_string(),
+                ));
+            }
+
+            Some(parent_id)
+        }
+        Some(None) => None,
+        None => existing.parent_id,
+    };
+
+    let folder: Folder = sqlx::query_as(
+        "UPDATE folders
+         SET name = $1, parent_id = $2, updated_at = NOW()
+         WHERE id = $3
+         RETURNING id, user_id, parent_id, name, created_at, updated_at"
+    )
+    .bind(name)
+    .bind(parent_id)
+    .bind(folder_id)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok(Json(folder))
+}
+
+/// Delete a folder; its dashboards and subfolders move to the root
+pub async fn delete_folder(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(folder_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    // dashboards.folder_id and folders.parent_id are ON DELETE SET NULL
+    let result = sqlx::query(
+        "DELETE FROM folders WHERE id = $1 AND user_id = $2"
+    )
+    .bind(folder_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Folder not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Move a dashboard into a folder (or back to the root)
+pub async fn move_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    Json(payload): Json<MoveDashboardRequest>,
+) -> Result<impl IntoResponse> {
+    if let Some(folder_id) = payload.folder_id {
+        ensure_folder_owned(&state, user_ctx.user_id, folder_id).await?;
+    }
+
+    let dashboard: Option<Dashboard> = sqlx::query_as(
+        "UPDATE dashboards
+         SET folder_id = $1, updated_at = NOW()
+         WHERE id = $2 AND user_id = $3 AND deleted_at IS NULL
+         RETURNING id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at"
+    )
+    .bind(payload.folder_id)
+    .bind(dashboard_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let dashboard = dashboard.ok_or_else(|| AppError::NotFound("Dashboard not found".to_string()))?;
+
+    Ok(Json(DashboardResponse::from(dashboard)))
+}
+
+/// Fail with 404 unless the folder exists and belongs to the user
+async fn ensure_folder_owned(state: &AppState, user_id: Uuid, folder_id: Uuid) -> Result<()> {
+    let (exists,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM folders WHERE id = $1 AND user_id = $2)"
+    )
+    .bind(folder_id)
+    .bind(user_id)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    if !exists {
+        return Err(AppError::NotFound("Folder not found".to_string()));
+    }
+
+    Ok(())
+}
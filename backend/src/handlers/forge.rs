@@ -0,0 +1,153 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    models::{forge_base_url, CreateForgeAccountRequest, ForgeAccount, UpdateForgeAccountRequest},
+    secrets,
+    AppState,
+};
+
+const FORGE_ACCOUNT_COLUMNS: &str =
+    "id, user_id, name, forge, base_url, token_encrypted, created_at, updated_at";
+
+/// List the user's forge accounts (tokens are never returned)
+pub async fn list_forge_accounts(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let accounts: Vec<ForgeAccount> = sqlx::query_as(&format!(
+        "SELECT {} FROM forge_accounts WHERE user_id = $1 ORDER BY name",
+        FORGE_ACCOUNT_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(accounts))
+}
+
+/// Store a token for a GitHub, GitLab or Gitea instance
+pub async fn create_forge_account(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateForgeAccountRequest>,
+) -> Result<impl IntoResponse> {
+    if payload.name.trim().is_empty() {
+        return Err(AppError::Validation("Account name is required".to_string()));
+    }
+    if payload.token.trim().is_empty() {
+        return Err(AppError::Validation("Token is required".to_string()));
+    }
+
+    let base_url = forge_base_url(payload.forge, payload.base_url.as_deref())?;
+    let token_encrypted = secrets::encrypt(&state.config, payload.token.trim())?;
+
+    let account: ForgeAccount = sqlx::query_as(&format!(
+        "INSERT INTO forge_accounts (user_id, name, forge, base_url, token_encrypted)
+         VALUES ($1, $2, $3, $4, $5)
+         RETURNING {}",
+        FORGE_ACCOUNT_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .bind(payload.name.trim())
+    .bind(payload.forge)
+    .bind(base_url)
+    .bind(token_encrypted)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(account)))
+}
+
+/// Rename a forge account, move it to another base URL or replace its token
+pub async fn update_forge_account(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+    Json(payload): Json<UpdateForgeAccountRequest>,
+) -> Result<impl IntoResponse> {
+    let existing = find_forge_account(&state, user_ctx.user_id, account_id).await?;
+
+    let name = match payload.name {
+        Some(name) if name.trim().is_empty() => {
+            return Err(AppError::Validation("Account name is required".to_string()));
+        }
+        Some(name) => name.trim().to_string(),
+        None => existing.name,
+    };
+
+    let base_url = match payload.base_url {
+        Some(base_url) => forge_base_url(existing.forge, Some(&base_url))?,
+        None => existing.base_url,
+    };
+
+    let token_encrypted = match payload.token {
+        Some(token) if token.trim().is_empty() => {
+            return Err(AppError::Validation("Token is required".to_string()));
+        }
+        Some(token) => secrets::encrypt(&state.config, token.trim())?,
+        None => existing.token_encrypted,
+    };
+
+    let account: ForgeAccount = sqlx::query_as(&format!(
+        "UPDATE forge_accounts
+         SET name = $1, base_url = $2, token_encrypted = $3, updated_at = NOW()
+         WHERE id = $4
+         RETURNING {}",
+        FORGE_ACCOUNT_COLUMNS
+    ))
+    .bind(name)
+    .bind(base_url)
+    .bind(token_encrypted)
+    .bind(account_id)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok(Json(account))
+}
+
+/// Delete a forge account; activity sources using it start failing
+pub async fn delete_forge_account(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(account_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "DELETE FROM forge_accounts WHERE id = $1 AND user_id = $2"
+    )
+    .bind(account_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Forge account not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Look up one of the user's forge accounts
+pub(crate) async fn find_forge_account(
+    state: &AppState,
+    user_id: Uuid,
+    account_id: Uuid,
+) -> Result<ForgeAccount> {
+    let account: Option<ForgeAccount> = sqlx::query_as(&format!(
+        "SELECT {} FROM forge_accounts WHERE id = $1 AND user_id = $2",
+        FORGE_ACCOUNT_COLUMNS
+    ))
+    .bind(account_id)
+    .bind(user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    account.ok_or_else(|| AppError::NotFound("Forge account not found".to_string()))
+}
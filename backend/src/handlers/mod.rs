@@ -1,7 +1,18 @@
 pub mod health;
 pub mod auth;
 pub mod dashboard;
-
-pub use health::*;
-pub use auth::*;
-pub use dashboard::*;
+pub mod export;
+pub mod template;
+pub mod folder;
+pub mod tag;
+pub mod widget_data;
+pub mod snapshot;
+pub mod monitor;
+pub mod channel;
+pub mod alert;
+pub mod report;
+pub mod push;
+pub mod data_source;
+pub mod forge;
+pub mod organization;
+pub mod share;
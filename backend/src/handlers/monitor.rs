@@ -0,0 +1,285 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    config::Config,
+    egress::check_url,
+    error::{AppError, FieldError, Result},
+    models::{
+        parse_windows, validate_monitor, CreateMonitorRequest, Incident, Monitor, MonitorCheck,
+        MonitorChecksQuery, SlaQuery, SlaResponse, SlaWindow, UpdateMonitorRequest,
+        DEFAULT_PAGE_SIZE, DEFAULT_SLA_WINDOWS, MAX_PAGE_SIZE,
+    },
+    AppState,
+};
+
+const MONITOR_COLUMNS: &str = "id, user_id, name, url, interval_seconds, expected_status, timeout_ms, \
+     enabled, last_is_up, last_checked_at, next_check_at, created_at, updated_at";
+
+/// List all monitors for the authenticated user
+pub async fn list_monitors(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let monitors: Vec<Monitor> = sqlx::query_as(&format!(
+        "SELECT {} FROM monitors WHERE user_id = $1 ORDER BY name",
+        MONITOR_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(monitors))
+}
+
+/// Get a monitor by ID
+pub async fn get_monitor(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(monitor_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let monitor = find_monitor(&state, user_ctx.user_id, monitor_id).await?;
+    Ok(Json(monitor))
+}
+
+/// Create a monitor; its first check runs on the worker's next tick
+pub async fn create_monitor(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateMonitorRequest>,
+) -> Result<impl IntoResponse> {
+    validate_monitor(
+        &payload.name,
+        &payload.url,
+        payload.interval_seconds,
+        payload.expected_status,
+        payload.timeout_ms,
+    )?;
+    check_monitor_url(&state.config, &payload.url).await?;
+
+    let monitor: Monitor = sqlx::query_as(&format!(
+        "INSERT INTO monitors (user_id, name, url, interval_seconds, expected_status, timeout_ms, enabled)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)
+         RETURNING {}",
+        MONITOR_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .bind(payload.name.trim())
+    .bind(payload.url.trim())
+    .bind(payload.interval_seconds)
+    .bind(payload.expected_status)
+    .bind(payload.timeout_ms)
+    .bind(payload.enabled)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(monitor)))
+}
+
+/// Update a monitor
+pub async fn update_monitor(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(monitor_id): Path<Uuid>,
+    Json(payload): Json<UpdateMonitorRequest>,
+) -> Result<impl IntoResponse> {
+    let existing = find_monitor(&state, user_ctx.user_id, monitor_id).await?;
+
+    let name = payload.name.unwrap_or(existing.name);
+    let url = payload.url.unwrap_or(existing.url);
+    let interval_seconds = payload.interval_seconds.unwrap_or(existing.interval_seconds);
+    let expected_status = payload.expected_status.unwrap_or(existing.expected_status);
+    let timeout_ms = payload.timeout_ms.unwrap_or(existing.timeout_ms);
+    let enabled = payload.enabled.unwrap_or(existing.enabled);
+
+    validate_monitor(&name, &url, interval_seconds, expected_status, timeout_ms)?;
+    check_monitor_url(&state.config, &url).await?;
+
+    let monitor: Monitor = sqlx::query_as(&format!(
+        "UPDATE monitors
+         SET name = $1, url = $2, interval_seconds = $3, expected_status = $4, timeout_ms = $5,
+             enabled = $6, next_check_at = LEAST(next_check_at, NOW() + make_interval(secs => $3)),
+             updated_at = NOW()
+         WHERE id = $7
+         RETURNING {}",
+        MONITOR_COLUMNS
+    ))
+    .bind(name.trim())
+    .bind(url.trim())
+    .bind(interval_seconds)
+    .bind(expected_status)
+    .bind(timeout_ms)
+    .bind(enabled)
+    .bind(monitor_id)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok(Json(monitor))
+}
+
+/// Delete a monitor along with its history
+pub async fn delete_monitor(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(monitor_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "DELETE FROM monitors WHERE id = $1 AND user_id = $2"
+    )
+    .bind(monitor_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Monitor not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List a monitor's check history, newest first
+pub async fn list_monitor_checks(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(monitor_id): Path<Uuid>,
+    Query(query): Query<MonitorChecksQuery>,
+) -> Result<impl IntoResponse> {
+    find_monitor(&state, user_ctx.user_id, monitor_id).await?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    let checks: Vec<MonitorCheck> = sqlx::query_as(
+        "SELECT id, checked_at, is_up, status_code, response_time_ms, error
+         FROM monitor_checks
+         WHERE monitor_id = $1 AND ($2::timestamptz IS NULL OR checked_at < $2)
+         ORDER BY checked_at DESC
+         LIMIT $3"
+    )
+    .bind(monitor_id)
+    .bind(query.before)
+    .bind(limit)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(checks))
+}
+
+/// List a monitor's incidents, newest first
+pub async fn list_monitor_incidents(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(monitor_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    find_monitor(&state, user_ctx.user_id, monitor_id).await?;
+
+    let incidents: Vec<Incident> = sqlx::query_as(
+        "SELECT id, monitor_id, started_at, resolved_at, cause
+         FROM monitor_incidents
+         WHERE monitor_id = $1
+         ORDER BY started_at DESC
+         LIMIT $2"
+    )
+    .bind(monitor_id)
+    .bind(MAX_PAGE_SIZE)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(incidents))
+}
+
+/// SLA figures (uptime, incidents, downtime) for windows ending now, e.g. `?windows=24h,7d,30d`
+pub async fn get_monitor_sla(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(monitor_id): Path<Uuid>,
+    Query(query): Query<SlaQuery>,
+) -> Result<impl IntoResponse> {
+    find_monitor(&state, user_ctx.user_id, monitor_id).await?;
+
+    let requested = parse_windows(query.windows.as_deref().unwrap_or(DEFAULT_SLA_WINDOWS))?;
+    let now = Utc::now();
+    let mut windows = Vec::new();
+
+    for (window, duration) in requested {
+        let from = now - duration;
+
+        let (checks, failed_checks, avg_response_time_ms): (i64, i64, Option<f64>) = sqlx::query_as(
+            "SELECT COUNT(*), COUNT(*) FILTER (WHERE NOT is_up), AVG(response_time_ms)::float8
+             FROM monitor_checks
+             WHERE monitor_id = $1 AND checked_at >= $2 AND checked_at <= $3"
+        )
+        .bind(monitor_id)
+        .bind(from)
+        .bind(now)
+        .fetch_one(state.db.pool())
+        .await?;
+
+        // Incidents are clipped to the window, open ones count until now
+        let (incidents, downtime_seconds): (i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(EXTRACT(EPOCH FROM
+                        LEAST(COALESCE(resolved_at, $3), $3) - GREATEST(started_at, $2)
+                    )), 0)::bigint
+             FROM monitor_incidents
+             WHERE monitor_id = $1 AND started_at <= $3 AND (resolved_at IS NULL OR resolved_at > $2)"
+        )
+        .bind(monitor_id)
+        .bind(from)
+        .bind(now)
+        .fetch_one(state.db.pool())
+        .await?;
+
+        let uptime_percentage = (checks > 0)
+            .then(|| (checks - failed_checks) as f64 / checks as f64 * 100.0);
+
+        windows.push(SlaWindow {
+            window,
+            from,
+            uptime_percentage,
+            checks,
+            failed_checks,
+            incidents,
+            downtime_seconds,
+            avg_response_time_ms,
+        });
+    }
+
+    Ok(Json(SlaResponse { monitor_id, windows }))
+}
+
+/// Refuse monitor URLs the egress guard would block; hosts that don't resolve yet are left to the checks
+async fn check_monitor_url(config: &Config, url: &str) -> Result<()> {
+    let Ok(url) = Url::parse(url.trim()) else {
+        return Ok(());
+    };
+
+    match check_url(config, &url).await {
+        Err(AppError::Validation(message)) => {
+            Err(AppError::InvalidFields(vec![FieldError::new("url", message)]))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Load a monitor owned by the user
+async fn find_monitor(state: &AppState, user_id: Uuid, monitor_id: Uuid) -> Result<Monitor> {
+    let monitor: Option<Monitor> = sqlx::query_as(&format!(
+        "SELECT {} FROM monitors WHERE id = $1 AND user_id = $2",
+        MONITOR_COLUMNS
+    ))
+    .bind(monitor_id)
+    .bind(user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    monitor.ok_or_else(|| AppError::NotFound("Monitor not found".to_string()))
+}
@@ -0,0 +1,227 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    models::{
+        AddMemberRequest, CreateOrganizationRequest, Organization, OrganizationMember,
+        OrganizationRole,
+    },
+    AppState,
+};
+
+/// List the organizations the authenticated user belongs to
+pub async fn list_organizations(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let organizations: Vec<Organization> = sqlx::query_as(
+        "SELECT o.id, o.name, m.role, o.created_at
+         FROM organizations o
+         JOIN organization_members m ON m.organization_id = o.id
+         WHERE m.user_id = $1
+         ORDER BY o.name"
+    )
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(organizations))
+}
+
+/// Create an organization owned by the authenticated user
+pub async fn create_organization(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateOrganizationRequest>,
+) -> Result<impl IntoResponse> {
+    if payload.name.trim().is_empty() {
+        return Err(AppError::Validation("Organization name is required".to_string()));
+    }
+
+    let mut tx = state.db.pool().begin().await?;
+
+    let (id, name, created_at): (Uuid, String, chrono::DateTime<chrono::Utc>) = sqlx::query_as(
+        "INSERT INTO organizations (name) VALUES ($1) RETURNING id, name, created_at"
+    )
+    .bind(payload.name.trim())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO organization_members (organization_id, user_id, role) VALUES ($1, $2, $3)"
+    )
+    .bind(id)
+    .bind(user_ctx.user_id)
+    .bind(OrganizationRole::Owner)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(Organization { id, name, role: OrganizationRole::Owner, created_at }),
+    ))
+}
+
+/// List an organization's members; any member may look
+pub async fn list_members(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(organization_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    require_role(&state, organization_id, user_ctx.user_id, OrganizationRole::Member).await?;
+
+    let members: Vec<OrganizationMember> = sqlx::query_as(
+        "SELECT m.user_id, u.email, m.role, m.created_at
+         FROM organization_members m
+         JOIN users u ON u.id = m.user_id
+         WHERE m.organization_id = $1
+         ORDER BY u.email"
+    )
+    .bind(organization_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(members))
+}
+
+/// Add a registered user to an organization, or change their role (owners only)
+pub async fn add_member(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(organization_id): Path<Uuid>,
+    Json(payload): Json<AddMemberRequest>,
+) -> Result<impl IntoResponse> {
+    require_role(&state, organization_id, user_ctx.user_id, OrganizationRole::Owner).await?;
+
+    let user_id = find_user_id(&state, &payload.email).await?;
+
+    let mut tx = state.db.pool().begin().await?;
+
+    let member: OrganizationMember = sqlx::query_as(
+        "WITH upserted AS (
+             INSERT INTO organization_members (organization_id, user_id, role)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (organization_id, user_id) DO UPDATE SET role = EXCLUDED.role
+             RETURNING user_id, role, created_at
+         )
+         SELECT upserted.user_id, u.email, upserted.role, upserted.created_at
+         FROM upserted JOIN users u ON u.id = upserted.user_id"
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .bind(payload.role)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    ensure_owner_remains(&mut tx, organization_id).await?;
+    tx.commit().await?;
+
+    Ok(Json(member))
+}
+
+/// Remove a member; owners may remove anyone, members only themselves
+pub async fn remove_member(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path((organization_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse> {
+    let required = if user_id == user_ctx.user_id {
+        OrganizationRole::Member
+    } else {
+        OrganizationRole::Owner
+    };
+    require_role(&state, organization_id, user_ctx.user_id, required).await?;
+
+    let mut tx = state.db.pool().begin().await?;
+
+    let result = sqlx::query(
+        "DELETE FROM organization_members WHERE organization_id = $1 AND user_id = $2"
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .execute(&mut *tx)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Member not found".to_string()));
+    }
+
+    ensure_owner_remains(&mut tx, organization_id).await?;
+    tx.commit().await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// The user's role in an organization, if they belong to it
+pub(crate) async fn member_role(
+    state: &AppState,
+    organization_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<OrganizationRole>> {
+    let role: Option<(OrganizationRole,)> = sqlx::query_as(
+        "SELECT role FROM organization_members WHERE organization_id = $1 AND user_id = $2"
+    )
+    .bind(organization_id)
+    .bind(user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    Ok(role.map(|(role,)| role))
+}
+
+/// Fail unless the user belongs to the organization with at least `required` rights
+async fn require_role(
+    state: &AppState,
+    organization_id: Uuid,
+    user_id: Uuid,
+    required: OrganizationRole,
+) -> Result<()> {
+    match member_role(state, organization_id, user_id).await? {
+        None => Err(AppError::NotFound("Organization not found".to_string())),
+        Some(OrganizationRole::Member) if required == OrganizationRole::Owner => Err(AppError::Forbidden),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Look up a registered user by email
+pub(crate) async fn find_user_id(state: &AppState, email: &str) -> Result<Uuid> {
+    let user: Option<(Uuid,)> = sqlx::query_as("SELECT id FROM users WHERE email = $1")
+        .bind(email.trim())
+        .fetch_optional(state.db.pool())
+        .await?;
+
+    user.map(|(id,)| id)
+        .ok_or_else(|| AppError::NotFound("No user with that email".to_string()))
+}
+
+/// Membership changes must leave the organization with an owner
+async fn ensure_owner_remains(tx: &mut Transaction<'_, Postgres>, organization_id: Uuid) -> Result<()> {
+    // Lock the organization so concurrent changes can't both remove the last owner
+    sqlx::query("SELECT 1 FROM organizations WHERE id = $1 FOR UPDATE")
+        .bind(organization_id)
+        .execute(&mut **tx)
+        .await?;
+
+    let (owners,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM organization_members WHERE organization_id = $1 AND role = 'owner'"
+    )
+    .bind(organization_id)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    if owners == 0 {
+        return Err(AppError::Validation("An organization needs at least one owner".to_string()));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,239 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde_json::Value as JsonValue;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    models::{
+        new_push_secret, CreatePushSourceRequest, PushPayload, PushSource, PushSourceResponse,
+        MAX_PUSH_POINTS, PUSH_HISTORY_LIMIT, PUSH_SIGNATURE_HEADER,
+    },
+    AppState,
+};
+
+const PUSH_SOURCE_COLUMNS: &str = "id, user_id, name, secret, keep_history, latest, latest_at, created_at, updated_at";
+
+/// List all push sources for the authenticated user
+pub async fn list_push_sources(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let sources: Vec<PushSource> = sqlx::query_as(&format!(
+        "SELECT {} FROM push_sources WHERE user_id = $1 ORDER BY name",
+        PUSH_SOURCE_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let sources: Vec<PushSourceResponse> = sources.into_iter().map(PushSourceResponse::from).collect();
+
+    Ok(Json(sources))
+}
+
+/// Get a push source by ID
+pub async fn get_push_source(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(source_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let source: Option<PushSource> = sqlx::query_as(&format!(
+        "SELECT {} FROM push_sources WHERE id = $1 AND user_id = $2",
+        PUSH_SOURCE_COLUMNS
+    ))
+    .bind(source_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let source = source.ok_or_else(|| AppError::NotFound("Push source not found".to_string()))?;
+
+    Ok(Json(PushSourceResponse::from(source)))
+}
+
+/// Create a push source; the response is the only time its secret is shown
+pub async fn create_push_source(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<CreatePushSourceRequest>,
+) -> Result<impl IntoResponse> {
+    if payload.name.trim().is_empty() {
+        return Err(AppError::Validation("Push source name is required".to_string()));
+    }
+
+    let source: PushSource = sqlx::query_as(&format!(
+        "INSERT INTO push_sources (user_id, name, secret, keep_history)
+         VALUES ($1, $2, $3, $4)
+         RETURNING {}",
+        PUSH_SOURCE_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .bind(payload.name.trim())
+    .bind(new_push_secret())
+    .bind(payload.keep_history)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(PushSourceResponse::with_secret(source))))
+}
+
+/// Replace a push source's secret; pushes signed with the old one are rejected from now on
+pub async fn rotate_push_secret(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(source_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let source: Option<PushSource> = sqlx::query_as(&format!(
+        "UPDATE push_sources SET secret = $1, updated_at = NOW()
+         WHERE id = $2 AND user_id = $3
+         RETURNING {}",
+        PUSH_SOURCE_COLUMNS
+    ))
+    .bind(new_push_secret())
+    .bind(source_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let source = source.ok_or_else(|| AppError::NotFound("Push source not found".to_string()))?;
+
+    Ok(Json(PushSourceResponse::with_secret(source)))
+}
+
+/// Delete a push source and its history
+pub async fn delete_push_source(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(source_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "DELETE FROM push_sources WHERE id = $1 AND user_id = $2"
+    )
+    .bind(source_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Push source not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Accept a push from an external system.
+///
+/// The body is `{"value": ...}` or `{"points": [{"timestamp": ..., "value": ...}]}`, signed with
+/// `X-Signature-256: sha256=<hex HMAC-SHA256 of the body keyed with the source secret>`.
+pub async fn ingest_push(
+    State(state): State<AppState>,
+    Path(source_id): Path<Uuid>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse> {
+    let source: Option<PushSource> = sqlx::query_as(&format!(
+        "SELECT {} FROM push_sources WHERE id = $1",
+        PUSH_SOURCE_COLUMNS
+    ))
+    .bind(source_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let source = source.ok_or_else(|| AppError::NotFound("Push source not found".to_string()))?;
+
+    verify_signature(&source.secret, &headers, &body)?;
+
+    let payload: PushPayload = serde_json::from_slice(&body)
+        .map_err(|e| AppError::Validation(format!("Invalid push payload: {}", e)))?;
+
+    let now = Utc::now();
+    let mut points: Vec<(chrono::DateTime<Utc>, JsonValue)> = match payload {
+        PushPayload::Value { value } => vec![(now, value)],
+        PushPayload::Series { points } => points
+            .into_iter()
+            .map(|point| (point.timestamp.unwrap_or(now), point.value))
+            .collect(),
+    };
+
+    if points.is_empty() {
+        return Err(AppError::Validation("At least one point is required".to_string()));
+    }
+    if points.len() > MAX_PUSH_POINTS {
+        return Err(AppError::Validation(format!(
+            "At most {} points can be pushed at once",
+            MAX_PUSH_POINTS
+        )));
+    }
+    points.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let (latest_at, latest) = points.last().cloned().unwrap_or((now, JsonValue::Null));
+
+    let mut tx = state.db.pool().begin().await?;
+
+    // Backfilled points older than the current latest value don't replace it
+    sqlx::query(
+        "UPDATE push_sources SET latest = $1, latest_at = $2
+         WHERE id = $3 AND (latest_at IS NULL OR latest_at <= $2)"
+    )
+    .bind(&latest)
+    .bind(latest_at)
+    .bind(source.id)
+    .execute(&mut *tx)
+    .await?;
+
+    if source.keep_history {
+        let (timestamps, values): (Vec<_>, Vec<_>) = points.into_iter().unzip();
+
+        sqlx::query(
+            "INSERT INTO push_points (source_id, recorded_at, value)
+             SELECT $1, * FROM UNNEST($2::timestamptz[], $3::jsonb[])"
+        )
+        .bind(source.id)
+        .bind(&timestamps)
+        .bind(&values)
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "DELETE FROM push_points
+             WHERE source_id = $1 AND id NOT IN (
+                 SELECT id FROM push_points WHERE source_id = $1 ORDER BY recorded_at DESC LIMIT $2
+             )"
+        )
+        .bind(source.id)
+        .bind(PUSH_HISTORY_LIMIT)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// Check the body's HMAC-SHA256 signature in constant time
+fn verify_signature(secret: &str, headers: &HeaderMap, body: &[u8]) -> Result<()> {
+    let signature = headers
+        .get(PUSH_SIGNATURE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("sha256="))
+        .and_then(|value| hex::decode(value).ok())
+        .ok_or_else(|| AppError::Auth("Missing or malformed X-Signature-256 header".to_string()))?;
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes())
+        .map_err(|e| AppError::Internal(format!("Invalid HMAC key: {}", e)))?;
+    mac.update(body);
+
+    mac.verify_slice(&signature)
+        .map_err(|_| AppError::Auth("Invalid signature".to_string()))
+}
@@ -0,0 +1,181 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    handlers::{alert::ensure_channels_owned, dashboard::find_dashboard},
+    models::{ReportDelivery, ReportSchedule, ReportScheduleRequest, ReportTrigger, MAX_PAGE_SIZE},
+    notify::report::send_report,
+    AppState,
+};
+
+pub(crate) const REPORT_SCHEDULE_COLUMNS: &str = "id, user_id, dashboard_id, name, cron, timezone, \
+     channel_ids, enabled, next_run_at, last_run_at, created_at, updated_at";
+
+/// List all report schedules for the authenticated user
+pub async fn list_report_schedules(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let schedules: Vec<ReportSchedule> = sqlx::query_as(&format!(
+        "SELECT {} FROM report_schedules WHERE user_id = $1 ORDER BY name",
+        REPORT_SCHEDULE_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(schedules))
+}
+
+/// Get a report schedule by ID
+pub async fn get_report_schedule(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let schedule = find_report_schedule(&state, user_ctx.user_id, schedule_id).await?;
+    Ok(Json(schedule))
+}
+
+/// Create a report schedule
+pub async fn create_report_schedule(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<ReportScheduleRequest>,
+) -> Result<impl IntoResponse> {
+    let next_run_at = payload.validate()?;
+    find_dashboard(&state, user_ctx.user_id, payload.dashboard_id).await?;
+    ensure_channels_owned(&state, user_ctx.user_id, &payload.channel_ids).await?;
+
+    let schedule: ReportSchedule = sqlx::query_as(&format!(
+        "INSERT INTO report_schedules (user_id, dashboard_id, name, cron, timezone, channel_ids, enabled, next_run_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+         RETURNING {}",
+        REPORT_SCHEDULE_COLUMNS
+    ))
+    .bind(user_ctx.user_id)
+    .bind(payload.dashboard_id)
+    .bind(payload.name.trim())
+    .bind(payload.cron.trim())
+    .bind(&payload.timezone)
+    .bind(&payload.channel_ids)
+    .bind(payload.enabled)
+    .bind(next_run_at)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(schedule)))
+}
+
+/// Replace a report schedule
+pub async fn update_report_schedule(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+    Json(payload): Json<ReportScheduleRequest>,
+) -> Result<impl IntoResponse> {
+    let next_run_at = payload.validate()?;
+    find_dashboard(&state, user_ctx.user_id, payload.dashboard_id).await?;
+    ensure_channels_owned(&state, user_ctx.user_id, &payload.channel_ids).await?;
+
+    let schedule: Option<ReportSchedule> = sqlx::query_as(&format!(
+        "UPDATE report_schedules
+         SET dashboard_id = $1, name = $2, cron = $3, timezone = $4, channel_ids = $5,
+             enabled = $6, next_run_at = $7, updated_at = NOW()
+         WHERE id = $8 AND user_id = $9
+         RETURNING {}",
+        REPORT_SCHEDULE_COLUMNS
+    ))
+    .bind(payload.dashboard_id)
+    .bind(payload.name.trim())
+    .bind(payload.cron.trim())
+    .bind(&payload.timezone)
+    .bind(&payload.channel_ids)
+    .bind(payload.enabled)
+    .bind(next_run_at)
+    .bind(schedule_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let schedule = schedule.ok_or_else(|| AppError::NotFound("Report schedule not found".to_string()))?;
+
+    Ok(Json(schedule))
+}
+
+/// Delete a report schedule and its delivery log
+pub async fn delete_report_schedule(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "DELETE FROM report_schedules WHERE id = $1 AND user_id = $2"
+    )
+    .bind(schedule_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Report schedule not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Render and deliver a report right away, without touching its schedule
+pub async fn send_report_now(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let schedule = find_report_schedule(&state, user_ctx.user_id, schedule_id).await?;
+    let delivery = send_report(&state, &schedule, ReportTrigger::Manual).await?;
+
+    Ok(Json(delivery))
+}
+
+/// List a report schedule's delivery log, newest first
+pub async fn list_report_deliveries(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(schedule_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    find_report_schedule(&state, user_ctx.user_id, schedule_id).await?;
+
+    let deliveries: Vec<ReportDelivery> = sqlx::query_as(
+        "SELECT id, schedule_id, trigger, status, error, created_at
+         FROM report_deliveries
+         WHERE schedule_id = $1
+         ORDER BY created_at DESC
+         LIMIT $2"
+    )
+    .bind(schedule_id)
+    .bind(MAX_PAGE_SIZE)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(deliveries))
+}
+
+/// Load a report schedule owned by the user
+async fn find_report_schedule(state: &AppState, user_id: Uuid, schedule_id: Uuid) -> Result<ReportSchedule> {
+    let schedule: Option<ReportSchedule> = sqlx::query_as(&format!(
+        "SELECT {} FROM report_schedules WHERE id = $1 AND user_id = $2",
+        REPORT_SCHEDULE_COLUMNS
+    ))
+    .bind(schedule_id)
+    .bind(user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    schedule.ok_or_else(|| AppError::NotFound("Report schedule not found".to_string()))
+}
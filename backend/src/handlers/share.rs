@@ -0,0 +1,126 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    handlers::{dashboard::find_dashboard, organization::find_user_id},
+    models::{strip_secrets, Dashboard, DashboardResponse, DashboardShare, ShareDashboardRequest},
+    AppState,
+};
+
+/// List dashboards other users shared with the caller, directly or through an organization
+pub async fn list_shared_dashboards(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let dashboards: Vec<Dashboard> = sqlx::query_as(
+        "SELECT id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at
+         FROM dashboards
+         WHERE deleted_at IS NULL AND user_id <> $1
+           AND (EXISTS (SELECT 1 FROM dashboard_shares s WHERE s.dashboard_id = dashboards.id AND s.user_id = $1)
+                OR organization_id IN (SELECT organization_id FROM organization_members WHERE user_id = $1))
+         ORDER BY name, id"
+    )
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let response: Vec<DashboardResponse> = dashboards
+        .into_iter()
+        .map(|dashboard| DashboardResponse::from(for_reader(dashboard, user_ctx.user_id)))
+        .collect();
+
+    Ok(Json(response))
+}
+
+/// List the users a dashboard is shared with (owner only)
+pub async fn list_shares(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    find_dashboard(&state, user_ctx.user_id, dashboard_id).await?;
+
+    let shares: Vec<DashboardShare> = sqlx::query_as(
+        "SELECT s.user_id, u.email, s.created_at
+         FROM dashboard_shares s
+         JOIN users u ON u.id = s.user_id
+         WHERE s.dashboard_id = $1
+         ORDER BY u.email"
+    )
+    .bind(dashboard_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(shares))
+}
+
+/// Share a dashboard read-only with another registered user (owner only)
+pub async fn share_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    Json(payload): Json<ShareDashboardRequest>,
+) -> Result<impl IntoResponse> {
+    find_dashboard(&state, user_ctx.user_id, dashboard_id).await?;
+
+    let user_id = find_user_id(&state, &payload.email).await?;
+    if user_id == user_ctx.user_id {
+        return Err(AppError::Validation("You already own this dashboard".to_string()));
+    }
+
+    let share: DashboardShare = sqlx::query_as(
+        "WITH inserted AS (
+             INSERT INTO dashboard_shares (dashboard_id, user_id)
+             VALUES ($1, $2)
+             ON CONFLICT (dashboard_id, user_id) DO UPDATE SET user_id = EXCLUDED.user_id
+             RETURNING user_id, created_at
+         )
+         SELECT inserted.user_id, u.email, inserted.created_at
+         FROM inserted JOIN users u ON u.id = inserted.user_id"
+    )
+    .bind(dashboard_id)
+    .bind(user_id)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(share)))
+}
+
+/// Stop sharing a dashboard with a user (owner only)
+pub async fn unshare_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path((dashboard_id, user_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse> {
+    find_dashboard(&state, user_ctx.user_id, dashboard_id).await?;
+
+    let result = sqlx::query(
+        "DELETE FROM dashboard_shares WHERE dashboard_id = $1 AND user_id = $2"
+    )
+    .bind(dashboard_id)
+    .bind(user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Share not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Readers other than the owner never see the credentials in widget configs
+pub(crate) fn for_reader(mut dashboard: Dashboard, user_id: Uuid) -> Dashboard {
+    if dashboard.user_id != user_id {
+        strip_secrets(&mut dashboard.layout_json);
+        strip_secrets(&mut dashboard.settings_json);
+    }
+    dashboard
+}
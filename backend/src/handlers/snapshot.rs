@@ -0,0 +1,212 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    handlers::{dashboard::find_dashboard, widget_data::parse_dashboard},
+    models::{
+        is_secret_key, new_share_token, strip_secrets, CreateSnapshotRequest, SharedSnapshotResponse, Snapshot,
+        SnapshotSummary,
+    },
+    widgets::resolve_widgets,
+    AppState,
+};
+
+const SNAPSHOT_COLUMNS: &str = "id, dashboard_id, user_id, name, layout_json, settings_json, \
+     variables, widgets, share_token, expires_at, created_at";
+
+/// Freeze a dashboard: resolve every widget now and store the result with the layout.
+///
+/// Dashboard variables can be overridden with `?var-<name>=<value>` like the data endpoint.
+pub async fn create_snapshot(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    Query(query): Query<HashMap<String, String>>,
+    payload: Option<Json<CreateSnapshotRequest>>,
+) -> Result<impl IntoResponse> {
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+
+    if let Some(expires_at) = payload.expires_at {
+        if expires_at <= Utc::now() {
+            return Err(AppError::Validation("expires_at must be in the future".to_string()));
+        }
+    }
+
+    let dashboard = find_dashboard(&state, user_ctx.user_id, dashboard_id).await?;
+    let (layout, mut variables) =
+        parse_dashboard(dashboard.layout_json.clone(), dashboard.settings_json.clone(), &query)?;
+
+    let widgets = resolve_widgets(&state, user_ctx.user_id, &layout.widgets, &variables).await;
+
+    let name = match payload.name {
+        Some(name) if name.trim().is_empty() => {
+            return Err(AppError::Validation("Snapshot name is required".to_string()));
+        }
+        Some(name) => name.trim().to_string(),
+        None => format!("{} @ {}", dashboard.name, Utc::now().format("%Y-%m-%d %H:%M UTC")),
+    };
+    let share_token = payload.share.then(new_share_token);
+
+    // Snapshots can be shared publicly, so credentials in widget configs and secret variables are never stored
+    let mut layout_json = dashboard.layout_json;
+    let mut settings_json = dashboard.settings_json;
+    strip_secrets(&mut layout_json);
+    strip_secrets(&mut settings_json);
+    variables.retain(|name, _| !is_secret_key(name));
+
+    let snapshot: Snapshot = sqlx::query_as(&format!(
+        "INSERT INTO dashboard_snapshots
+         (dashboard_id, user_id, name, layout_json, settings_json, variables, widgets, share_token, expires_at)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+         RETURNING {}",
+        SNAPSHOT_COLUMNS
+    ))
+    .bind(dashboard.id)
+    .bind(user_ctx.user_id)
+    .bind(name)
+    .bind(layout_json)
+    .bind(settings_json)
+    .bind(sqlx::types::Json(&variables))
+    .bind(sqlx::types::Json(&widgets))
+    .bind(share_token)
+    .bind(payload.expires_at)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((StatusCode::CREATED, Json(snapshot)))
+}
+
+/// List a dashboard's unexpired snapshots, newest first
+pub async fn list_snapshots(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let snapshots: Vec<SnapshotSummary> = sqlx::query_as(
+        "SELECT id, dashboard_id, name, share_token, expires_at, created_at
+         FROM dashboard_snapshots
+         WHERE dashboard_id = $1 AND user_id = $2 AND (expires_at IS NULL OR expires_at > NOW())
+         ORDER BY created_at DESC"
+    )
+    .bind(dashboard_id)
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(snapshots))
+}
+
+/// Get a snapshot with its frozen widget data
+pub async fn get_snapshot(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(snapshot_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let snapshot: Option<Snapshot> = sqlx::query_as(&format!(
+        "SELECT {} FROM dashboard_snapshots
+         WHERE id = $1 AND user_id = $2 AND (expires_at IS NULL OR expires_at > NOW())",
+        SNAPSHOT_COLUMNS
+    ))
+    .bind(snapshot_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let snapshot = snapshot.ok_or_else(|| AppError::NotFound("Snapshot not found".to_string()))?;
+
+    Ok(Json(snapshot))
+}
+
+/// Delete a snapshot
+pub async fn delete_snapshot(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(snapshot_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "DELETE FROM dashboard_snapshots WHERE id = $1 AND user_id = $2"
+    )
+    .bind(snapshot_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Snapshot not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create (or rotate) a snapshot's share link
+pub async fn share_snapshot(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(snapshot_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let snapshot: Option<Snapshot> = sqlx::query_as(&format!(
+        "UPDATE dashboard_snapshots
+         SET share_token = $1
+         WHERE id = $2 AND user_id = $3 AND (expires_at IS NULL OR expires_at > NOW())
+         RETURNING {}",
+        SNAPSHOT_COLUMNS
+    ))
+    .bind(new_share_token())
+    .bind(snapshot_id)
+    .bind(user_ctx.user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let snapshot = snapshot.ok_or_else(|| AppError::NotFound("Snapshot not found".to_string()))?;
+
+    Ok(Json(snapshot))
+}
+
+/// Revoke a snapshot's share link
+pub async fn unshare_snapshot(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(snapshot_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "UPDATE dashboard_snapshots SET share_token = NULL WHERE id = $1 AND user_id = $2"
+    )
+    .bind(snapshot_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Snapshot not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// View a shared snapshot without authentication
+pub async fn get_shared_snapshot(
+    State(state): State<AppState>,
+    Path(share_token): Path<String>,
+) -> Result<impl IntoResponse> {
+    let snapshot: Option<Snapshot> = sqlx::query_as(&format!(
+        "SELECT {} FROM dashboard_snapshots
+         WHERE share_token = $1 AND (expires_at IS NULL OR expires_at > NOW())",
+        SNAPSHOT_COLUMNS
+    ))
+    .bind(share_token)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let snapshot = snapshot.ok_or_else(|| AppError::NotFound("Snapshot not found".to_string()))?;
+
+    Ok(Json(SharedSnapshotResponse::from(snapshot)))
+}
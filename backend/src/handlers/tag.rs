@@ -0,0 +1,189 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    models::{CreateTagRequest, FavoriteRequest, SetDashboardTagsRequest, Tag},
+    AppState,
+};
+
+/// List all tags for the authenticated user
+pub async fn list_tags(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let tags: Vec<Tag> = sqlx::query_as(
+        "SELECT id, user_id, name, created_at FROM tags WHERE user_id = $1 ORDER BY name"
+    )
+    .bind(user_ctx.user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(tags))
+}
+
+/// Create a new tag
+pub async fn create_tag(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<CreateTagRequest>,
+) -> Result<impl IntoResponse> {
+    let name = normalize_tag(&payload.name)?;
+
+    let tag: Option<Tag> = sqlx::query_as(
+        "INSERT INTO tags (user_id, name)
+         VALUES ($1, $2)
+         ON CONFLICT (user_id, name) DO NOTHING
+         RETURNING id, user_id, name, created_at"
+    )
+    .bind(user_ctx.user_id)
+    .bind(&name)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let tag = tag.ok_or_else(|| AppError::Validation(format!("Tag '{}' already exists", name)))?;
+
+    Ok((StatusCode::CREATED, Json(tag)))
+}
+
+/// Delete a tag, removing it from every dashboard
+pub async fn delete_tag(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(tag_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    let result = sqlx::query(
+        "DELETE FROM tags WHERE id = $1 AND user_id = $2"
+    )
+    .bind(tag_id)
+    .bind(user_ctx.user_id)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Tag not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Replace a dashboard's tags, creating tags that don't exist yet
+pub async fn set_dashboard_tags(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    Json(payload): Json<SetDashboardTagsRequest>,
+) -> Result<impl IntoResponse> {
+    let mut names = payload
+        .tags
+        .iter()
+        .map(|name| normalize_tag(name))
+        .collect::<Result<Vec<_>>>()?;
+    names.sort();
+    names.dedup();
+
+    let mut tx = state.db.pool().begin().await?;
+
+    let (owned,): (bool,) = sqlx::query_as(
+        "SELECT EXISTS (SELECT 1 FROM dashboards WHERE id = $1 AND user_id = $2 AND deleted_at IS NULL)"
+    )
+    .bind(dashboard_id)
+    .bind(user_ctx.user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if !owned {
+        return Err(AppError::NotFound("Dashboard not found".to_string()));
+    }
+
+    sqlx::query(
+        "INSERT INTO tags (user_id, name)
+         SELECT $1, UNNEST($2::text[])
+         ON CONFLICT (user_id, name) DO NOTHING"
+    )
+    .bind(user_ctx.user_id)
+    .bind(&names)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM dashboard_tags WHERE dashboard_id = $1")
+        .bind(dashboard_id)
+        .execute(&mut *tx)
+        .await?;
+
+    sqlx::query(
+        "INSERT INTO dashboard_tags (dashboard_id, tag_id)
+         SELECT $1, id FROM tags WHERE user_id = $2 AND name = ANY($3)"
+    )
+    .bind(dashboard_id)
+    .bind(user_ctx.user_id)
+    .bind(&names)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(Json(names))
+}
+
+/// Favorite (and optionally pin) a dashboard
+pub async fn favorite_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    payload: Option<Json<FavoriteRequest>>,
+) -> Result<impl IntoResponse> {
+    let payload = payload.map(|Json(payload)| payload).unwrap_or_default();
+
+    let result = sqlx::query(
+        "INSERT INTO dashboard_favorites (user_id, dashboard_id, pinned)
+         SELECT $1, id, $3 FROM dashboards WHERE id = $2 AND user_id = $1 AND deleted_at IS NULL
+         ON CONFLICT (user_id, dashboard_id) DO UPDATE SET pinned = EXCLUDED.pinned"
+    )
+    .bind(user_ctx.user_id)
+    .bind(dashboard_id)
+    .bind(payload.pinned)
+    .execute(state.db.pool())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound("Dashboard not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Remove a dashboard from the user's favorites
+pub async fn unfavorite_dashboard(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+) -> Result<impl IntoResponse> {
+    sqlx::query(
+        "DELETE FROM dashboard_favorites WHERE user_id = $1 AND dashboard_id = $2"
+    )
+    .bind(user_ctx.user_id)
+    .bind(dashboard_id)
+    .execute(state.db.pool())
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Trim and validate a tag name
+fn normalize_tag(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::Validation("Tag name is required".to_string()));
+    }
+    if name.chars().count() > 50 {
+        return Err(AppError::Validation("Tag names are limited to 50 characters".to_string()));
+    }
+    Ok(name.to_string())
+}
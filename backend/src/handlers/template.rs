@@ -0,0 +1,223 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    models::{
+        builtin_templates, check_template, is_secret_key, strip_secrets, validate_dashboard,
+        Dashboard, DashboardResponse, DashboardTemplate, FromTemplateRequest,
+        PublishTemplateRequest, TemplateListQuery, TemplateRow, TemplateVersionQuery,
+    },
+    AppState,
+};
+
+const TEMPLATE_COLUMNS: &str = "id, slug, version, user_id, name, description, variables, \
+     widget_types, layout_json, settings_json, created_at";
+
+/// List the template gallery (latest version of each template)
+pub async fn list_templates(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Query(query): Query<TemplateListQuery>,
+) -> Result<impl IntoResponse> {
+    let widget_type = query.widget_type.map(|widget_type| widget_type.as_str());
+
+    let rows: Vec<TemplateRow> = sqlx::query_as(&format!(
+        "SELECT DISTINCT ON (slug) {}
+         FROM dashboard_templates
+         WHERE $1::text IS NULL OR $1 = ANY(widget_types)
+         ORDER BY slug, version DESC",
+        TEMPLATE_COLUMNS
+    ))
+    .bind(widget_type)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let templates: Vec<DashboardTemplate> = builtin_templates()
+        .into_iter()
+        .filter(|template| match widget_type {
+            Some(widget_type) => template.widget_types.iter().any(|t| t == widget_type),
+            None => true,
+        })
+        .chain(rows.into_iter().map(DashboardTemplate::from))
+        .collect();
+
+    Ok(Json(templates))
+}
+
+/// Get a template by slug (latest version unless `?version=` is given)
+pub async fn get_template(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+    Query(query): Query<TemplateVersionQuery>,
+) -> Result<impl IntoResponse> {
+    let template = find_template(&state, &slug, query.version).await?;
+    Ok(Json(template))
+}
+
+/// List every published version of a template
+pub async fn list_template_versions(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(slug): Path<String>,
+) -> Result<impl IntoResponse> {
+    if let Some(template) = builtin_templates().into_iter().find(|t| t.slug == slug) {
+        return Ok(Json(vec![template]));
+    }
+
+    let rows: Vec<TemplateRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM dashboard_templates WHERE slug = $1 ORDER BY version DESC",
+        TEMPLATE_COLUMNS
+    ))
+    .bind(&slug)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    if rows.is_empty() {
+        return Err(AppError::NotFound("Template not found".to_string()));
+    }
+
+    let templates: Vec<DashboardTemplate> = rows.into_iter().map(DashboardTemplate::from).collect();
+
+    Ok(Json(templates))
+}
+
+/// Publish a template, or a new version of a template the user already owns
+pub async fn publish_template(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(mut payload): Json<PublishTemplateRequest>,
+) -> Result<impl IntoResponse> {
+    let widget_types = check_template(&payload)?;
+
+    // Templates are visible to every user, so credentials copied from a dashboard must not be published
+    strip_secrets(&mut payload.layout_json);
+    strip_secrets(&mut payload.settings_json);
+    for variable in payload.variables.iter_mut().filter(|variable| is_secret_key(&variable.name)) {
+        variable.default = None;
+    }
+
+    if builtin_templates().iter().any(|template| template.slug == payload.slug) {
+        return Err(AppError::Validation("Template slug is reserved".to_string()));
+    }
+
+    let mut tx = state.db.pool().begin().await?;
+
+    // Row locks can't serialize the first publish of a slug, so lock the slug itself
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+        .bind(&payload.slug)
+        .execute(&mut *tx)
+        .await?;
+
+    let latest: Option<(uuid::Uuid, i32)> = sqlx::query_as(
+        "SELECT user_id, version FROM dashboard_templates
+         WHERE slug = $1
+         ORDER BY version DESC
+         LIMIT 1"
+    )
+    .bind(&payload.slug)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    let version = match latest {
+        Some((owner_id, _)) if owner_id != user_ctx.user_id => {
+            return Err(AppError::Validation("Template slug is already taken".to_string()));
+        }
+        Some((_, version)) => version + 1,
+        None => 1,
+    };
+
+    let row: TemplateRow = sqlx::query_as(&format!(
+        "INSERT INTO dashboard_templates
+         (slug, version, user_id, name, description, variables, widget_types, layout_json, settings_json)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+         RETURNING {}",
+        TEMPLATE_COLUMNS
+    ))
+    .bind(&payload.slug)
+    .bind(version)
+    .bind(user_ctx.user_id)
+    .bind(payload.name.trim())
+    .bind(&payload.description)
+    .bind(sqlx::types::Json(&payload.variables))
+    .bind(&widget_types)
+    .bind(&payload.layout_json)
+    .bind(&payload.settings_json)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok((StatusCode::CREATED, Json(DashboardTemplate::from(row))))
+}
+
+/// Create a dashboard by instantiating a template with variable values
+pub async fn create_from_template(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(payload): Json<FromTemplateRequest>,
+) -> Result<impl IntoResponse> {
+    let template = find_template(&state, &payload.template, payload.version).await?;
+
+    let (layout_json, settings_json) = template.instantiate(&payload.variables)?;
+    let (layout_json, settings_json) = validate_dashboard(layout_json, settings_json)?;
+
+    let name = payload.name.unwrap_or_else(|| template.name.clone());
+    if name.trim().is_empty() {
+        return Err(AppError::Validation("Dashboard name is required".to_string()));
+    }
+
+    let dashboard: Dashboard = sqlx::query_as(
+        "INSERT INTO dashboards (user_id, name, layout_json, settings_json)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id, user_id, name, layout_json, settings_json, source_dashboard_id, folder_id, organization_id, created_at, updated_at, deleted_at"
+    )
+    .bind(user_ctx.user_id)
+    .bind(name.trim())
+    .bind(layout_json)
+    .bind(settings_json)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(DashboardResponse::from(dashboard)),
+    ))
+}
+
+/// Look up a built-in or published template
+async fn find_template(
+    state: &AppState,
+    slug: &str,
+    version: Option<i32>,
+) -> Result<DashboardTemplate> {
+    if let Some(template) = builtin_templates().into_iter().find(|t| t.slug == slug) {
+        return match version {
+            Some(version) if version != template.version => {
+                Err(AppError::NotFound("Template version not found".to_string()))
+            }
+            _ => Ok(template),
+        };
+    }
+
+    let row: Option<TemplateRow> = sqlx::query_as(&format!(
+        "SELECT {} FROM dashboard_templates
+         WHERE slug = $1 AND ($2::int IS NULL OR version = $2)
+         ORDER BY version DESC
+         LIMIT 1",
+        TEMPLATE_COLUMNS
+    ))
+    .bind(slug)
+    .bind(version)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    row.map(DashboardTemplate::from)
+        .ok_or_else(|| AppError::NotFound("Template not found".to_string()))
+}
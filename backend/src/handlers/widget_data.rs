@@ -0,0 +1,74 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::IntoResponse,
+    Json,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    handlers::dashboard::find_dashboard,
+    models::{resolve_variables, DashboardLayout, DashboardSettings},
+    widgets::{resolve_widget, resolve_widgets, ResolvedWidget},
+    AppState,
+};
+
+/// Resolved data for a whole dashboard
+#[derive(Debug, Serialize)]
+pub struct DashboardDataResponse {
+    /// Variable values used, after applying `?var-name=` overrides
+    pub variables: HashMap<String, String>,
+    pub widgets: Vec<ResolvedWidget>,
+}
+
+/// Resolve every widget of a dashboard server-side.
+///
+/// Dashboard variables can be overridden per request with `?var-<name>=<value>`.
+pub async fn get_dashboard_data(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(dashboard_id): Path<Uuid>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse> {
+    let dashboard = find_dashboard(&state, user_ctx.user_id, dashboard_id).await?;
+    let (layout, variables) = parse_dashboard(dashboard.layout_json, dashboard.settings_json, &query)?;
+
+    let widgets = resolve_widgets(&state, user_ctx.user_id, &layout.widgets, &variables).await;
+
+    Ok(Json(DashboardDataResponse { variables, widgets }))
+}
+
+/// Resolve a single widget of a dashboard server-side
+pub async fn get_widget_data(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path((dashboard_id, widget_id)): Path<(Uuid, String)>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<impl IntoResponse> {
+    let dashboard = find_dashboard(&state, user_ctx.user_id, dashboard_id).await?;
+    let (layout, variables) = parse_dashboard(dashboard.layout_json, dashboard.settings_json, &query)?;
+
+    let widget = layout
+        .widgets
+        .iter()
+        .find(|widget| widget.id == widget_id)
+        .ok_or_else(|| AppError::NotFound("Widget not found".to_string()))?;
+
+    Ok(Json(resolve_widget(&state, user_ctx.user_id, widget, &variables).await))
+}
+
+/// Parse a stored dashboard and resolve its variable values for this request
+pub(crate) fn parse_dashboard(
+    layout_json: serde_json::Value,
+    settings_json: serde_json::Value,
+    query: &HashMap<String, String>,
+) -> Result<(DashboardLayout, HashMap<String, String>)> {
+    let layout = DashboardLayout::from_json(layout_json).map_err(AppError::InvalidFields)?;
+    let settings = DashboardSettings::from_json(settings_json).map_err(AppError::InvalidFields)?;
+    let variables = resolve_variables(&settings.variables, query)?;
+
+    Ok((layout, variables))
+}
@@ -0,0 +1,164 @@
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use std::time::Duration;
+
+use crate::{
+    error::Result,
+    handlers::alert::ALERT_RULE_COLUMNS,
+    models::{AlertRule, AlertState, NotificationChannel},
+    notify::{deliver, Notification},
+    AppState,
+};
+
+/// How often alert rules are evaluated
+const EVALUATION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How often the worker looks for rules that are due
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Most rules claimed per tick
+const BATCH_SIZE: i64 = 100;
+
+/// Evaluate due alert rules against fresh widget data
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let rules = match claim_due_rules(&state).await {
+            Ok(rules) => rules,
+            Err(e) => {
+                tracing::error!("Failed to load alert rules: {:?}", e);
+                continue;
+            }
+        };
+
+        // Widget fetches go through the Redis cache, so rules sharing a series share one upstream call
+        for rule in &rules {
+            if let Err(e) = evaluate_rule(&state, rule).await {
+                tracing::warn!("Failed to evaluate alert rule {}: {:?}", rule.id, e);
+            }
+        }
+    }
+}
+
+/// Move due rules to their next evaluation time and return them
+async fn claim_due_rules(state: &AppState) -> Result<Vec<AlertRule>> {
+    // Claiming with SKIP LOCKED lets several backend instances share the work
+    let rules: Vec<AlertRule> = sqlx::query_as(&format!(
+        "UPDATE alert_rules
+         SET next_evaluation_at = NOW() + make_interval(secs => $1)
+         WHERE id IN (
+             SELECT id FROM alert_rules
+             WHERE enabled AND next_evaluation_at <= NOW()
+             ORDER BY next_evaluation_at
+             LIMIT $2
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING {}",
+        ALERT_RULE_COLUMNS
+    ))
+    .bind(EVALUATION_INTERVAL.as_secs_f64())
+    .bind(BATCH_SIZE)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(rules)
+}
+
+async fn evaluate_rule(state: &AppState, rule: &AlertRule) -> Result<()> {
+    let now = Utc::now();
+
+    let Some(value) = rule.widget_type.read_metric(state, &rule.series, &rule.metric).await? else {
+        tracing::debug!("No {} data for alert rule {}", rule.series, rule.id);
+        return Ok(());
+    };
+
+    let evaluation = rule.evaluate(value, now);
+    let notified = evaluation.notify && notify(state, rule, evaluation.state, value, now).await?;
+
+    let mut tx = state.db.pool().begin().await?;
+
+    sqlx::query(
+        "UPDATE alert_rules
+         SET state = $1, pending_since = $2, last_value = $3, last_evaluated_at = $4,
+             state_changed_at = CASE WHEN state <> $1 THEN $4 ELSE state_changed_at END,
+             last_notified_at = CASE WHEN $5 THEN $4 ELSE last_notified_at END
+         WHERE id = $6"
+    )
+    .bind(evaluation.state)
+    .bind(evaluation.pending_since)
+    .bind(value)
+    .bind(now)
+    .bind(notified)
+    .bind(rule.id)
+    .execute(&mut *tx)
+    .await?;
+
+    if evaluation.state != rule.state {
+        sqlx::query(
+            "INSERT INTO alert_events (rule_id, from_state, to_state, value, notified)
+             VALUES ($1, $2, $3, $4, $5)"
+        )
+        .bind(rule.id)
+        .bind(rule.state)
+        .bind(evaluation.state)
+        .bind(value)
+        .bind(notified)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Send a state change to the rule's channels; true if at least one delivery succeeded
+async fn notify(
+    state: &AppState,
+    rule: &AlertRule,
+    alert_state: AlertState,
+    value: f64,
+    at: DateTime<Utc>,
+) -> Result<bool> {
+    let channels: Vec<NotificationChannel> = sqlx::query_as(
+        "SELECT id, user_id, name, config, created_at, updated_at
+         FROM notification_channels
+         WHERE user_id = $1 AND id = ANY($2)"
+    )
+    .bind(rule.user_id)
+    .bind(&rule.channel_ids)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let notification = Notification {
+        title: format!("[{}] {}", alert_state.as_str().to_uppercase(), rule.name),
+        text: format!("{} (current value: {})", rule.condition(), value),
+        html: None,
+        payload: json!({
+            "event": "alert",
+            "rule_id": rule.id,
+            "name": rule.name,
+            "state": alert_state,
+            "widget_type": rule.widget_type,
+            "series": rule.series,
+            "metric": rule.metric,
+            "comparison": rule.comparison,
+            "threshold": rule.threshold,
+            "value": value,
+            "at": at,
+        }),
+    };
+
+    let mut delivered = false;
+    for channel in &channels {
+        match deliver(&state.config, state.mailer.as_ref(), &channel.config, &notification).await {
+            Ok(()) => delivered = true,
+            Err(e) => tracing::warn!("Failed to notify channel {} for alert {}: {:?}", channel.id, rule.id, e),
+        }
+    }
+
+    Ok(delivered)
+}
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+use crate::{error::Result, AppState};
+
+/// How often the metric retention policy is applied
+const RETENTION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Roll aged raw metrics up into hourly buckets and drop expired rollups
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(RETENTION_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = apply_retention(&state).await {
+            tracing::error!("Failed to apply metric retention: {:?}", e);
+        }
+    }
+}
+
+async fn apply_retention(state: &AppState) -> Result<()> {
+    let mut tx = state.db.pool().begin().await?;
+
+    // Cut on an hour boundary so a bucket is never rolled up twice
+    let (cutoff,): (DateTime<Utc>,) = sqlx::query_as(
+        "SELECT date_trunc('hour', NOW() - make_interval(days => $1))"
+    )
+    .bind(state.config.metrics_raw_retention_days)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let rolled_up = sqlx::query(
+        "INSERT INTO widget_metric_rollups
+         (widget_type, series, metric, bucket_start, min_value, max_value, avg_value, sample_count)
+         SELECT widget_type, series, metric, date_trunc('hour', recorded_at),
+                MIN(value), MAX(value), AVG(value), COUNT(*)
+         FROM widget_metrics
+         WHERE recorded_at < $1
+         GROUP BY 1, 2, 3, 4
+         ON CONFLICT (widget_type, series, metric, bucket_start) DO UPDATE SET
+             min_value = LEAST(widget_metric_rollups.min_value, EXCLUDED.min_value),
+             max_value = GREATEST(widget_metric_rollups.max_value, EXCLUDED.max_value),
+             avg_value = (widget_metric_rollups.avg_value * widget_metric_rollups.sample_count
+                          + EXCLUDED.avg_value * EXCLUDED.sample_count)
+                         / (widget_metric_rollups.sample_count + EXCLUDED.sample_count),
+             sample_count = widget_metric_rollups.sample_count + EXCLUDED.sample_count"
+    )
+    .bind(cutoff)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM widget_metrics WHERE recorded_at < $1")
+        .bind(cutoff)
+        .execute(&mut *tx)
+        .await?;
+
+    let expired = sqlx::query(
+        "DELETE FROM widget_metric_rollups WHERE bucket_start < NOW() - make_interval(days => $1)"
+    )
+    .bind(state.config.metrics_rollup_retention_days)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if rolled_up.rows_affected() > 0 || expired.rows_affected() > 0 {
+        tracing::info!(
+            "Rolled up {} metric buckets, deleted {} expired rollups",
+            rolled_up.rows_affected(),
+            expired.rows_affected()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+pub mod alerts;
+pub mod metrics;
+pub mod monitors;
+pub mod reports;
+pub mod snapshots;
+pub mod trash;
+
+use crate::AppState;
+
+/// Start all background jobs; they run until the process exits
+pub fn spawn_all(state: AppState) {
+    tokio::spawn(trash::run(state.clone()));
+    tokio::spawn(snapshots::run(state.clone()));
+    tokio::spawn(metrics::run(state.clone()));
+    tokio::spawn(monitors::run(state.clone()));
+    tokio::spawn(monitors::cleanup(state.clone()));
+    tokio::spawn(alerts::run(state.clone()));
+    tokio::spawn(reports::run(state));
+}
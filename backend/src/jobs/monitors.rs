@@ -0,0 +1,149 @@
+use futures::future::join_all;
+use std::time::Duration;
+
+use crate::{error::Result, models::Monitor, widgets::status::check_url, AppState};
+
+/// How often the worker looks for monitors that are due
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Most monitors claimed per tick
+const BATCH_SIZE: i64 = 50;
+
+/// How often old check history is deleted
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Check due monitors and record the results
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        if let Err(e) = check_due_monitors(&state).await {
+            tracing::error!("Failed to run monitor checks: {:?}", e);
+        }
+    }
+}
+
+/// Delete check history older than the retention period
+pub async fn cleanup(state: AppState) {
+    let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let result = sqlx::query(
+            "DELETE FROM monitor_checks WHERE checked_at < NOW() - make_interval(days => $1)"
+        )
+        .bind(state.config.monitor_check_retention_days)
+        .execute(state.db.pool())
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() > 0 => {
+                tracing::info!("Deleted {} old monitor checks", result.rows_affected());
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to delete old monitor checks: {:?}", e),
+        }
+    }
+}
+
+async fn check_due_monitors(state: &AppState) -> Result<()> {
+    // Claiming with SKIP LOCKED lets several backend instances share the work
+    let monitors: Vec<Monitor> = sqlx::query_as(
+        "UPDATE monitors
+         SET next_check_at = NOW() + make_interval(secs => interval_seconds)
+         WHERE id IN (
+             SELECT id FROM monitors
+             WHERE enabled AND next_check_at <= NOW()
+             ORDER BY next_check_at
+             LIMIT $1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, user_id, name, url, interval_seconds, expected_status, timeout_ms,
+                   enabled, last_is_up, last_checked_at, next_check_at, created_at, updated_at"
+    )
+    .bind(BATCH_SIZE)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let results = join_all(monitors.iter().map(|monitor| check_monitor(state, monitor))).await;
+
+    for (monitor, result) in monitors.iter().zip(results) {
+        if let Err(e) = result {
+            tracing::error!("Failed to record check for monitor {}: {:?}", monitor.id, e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Run one check, store it, and open or resolve the monitor's incident
+async fn check_monitor(state: &AppState, monitor: &Monitor) -> Result<()> {
+    let timeout = Duration::from_millis(monitor.timeout_ms as u64);
+    let check = check_url(&state.config, &monitor.url, timeout).await;
+
+    let (is_up, error) = match check.status_code {
+        Some(code) if monitor.accepts(code) => (true, None),
+        Some(code) => (false, Some(format!("Unexpected status {}", code))),
+        None => (false, Some(check.status.trim_start_matches("down: ").to_string())),
+    };
+
+    let mut tx = state.db.pool().begin().await?;
+
+    sqlx::query(
+        "INSERT INTO monitor_checks (monitor_id, is_up, status_code, response_time_ms, error)
+         VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(monitor.id)
+    .bind(is_up)
+    .bind(check.status_code.map(i32::from))
+    .bind(check.response_time_ms.map(|ms| ms.min(i32::MAX as u64) as i32))
+    .bind(&error)
+    .execute(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        "UPDATE monitors SET last_is_up = $1, last_checked_at = NOW() WHERE id = $2"
+    )
+    .bind(is_up)
+    .bind(monitor.id)
+    .execute(&mut *tx)
+    .await?;
+
+    match error {
+        Some(cause) => {
+            let opened = sqlx::query(
+                "INSERT INTO monitor_incidents (monitor_id, cause)
+                 VALUES ($1, $2)
+                 ON CONFLICT (monitor_id) WHERE resolved_at IS NULL DO NOTHING"
+            )
+            .bind(monitor.id)
+            .bind(&cause)
+            .execute(&mut *tx)
+            .await?;
+
+            if opened.rows_affected() > 0 {
+                tracing::warn!("Monitor {} is down: {}", monitor.id, cause);
+            }
+        }
+        None => {
+            let resolved = sqlx::query(
+                "UPDATE monitor_incidents SET resolved_at = NOW()
+                 WHERE monitor_id = $1 AND resolved_at IS NULL"
+            )
+            .bind(monitor.id)
+            .execute(&mut *tx)
+            .await?;
+
+            if resolved.rows_affected() > 0 {
+                tracing::info!("Monitor {} recovered", monitor.id);
+            }
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use chrono::Utc;
+use std::time::Duration;
+
+use crate::{
+    error::Result,
+    handlers::report::REPORT_SCHEDULE_COLUMNS,
+    models::{next_run, ReportSchedule, ReportTrigger},
+    notify::report::send_report,
+    AppState,
+};
+
+/// How often due report schedules are looked up
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Send reports whose schedule has come due
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let schedules = match claim_due_schedules(&state).await {
+            Ok(schedules) => schedules,
+            Err(e) => {
+                tracing::error!("Failed to load due report schedules: {:?}", e);
+                continue;
+            }
+        };
+
+        for schedule in &schedules {
+            match send_report(&state, schedule, ReportTrigger::Schedule).await {
+                Ok(delivery) => tracing::info!("Report {} delivered: {:?}", schedule.id, delivery.status),
+                Err(e) => tracing::error!("Failed to send report {}: {:?}", schedule.id, e),
+            }
+        }
+    }
+}
+
+/// Move due schedules to their next run time and return them
+async fn claim_due_schedules(state: &AppState) -> Result<Vec<ReportSchedule>> {
+    let mut tx = state.db.pool().begin().await?;
+
+    let schedules: Vec<ReportSchedule> = sqlx::query_as(&format!(
+        "SELECT {} FROM report_schedules
+         WHERE enabled AND next_run_at <= NOW()
+         FOR UPDATE SKIP LOCKED",
+        REPORT_SCHEDULE_COLUMNS
+    ))
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let now = Utc::now();
+    for schedule in &schedules {
+        // A schedule whose cron or timezone stopped parsing is disabled rather than retried forever
+        let next = next_run(&schedule.cron, &schedule.timezone, now).ok();
+
+        sqlx::query(
+            "UPDATE report_schedules
+             SET next_run_at = COALESCE($1, next_run_at), enabled = $1 IS NOT NULL
+             WHERE id = $2"
+        )
+        .bind(next)
+        .bind(schedule.id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(schedules)
+}
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use crate::AppState;
+
+/// How often expired snapshots are cleaned up
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Delete snapshots whose expiry has passed
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let result = sqlx::query(
+            "DELETE FROM dashboard_snapshots WHERE expires_at IS NOT NULL AND expires_at <= NOW()"
+        )
+        .execute(state.db.pool())
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() > 0 => {
+                tracing::info!("Deleted {} expired snapshots", result.rows_affected());
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to delete expired snapshots: {:?}", e),
+        }
+    }
+}
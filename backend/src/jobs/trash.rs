@@ -0,0 +1,31 @@
+use std::time::Duration;
+
+use crate::AppState;
+
+/// How often trashed dashboards are checked for expiry
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Permanently delete dashboards that have been in the trash longer than the retention period
+pub async fn run(state: AppState) {
+    let mut interval = tokio::time::interval(PURGE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let result = sqlx::query(
+            "DELETE FROM dashboards 
+             WHERE deleted_at IS NOT NULL AND deleted_at < NOW() - make_interval(days => $1)"
+        )
+        .bind(state.config.trash_retention_days)
+        .execute(state.db.pool())
+        .await;
+
+        match result {
+            Ok(result) if result.rows_affected() > 0 => {
+                tracing::info!("Purged {} dashboards from the trash", result.rows_affected());
+            }
+            Ok(_) => {}
+            Err(e) => tracing::error!("Failed to purge trashed dashboards: {:?}", e),
+        }
+    }
+}
@@ -2,16 +2,20 @@ mod auth;
 mod cache;
 mod config;
 mod db;
+mod egress;
 mod error;
 mod handlers;
+mod jobs;
 mod models;
+mod notify;
+mod secrets;
 mod widgets;
 
 use axum::{
-    routing::{get, post, put, delete},
+    routing::{get, post, put, patch, delete},
     Router,
 };
-use std::net::SocketAddr;
+use std::{net::SocketAddr, sync::Arc};
 use tower_http::{
     cors::CorsLayer,
     trace::TraceLayer,
@@ -23,6 +27,7 @@ use crate::{
     config::Config,
     db::Database,
     cache::Cache,
+    notify::mailer::Mailer,
 };
 
 /// Application state shared across all handlers
@@ -31,6 +36,7 @@ pub struct AppState {
     pub db: Database,
     pub cache: Cache,
     pub config: Config,
+    pub mailer: Arc<dyn Mailer>,
 }
 
 #[tokio::main]
@@ -59,13 +65,20 @@ async fn main() -> anyhow::Result<()> {
     let cache = Cache::new(&config.redis_url).await?;
     tracing::info!("Redis cache connection established");
 
+    // Initialize the mailer used by email notifications
+    let mailer = notify::mailer::from_config(&config)?;
+
     // Create application state
     let state = AppState {
         db,
         cache,
         config,
+        mailer,
     };
 
+    // Start background jobs
+    jobs::spawn_all(state.clone());
+
     // Build the router
     let app = Router::new()
         // Health check endpoint
@@ -107,16 +120,125 @@ fn api_routes() -> Router<AppState> {
         // Dashboard routes (protected)
         .route("/dashboards", get(handlers::dashboard::list_dashboards))
         .route("/dashboards", post(handlers::dashboard::create_dashboard))
+        .route("/dashboards/export", get(handlers::export::export_all_dashboards))
+        .route("/dashboards/import", post(handlers::export::import_dashboard))
+        .route("/dashboards/from-template", post(handlers::template::create_from_template))
+        .route("/dashboards/trash", get(handlers::dashboard::list_trash))
+        .route("/dashboards/shared", get(handlers::share::list_shared_dashboards))
         .route("/dashboards/:id", get(handlers::dashboard::get_dashboard))
         .route("/dashboards/:id", put(handlers::dashboard::update_dashboard))
+        .route("/dashboards/:id", patch(handlers::dashboard::patch_dashboard))
         .route("/dashboards/:id", delete(handlers::dashboard::delete_dashboard))
+        .route("/dashboards/:id/clone", post(handlers::dashboard::clone_dashboard))
+        .route("/dashboards/:id/restore", post(handlers::dashboard::restore_dashboard))
+        .route("/dashboards/:id/purge", delete(handlers::dashboard::purge_dashboard))
+        .route("/dashboards/:id/export", get(handlers::export::export_dashboard))
+        .route("/dashboards/:id/data", get(handlers::widget_data::get_dashboard_data))
+        .route("/dashboards/:id/widgets/:widget_id/data", get(handlers::widget_data::get_widget_data))
+        .route("/dashboards/:id/snapshots", get(handlers::snapshot::list_snapshots))
+        .route("/dashboards/:id/snapshots", post(handlers::snapshot::create_snapshot))
+        .route("/dashboards/:id/folder", put(handlers::folder::move_dashboard))
+        .route("/dashboards/:id/tags", put(handlers::tag::set_dashboard_tags))
+        .route("/dashboards/:id/favorite", put(handlers::tag::favorite_dashboard))
+        .route("/dashboards/:id/favorite", delete(handlers::tag::unfavorite_dashboard))
+        .route("/dashboards/:id/shares", get(handlers::share::list_shares))
+        .route("/dashboards/:id/shares", post(handlers::share::share_dashboard))
+        .route("/dashboards/:id/shares/:user_id", delete(handlers::share::unshare_dashboard))
+        
+        // Folder and tag routes (protected)
+        .route("/folders", get(handlers::folder::list_folders))
+        .route("/folders", post(handlers::folder::create_folder))
+        .route("/folders/:id", put(handlers::folder::update_folder))
+        .route("/folders/:id", delete(handlers::folder::delete_folder))
+        .route("/tags", get(handlers::tag::list_tags))
+        .route("/tags", post(handlers::tag::create_tag))
+        .route("/tags/:id", delete(handlers::tag::delete_tag))
+        
+        // Organization routes (protected)
+        .route("/organizations", get(handlers::organization::list_organizations))
+        .route("/organizations", post(handlers::organization::create_organization))
+        .route("/organizations/:id/members", get(handlers::organization::list_members))
+        .route("/organizations/:id/members", post(handlers::organization::add_member))
+        .route("/organizations/:id/members/:user_id", delete(handlers::organization::remove_member))
+        
+        // Snapshot routes (protected, except the public share link)
+        .route("/snapshots/:id", get(handlers::snapshot::get_snapshot))
+        .route("/snapshots/:id", delete(handlers::snapshot::delete_snapshot))
+        .route("/snapshots/:id/share", post(handlers::snapshot::share_snapshot))
+        .route("/snapshots/:id/share", delete(handlers::snapshot::unshare_snapshot))
+        .route("/shared/snapshots/:token", get(handlers::snapshot::get_shared_snapshot))
+        
+        // Uptime monitor routes (protected)
+        .route("/monitors", get(handlers::monitor::list_monitors))
+        .route("/monitors", post(handlers::monitor::create_monitor))
+        .route("/monitors/:id", get(handlers::monitor::get_monitor))
+        .route("/monitors/:id", put(handlers::monitor::update_monitor))
+        .route("/monitors/:id", delete(handlers::monitor::delete_monitor))
+        .route("/monitors/:id/checks", get(handlers::monitor::list_monitor_checks))
+        .route("/monitors/:id/incidents", get(handlers::monitor::list_monitor_incidents))
+        .route("/monitors/:id/sla", get(handlers::monitor::get_monitor_sla))
+        
+        // Alerting routes (protected)
+        .route("/alerts", get(handlers::alert::list_alert_rules))
+        .route("/alerts", post(handlers::alert::create_alert_rule))
+        .route("/alerts/:id", get(handlers::alert::get_alert_rule))
+        .route("/alerts/:id", put(handlers::alert::update_alert_rule))
+        .route("/alerts/:id", delete(handlers::alert::delete_alert_rule))
+        .route("/alerts/:id/events", get(handlers::alert::list_alert_events))
+        .route("/channels", get(handlers::channel::list_channels))
+        .route("/channels", post(handlers::channel::create_channel))
+        .route("/channels/:id", put(handlers::channel::update_channel))
+        .route("/channels/:id", delete(handlers::channel::delete_channel))
+        .route("/channels/:id/test", post(handlers::channel::test_channel))
+        
+        // Report schedule routes (protected)
+        .route("/reports", get(handlers::report::list_report_schedules))
+        .route("/reports", post(handlers::report::create_report_schedule))
+        .route("/reports/:id", get(handlers::report::get_report_schedule))
+        .route("/reports/:id", put(handlers::report::update_report_schedule))
+        .route("/reports/:id", delete(handlers::report::delete_report_schedule))
+        .route("/reports/:id/send", post(handlers::report::send_report_now))
+        .route("/reports/:id/deliveries", get(handlers::report::list_report_deliveries))
+        
+        // Push source routes (protected, except the signed ingest endpoint)
+        .route("/push-sources", get(handlers::push::list_push_sources))
+        .route("/push-sources", post(handlers::push::create_push_source))
+        .route("/push-sources/:id", get(handlers::push::get_push_source))
+        .route("/push-sources/:id", delete(handlers::push::delete_push_source))
+        .route("/push-sources/:id/rotate-secret", post(handlers::push::rotate_push_secret))
+        .route("/ingest/:id", post(handlers::push::ingest_push))
+        .route("/forge-accounts", get(handlers::forge::list_forge_accounts))
+        .route("/forge-accounts", post(handlers::forge::create_forge_account))
+        .route("/forge-accounts/:id", put(handlers::forge::update_forge_account))
+        .route("/forge-accounts/:id", delete(handlers::forge::delete_forge_account))
+        .route("/data-sources", get(handlers::data_source::list_data_sources))
+        .route("/data-sources", post(handlers::data_source::create_data_source))
+        .route("/data-sources/:id", put(handlers::data_source::update_data_source))
+        .route("/data-sources/:id", delete(handlers::data_source::delete_data_source))
+        .route("/data-sources/:id/test", post(handlers::data_source::test_data_source))
+        .route("/admin/github/quota", get(widgets::github::fetch_github_quota))
+        
+        // Template gallery routes (protected)
+        .route("/templates", get(handlers::template::list_templates))
+        .route("/templates", post(handlers::template::publish_template))
+        .route("/templates/:slug", get(handlers::template::get_template))
+        .route("/templates/:slug/versions", get(handlers::template::list_template_versions))
         
         // Widget data routes (protected)
         .route("/data/github", get(widgets::github::fetch_github_data))
         .route("/data/weather", get(widgets::weather::fetch_weather_data))
+        .route("/data/weather/locations", get(widgets::weather::search_weather_locations))
         .route("/data/news", get(widgets::news::fetch_news_data))
+        .route("/data/feed", get(widgets::feed::fetch_feed_data))
         .route("/data/crypto", get(widgets::crypto::fetch_crypto_data))
         .route("/data/status", get(widgets::status::fetch_status_data))
+        .route("/data/push", get(widgets::push::fetch_push_data))
+        .route("/data/http", post(widgets::http::fetch_http_data))
+        .route("/data/prometheus", get(widgets::prometheus::fetch_prometheus_data))
+        .route("/data/sql", post(widgets::sql::fetch_sql_data))
+        .route("/data/calendar", get(widgets::calendar::fetch_calendar_data))
+        .route("/data/activity", post(widgets::activity::fetch_activity_data))
+        .route("/data/:type/history", get(widgets::history::fetch_history))
 }
 
 /// Graceful shutdown signal handler
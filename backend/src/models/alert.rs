@@ -0,0 +1,406 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, FieldError, Result},
+    widgets::WidgetType,
+};
+
+/// Longest allowed `for_seconds` / `cooldown_seconds` (one week)
+pub const MAX_ALERT_DURATION_SECONDS: i32 = 7 * 86_400;
+
+/// Comparison between a widget value and a rule's threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum Comparison {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+    Ne,
+}
+
+impl Comparison {
+    pub fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Gt => value > threshold,
+            Comparison::Gte => value >= threshold,
+            Comparison::Lt => value < threshold,
+            Comparison::Lte => value <= threshold,
+            Comparison::Eq => value == threshold,
+            Comparison::Ne => value != threshold,
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Gt => ">",
+            Comparison::Gte => ">=",
+            Comparison::Lt => "<",
+            Comparison::Lte => "<=",
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+        }
+    }
+}
+
+/// Alert state machine: ok -> pending -> firing -> resolved -> ok
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum AlertState {
+    Ok,
+    /// Condition holds but not yet for `for_seconds`
+    Pending,
+    Firing,
+    /// Condition cleared after firing; becomes `ok` on the next evaluation
+    Resolved,
+}
+
+impl AlertState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertState::Ok => "ok",
+            AlertState::Pending => "pending",
+            AlertState::Firing => "firing",
+            AlertState::Resolved => "resolved",
+        }
+    }
+}
+
+/// Alert rule model
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AlertRule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub widget_type: WidgetType,
+    /// Coin symbol, URL or city, as used by the widget
+    pub series: String,
+    pub metric: String,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    /// How long the condition must hold before firing
+    pub for_seconds: i32,
+    /// Minimum time between two firing notifications
+    pub cooldown_seconds: i32,
+    pub channel_ids: Vec<Uuid>,
+    pub enabled: bool,
+    pub state: AlertState,
+    pub pending_since: Option<DateTime<Utc>>,
+    pub state_changed_at: DateTime<Utc>,
+    pub last_value: Option<f64>,
+    pub last_evaluated_at: Option<DateTime<Utc>>,
+    pub last_notified_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Outcome of evaluating a rule against a fresh value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation {
+    pub state: AlertState,
+    pub pending_since: Option<DateTime<Utc>>,
+    /// Whether the transition into `state` should be sent to the rule's channels
+    pub notify: bool,
+}
+
+impl AlertRule {
+    /// Advance the state machine with the latest value
+    pub fn evaluate(&self, value: f64, now: DateTime<Utc>) -> Evaluation {
+        let breached = self.comparison.holds(value, self.threshold);
+        let held_for = Duration::seconds(i64::from(self.for_seconds));
+
+        let cooled_down = self
+            .last_notified_at
+            .is_none_or(|at| now - at >= Duration::seconds(i64::from(self.cooldown_seconds)));
+        let fire = || Evaluation { state: AlertState::Firing, pending_since: None, notify: cooled_down };
+        // A firing suppressed by the cooldown is announced once the cooldown has passed
+        let announced = self.last_notified_at.is_some_and(|at| at >= self.state_changed_at);
+        let stay = |state| Evaluation { state, pending_since: self.pending_since, notify: false };
+        let reset = || Evaluation { state: AlertState::Ok, pending_since: None, notify: false };
+
+        match (self.state, breached) {
+            (AlertState::Ok | AlertState::Resolved, true) if self.for_seconds == 0 => fire(),
+            (AlertState::Ok | AlertState::Resolved, true) => Evaluation {
+                state: AlertState::Pending,
+                pending_since: Some(now),
+                notify: false,
+            },
+            (AlertState::Pending, true) => match self.pending_since {
+                Some(since) if now - since < held_for => stay(AlertState::Pending),
+                _ => fire(),
+            },
+            (AlertState::Firing, true) if !announced => fire(),
+            (AlertState::Firing, true) => stay(AlertState::Firing),
+            (AlertState::Firing, false) => Evaluation {
+                state: AlertState::Resolved,
+                pending_since: None,
+                // Only announce recovery if the firing itself was announced
+                notify: announced,
+            },
+            (AlertState::Ok | AlertState::Pending | AlertState::Resolved, false) => reset(),
+        }
+    }
+
+    /// One-line description used in notifications, e.g. "BTC price < 50000"
+    pub fn condition(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.series,
+            self.metric,
+            self.comparison.symbol(),
+            self.threshold
+        )
+    }
+}
+
+/// Create or replace alert rule request
+#[derive(Debug, Deserialize)]
+pub struct AlertRuleRequest {
+    pub name: String,
+    pub widget_type: WidgetType,
+    pub series: String,
+    pub metric: String,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    #[serde(default)]
+    pub for_seconds: i32,
+    #[serde(default)]
+    pub cooldown_seconds: i32,
+    #[serde(default)]
+    pub channel_ids: Vec<Uuid>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl AlertRuleRequest {
+    /// Check the rule definition, returning every problem found
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+
+        if self.name.trim().is_empty() {
+            errors.push(FieldError::new("name", "Alert name is required"));
+        }
+
+        if self.series.trim().is_empty() {
+            errors.push(FieldError::new("series", "Series is required"));
+        }
+
+        let metrics = self.widget_type.alert_metrics();
+        if metrics.is_empty() {
+            errors.push(FieldError::new(
+                "widget_type",
+                format!("Alerts are not supported for {} widgets", self.widget_type.as_str()),
+            ));
+        } else if !metrics.contains(&self.metric.as_str()) {
+            errors.push(FieldError::new(
+                "metric",
+                format!("Expected one of {}", metrics.join(", ")),
+            ));
+        }
+
+        if !self.threshold.is_finite() {
+            errors.push(FieldError::new("threshold", "Must be a finite number"));
+        }
+
+        for (field, value) in [("for_seconds", self.for_seconds), ("cooldown_seconds", self.cooldown_seconds)] {
+            if !(0..=MAX_ALERT_DURATION_SECONDS).contains(&value) {
+                errors.push(FieldError::new(
+                    field,
+                    format!("Must be between 0 and {}", MAX_ALERT_DURATION_SECONDS),
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidFields(errors))
+        }
+    }
+}
+
+/// One recorded state change
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AlertEvent {
+    pub id: i64,
+    pub from_state: AlertState,
+    pub to_state: AlertState,
+    pub value: Option<f64>,
+    pub notified: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(state: AlertState) -> AlertRule {
+        let created = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        AlertRule {
+            id: Uuid::nil(),
+            user_id: Uuid::nil(),
+            name: "BTC drop".to_string(),
+            widget_type: WidgetType::Crypto,
+            series: "BTC".to_string(),
+            metric: "price".to_string(),
+            comparison: Comparison::Lt,
+            threshold: 50_000.0,
+            for_seconds: 0,
+            cooldown_seconds: 0,
+            channel_ids: Vec::new(),
+            enabled: true,
+            state,
+            pending_since: None,
+            state_changed_at: created,
+            last_value: None,
+            last_evaluated_at: None,
+            last_notified_at: None,
+            created_at: created,
+            updated_at: created,
+        }
+    }
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-01-01T12:00:00Z").unwrap().with_timezone(&Utc) + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn breach_without_hold_fires_immediately() {
+        let evaluation = rule(AlertState::Ok).evaluate(40_000.0, at(0));
+        assert_eq!(evaluation.state, AlertState::Firing);
+        assert!(evaluation.notify);
+        assert_eq!(evaluation.pending_since, None);
+    }
+
+    #[test]
+    fn no_breach_stays_ok() {
+        let evaluation = rule(AlertState::Ok).evaluate(60_000.0, at(0));
+        assert_eq!(evaluation.state, AlertState::Ok);
+        assert!(!evaluation.notify);
+    }
+
+    #[test]
+    fn breach_with_hold_goes_pending_then_fires() {
+        let mut rule = rule(AlertState::Ok);
+        rule.for_seconds = 300;
+
+        let evaluation = rule.evaluate(40_000.0, at(0));
+        assert_eq!(evaluation.state, AlertState::Pending);
+        assert_eq!(evaluation.pending_since, Some(at(0)));
+        assert!(!evaluation.notify);
+
+        rule.state = AlertState::Pending;
+        rule.pending_since = evaluation.pending_since;
+
+        let evaluation = rule.evaluate(40_000.0, at(2));
+        assert_eq!(evaluation.state, AlertState::Pending);
+        assert_eq!(evaluation.pending_since, Some(at(0)));
+        assert!(!evaluation.notify);
+
+        let evaluation = rule.evaluate(40_000.0, at(5));
+        assert_eq!(evaluation.state, AlertState::Firing);
+        assert!(evaluation.notify);
+    }
+
+    #[test]
+    fn pending_resets_when_condition_clears() {
+        let mut rule = rule(AlertState::Pending);
+        rule.for_seconds = 300;
+        rule.pending_since = Some(at(0));
+
+        let evaluation = rule.evaluate(60_000.0, at(1));
+        assert_eq!(evaluation.state, AlertState::Ok);
+        assert_eq!(evaluation.pending_since, None);
+        assert!(!evaluation.notify);
+    }
+
+    #[test]
+    fn firing_stays_firing_without_renotifying() {
+        let mut rule = rule(AlertState::Firing);
+        rule.last_notified_at = Some(at(0));
+
+        let evaluation = rule.evaluate(40_000.0, at(10));
+        assert_eq!(evaluation.state, AlertState::Firing);
+        assert!(!evaluation.notify);
+    }
+
+    #[test]
+    fn cooldown_suppresses_refiring_notification() {
+        let mut rule = rule(AlertState::Resolved);
+        rule.cooldown_seconds = 3_600;
+        rule.last_notified_at = Some(at(0));
+        rule.state_changed_at = at(10);
+
+        let evaluation = rule.evaluate(40_000.0, at(30));
+        assert_eq!(evaluation.state, AlertState::Firing);
+        assert!(!evaluation.notify);
+
+        // Carry the suppressed firing forward as the alerts job stores it
+        rule.state = evaluation.state;
+        rule.pending_since = evaluation.pending_since;
+        rule.state_changed_at = at(30);
+
+        let evaluation = rule.evaluate(40_000.0, at(45));
+        assert_eq!(evaluation.state, AlertState::Firing);
+        assert!(!evaluation.notify);
+
+        let evaluation = rule.evaluate(40_000.0, at(60));
+        assert_eq!(evaluation.state, AlertState::Firing);
+        assert!(evaluation.notify);
+
+        // Once announced it isn't repeated, and its recovery is announced
+        rule.last_notified_at = Some(at(60));
+
+        let evaluation = rule.evaluate(40_000.0, at(120));
+        assert_eq!(evaluation.state, AlertState::Firing);
+        assert!(!evaluation.notify);
+
+        let evaluation = rule.evaluate(60_000.0, at(130));
+        assert_eq!(evaluation.state, AlertState::Resolved);
+        assert!(evaluation.notify);
+    }
+
+    #[test]
+    fn recovery_of_unannounced_firing_is_not_announced() {
+        let mut rule = rule(AlertState::Firing);
+        rule.cooldown_seconds = 3_600;
+        rule.last_notified_at = Some(at(0));
+        rule.state_changed_at = at(30);
+
+        let evaluation = rule.evaluate(60_000.0, at(40));
+        assert_eq!(evaluation.state, AlertState::Resolved);
+        assert!(!evaluation.notify);
+    }
+
+    #[test]
+    fn recovery_is_announced_only_after_an_announced_firing() {
+        let mut rule = rule(AlertState::Firing);
+        rule.state_changed_at = at(0);
+
+        rule.last_notified_at = Some(at(0));
+        let evaluation = rule.evaluate(60_000.0, at(10));
+        assert_eq!(evaluation.state, AlertState::Resolved);
+        assert!(evaluation.notify);
+
+        rule.last_notified_at = None;
+        let evaluation = rule.evaluate(60_000.0, at(10));
+        assert_eq!(evaluation.state, AlertState::Resolved);
+        assert!(!evaluation.notify);
+    }
+
+    #[test]
+    fn resolved_returns_to_ok() {
+        let evaluation = rule(AlertState::Resolved).evaluate(60_000.0, at(0));
+        assert_eq!(evaluation.state, AlertState::Ok);
+        assert!(!evaluation.notify);
+    }
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::error::FieldError;
+
+/// Where a notification channel delivers, and how
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ChannelConfig {
+    /// POST the JSON payload to a URL
+    Webhook {
+        url: String,
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        headers: HashMap<String, String>,
+    },
+    /// POST a Slack-compatible `{"text": ...}` message to an incoming-webhook URL
+    Slack { url: String },
+    /// Send an email through the configured mailer
+    Email { to: Vec<String> },
+}
+
+impl ChannelConfig {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ChannelConfig::Webhook { .. } => "webhook",
+            ChannelConfig::Slack { .. } => "slack",
+            ChannelConfig::Email { .. } => "email",
+        }
+    }
+
+    /// The URL a webhook or Slack channel posts to
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            ChannelConfig::Webhook { url, .. } | ChannelConfig::Slack { url } => Some(url),
+            ChannelConfig::Email { .. } => None,
+        }
+    }
+
+    /// Check the channel's target, returning every problem found
+    pub fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
+        match self {
+            ChannelConfig::Webhook { url, headers } => {
+                check_url(url, &mut errors);
+                for name in headers.keys() {
+                    if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+                        errors.push(FieldError::new("headers", format!("Invalid header name: {}", name)));
+                    }
+                }
+            }
+            ChannelConfig::Slack { url } => check_url(url, &mut errors),
+            ChannelConfig::Email { to } => {
+                if to.is_empty() {
+                    errors.push(FieldError::new("to", "At least one recipient is required"));
+                }
+                for address in to {
+                    if address.parse::<lettre::Address>().is_err() {
+                        errors.push(FieldError::new("to", format!("Invalid email address: {}", address)));
+                    }
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+fn check_url(url: &str, errors: &mut Vec<FieldError>) {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+        _ => errors.push(FieldError::new("url", "Must be an http(s) URL")),
+    }
+}
+
+/// Notification channel model
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct NotificationChannel {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    #[serde(flatten)]
+    pub config: sqlx::types::Json<ChannelConfig>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create or replace channel request, e.g. `{"name": "ops", "kind": "slack", "url": "..."}`
+#[derive(Debug, Deserialize)]
+pub struct ChannelRequest {
+    pub name: String,
+    #[serde(flatten)]
+    pub config: ChannelConfig,
+}
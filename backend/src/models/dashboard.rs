@@ -1,8 +1,20 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
+use crate::{
+    error::{AppError, Result},
+    widgets::WidgetType,
+};
+
+/// Page size used when the client doesn't ask for one
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+
+/// Largest page a client may request
+pub const MAX_PAGE_SIZE: i64 = 100;
+
 /// Dashboard model
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Dashboard {
@@ -11,8 +23,13 @@ pub struct Dashboard {
     pub name: String,
     pub layout_json: JsonValue,
     pub settings_json: JsonValue,
+    pub source_dashboard_id: Option<Uuid>,
+    pub folder_id: Option<Uuid>,
+    /// Organization whose members can read the dashboard
+    pub organization_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 /// Create dashboard request
@@ -33,6 +50,22 @@ pub struct UpdateDashboardRequest {
     pub settings_json: Option<JsonValue>,
 }
 
+/// Clone dashboard request
+#[derive(Debug, Default, Deserialize)]
+pub struct CloneDashboardRequest {
+    /// Name of the copy; defaults to "<source name> (copy)"
+    pub name: Option<String>,
+    /// Organization the copy belongs to; the caller must be a member
+    pub organization_id: Option<Uuid>,
+}
+
+/// Favorite dashboard request
+#[derive(Debug, Default, Deserialize)]
+pub struct FavoriteRequest {
+    #[serde(default)]
+    pub pinned: bool,
+}
+
 /// Dashboard response
 #[derive(Debug, Serialize)]
 pub struct DashboardResponse {
@@ -41,8 +74,23 @@ pub struct DashboardResponse {
     pub name: String,
     pub layout_json: JsonValue,
     pub settings_json: JsonValue,
+    pub source_dashboard_id: Option<Uuid>,
+    pub folder_id: Option<Uuid>,
+    pub organization_id: Option<Uuid>,
+    /// Tag names; only loaded by the list and get endpoints
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /// Whether the caller favorited the dashboard; only loaded by the list and get endpoints
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub favorite: Option<bool>,
+    /// Whether the caller pinned the dashboard; only loaded by the list and get endpoints
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set while the dashboard is in the trash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl From<Dashboard> for DashboardResponse {
@@ -53,8 +101,150 @@ impl From<Dashboard> for DashboardResponse {
             name: dashboard.name,
             layout_json: dashboard.layout_json,
             settings_json: dashboard.settings_json,
+            source_dashboard_id: dashboard.source_dashboard_id,
+            folder_id: dashboard.folder_id,
+            organization_id: dashboard.organization_id,
+            tags: None,
+            favorite: None,
+            pinned: None,
             created_at: dashboard.created_at,
             updated_at: dashboard.updated_at,
+            deleted_at: dashboard.deleted_at,
+        }
+    }
+}
+
+/// Field dashboards can be sorted by
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DashboardSort {
+    Name,
+    Created,
+    #[default]
+    Updated,
+}
+
+impl DashboardSort {
+    /// Column backing this sort field
+    pub fn column(&self) -> &'static str {
+        match self {
+            DashboardSort::Name => "name",
+            DashboardSort::Created => "created_at",
+            DashboardSort::Updated => "updated_at",
+        }
+    }
+}
+
+/// Sort direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+/// Dashboard listing query parameters
+#[derive(Debug, Deserialize)]
+pub struct ListDashboardsQuery {
+    /// Case-insensitive substring match on the dashboard name
+    pub q: Option<String>,
+    #[serde(default)]
+    pub sort: DashboardSort,
+    /// Defaults to ascending for names and descending for timestamps
+    pub order: Option<SortOrder>,
+    /// Only dashboards containing at least one widget of this type
+    pub widget_type: Option<WidgetType>,
+    /// Only dashboards carrying this tag name
+    pub tag: Option<String>,
+    /// Only dashboards in this folder; `root` selects dashboards outside any folder
+    pub folder: Option<String>,
+    /// Only (non-)favorited dashboards
+    pub favorite: Option<bool>,
+    /// Only (non-)pinned dashboards
+    pub pinned: Option<bool>,
+    /// Only dashboards that are (not) shared with other users or an organization
+    pub shared: Option<bool>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+impl ListDashboardsQuery {
+    pub fn order(&self) -> SortOrder {
+        self.order.unwrap_or(match self.sort {
+            DashboardSort::Name => SortOrder::Asc,
+            DashboardSort::Created | DashboardSort::Updated => SortOrder::Desc,
+        })
+    }
+
+    /// Parse the folder filter: `Some(None)` is the root, `Some(Some(id))` a folder
+    pub fn folder(&self) -> Result<Option<Option<Uuid>>> {
+        match self.folder.as_deref() {
+            None => Ok(None),
+            Some("root") => Ok(Some(None)),
+            Some(folder) => Uuid::parse_str(folder)
+                .map(|id| Some(Some(id)))
+                .map_err(|_| AppError::Validation("folder must be a folder id or 'root'".to_string())),
+        }
+    }
+
+    pub fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+            .clamp(1, MAX_PAGE_SIZE)
+    }
+}
+
+/// Opaque keyset cursor: the sort value and id of the last row on a page
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DashboardCursor {
+    pub sort: DashboardSort,
+    pub order: SortOrder,
+    pub value: String,
+    pub id: Uuid,
+}
+
+impl DashboardCursor {
+    /// Cursor pointing just past `dashboard` for the given ordering
+    pub fn after(dashboard: &DashboardResponse, sort: DashboardSort, order: SortOrder) -> Self {
+        let value = match sort {
+            DashboardSort::Name => dashboard.name.clone(),
+            DashboardSort::Created => dashboard.created_at.to_rfc3339(),
+            DashboardSort::Updated => dashboard.updated_at.to_rfc3339(),
+        };
+
+        Self {
+            sort,
+            order,
+            value,
+            id: dashboard.id,
         }
     }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self> {
+        URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| AppError::Validation("Invalid cursor".to_string()))
+    }
+
+    /// Timestamp value for `created`/`updated` cursors
+    pub fn timestamp(&self) -> Result<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.value)
+            .map(|value| value.with_timezone(&Utc))
+            .map_err(|_| AppError::Validation("Invalid cursor".to_string()))
+    }
+}
+
+/// One page of dashboards
+#[derive(Debug, Serialize)]
+pub struct DashboardPage {
+    pub items: Vec<DashboardResponse>,
+    /// Number of dashboards matching the filters across all pages
+    pub total: i64,
+    pub next_cursor: Option<String>,
 }
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgConnectOptions;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+
+/// A Postgres database SQL widgets can query
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct DataSource {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(skip_serializing)]
+    pub connection_encrypted: Vec<u8>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Register data source request
+#[derive(Debug, Deserialize)]
+pub struct CreateDataSourceRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// `postgres://` URL; stored encrypted and never returned
+    pub connection_string: String,
+}
+
+/// Update data source request
+#[derive(Debug, Deserialize)]
+pub struct UpdateDataSourceRequest {
+    pub name: Option<String>,
+    #[serde(default, with = "super::folder::double_option")]
+    pub description: Option<Option<String>>,
+    pub connection_string: Option<String>,
+}
+
+/// Check that a connection string is a Postgres URL sqlx can use
+pub fn validate_connection_string(connection_string: &str) -> Result<PgConnectOptions> {
+    if !connection_string.starts_with("postgres://") && !connection_string.starts_with("postgresql://") {
+        return Err(AppError::Validation(
+            "Connection string must be a postgres:// URL".to_string(),
+        ));
+    }
+
+    connection_string
+        .parse::<PgConnectOptions>()
+        .map_err(|e| AppError::Validation(format!("Invalid connection string: {}", e)))
+}
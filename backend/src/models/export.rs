@@ -0,0 +1,367 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+
+use super::Dashboard;
+
+/// `kind` marker identifying an exported dashboard document
+pub const DASHBOARD_DOCUMENT_KIND: &str = "insightboard/dashboard";
+
+/// Current export schema version.
+///
+/// Version 1 is the raw `DashboardResponse` shape (`name`, `layout_json`,
+/// `settings_json`) that people saved before export existed; it has no
+/// `kind`/`version` fields and is migrated on import.
+pub const DASHBOARD_DOCUMENT_VERSION: u64 = 2;
+
+/// Widget config keys whose values are never exported; header maps often carry credentials
+/// under custom names, so they go entirely
+const SECRET_KEY_MARKERS: &[&str] = &[
+    "headers",
+    "token",
+    "secret",
+    "password",
+    "api_key",
+    "apikey",
+    "authorization",
+    "credential",
+];
+
+/// Portable, versioned dashboard document
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DashboardDocument {
+    pub kind: String,
+    pub version: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exported_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<Uuid>,
+    pub dashboard: DashboardSpec,
+}
+
+/// The portable part of a dashboard
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DashboardSpec {
+    pub name: String,
+    #[serde(default)]
+    pub layout: JsonValue,
+    #[serde(default)]
+    pub settings: JsonValue,
+}
+
+/// Output format for exported documents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "application/json",
+            ExportFormat::Yaml => "application/yaml",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Yaml => "yaml",
+        }
+    }
+}
+
+/// Export query parameters
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Import query parameters
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    /// Overrides the name stored in the document
+    pub name: Option<String>,
+}
+
+impl DashboardDocument {
+    /// Build an export document from a stored dashboard, stripping secrets
+    pub fn from_dashboard(dashboard: Dashboard) -> Self {
+        let mut layout = dashboard.layout_json;
+        strip_secrets(&mut layout);
+        let mut settings = dashboard.settings_json;
+        strip_secrets(&mut settings);
+
+        Self {
+            kind: DASHBOARD_DOCUMENT_KIND.to_string(),
+            version: DASHBOARD_DOCUMENT_VERSION,
+            exported_at: Some(Utc::now()),
+            source_id: Some(dashboard.id),
+            dashboard: DashboardSpec {
+                name: dashboard.name,
+                layout,
+                settings,
+            },
+        }
+    }
+
+    /// Serialize the document in the requested format
+    pub fn render(&self, format: ExportFormat) -> Result<String> {
+        match format {
+            ExportFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|e| AppError::Internal(format!("Failed to serialize export: {}", e))),
+            ExportFormat::Yaml => serde_yaml::to_string(self)
+                .map_err(|e| AppError::Internal(format!("Failed to serialize export: {}", e))),
+        }
+    }
+
+    /// Parse a JSON or YAML document, migrating older schema versions
+    pub fn parse(body: &[u8], yaml: bool) -> Result<Self> {
+        let value: JsonValue = if yaml {
+            serde_yaml::from_slice(body)
+                .map_err(|e| AppError::Validation(format!("Invalid YAML document: {}", e)))?
+        } else {
+            serde_json::from_slice(body)
+                .map_err(|e| AppError::Validation(format!("Invalid JSON document: {}", e)))?
+        };
+
+        let value = migrate_document(value)?;
+
+        serde_json::from_value(value)
+            .map_err(|e| AppError::Validation(format!("Invalid dashboard document: {}", e)))
+    }
+}
+
+/// Upgrade a document to the current schema version, one version at a time
+fn migrate_document(mut value: JsonValue) -> Result<JsonValue> {
+    if !value.is_object() {
+        return Err(AppError::Validation("Dashboard document must be an object".to_string()));
+    }
+
+    if let Some(kind) = value.get("kind") {
+        if kind.as_str() != Some(DASHBOARD_DOCUMENT_KIND) {
+            return Err(AppError::Validation(format!(
+                "Unsupported document kind {}, expected '{}'",
+                kind, DASHBOARD_DOCUMENT_KIND
+            )));
+        }
+    }
+
+    let mut version = match value.get("version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| AppError::Validation("Document version must be a number".to_string()))?,
+    };
+
+    if version > DASHBOARD_DOCUMENT_VERSION {
+        return Err(AppError::Validation(format!(
+            "Document version {} is newer than the supported version {}",
+            version, DASHBOARD_DOCUMENT_VERSION
+        )));
+    }
+
+    while version < DASHBOARD_DOCUMENT_VERSION {
+        value = match version {
+            1 => migrate_v1_to_v2(value),
+            _ => {
+                return Err(AppError::Validation(format!(
+                    "Unsupported document version {}",
+                    version
+                )))
+            }
+        };
+        version += 1;
+    }
+
+    Ok(value)
+}
+
+/// v1 (raw API response) -> v2 (`kind`/`version` envelope around `dashboard`)
+fn migrate_v1_to_v2(mut value: JsonValue) -> JsonValue {
+    let take = |value: &mut JsonValue, key: &str| {
+        value
+            .as_object_mut()
+            .and_then(|fields| fields.remove(key))
+            .unwrap_or(JsonValue::Null)
+    };
+
+    let mut layout = take(&mut value, "layout_json");
+    strip_secrets(&mut layout);
+    let mut settings = take(&mut value, "settings_json");
+    strip_secrets(&mut settings);
+
+    json!({
+        "kind": DASHBOARD_DOCUMENT_KIND,
+        "version": 2,
+        "source_id": take(&mut value, "id"),
+        "dashboard": {
+            "name": take(&mut value, "name"),
+            "layout": layout,
+            "settings": settings,
+        },
+    })
+}
+
+/// Remove secret-looking keys anywhere in a JSON value, and blank the defaults of secret-named variables
+pub fn strip_secrets(value: &mut JsonValue) {
+    match value {
+        JsonValue::Object(fields) => {
+            fields.retain(|key, _| !is_secret_key(key));
+            fields.values_mut().for_each(strip_secrets);
+
+            // Variables carry their value under `default`; enum options are public anyway
+            let secret_variable = fields.get("name").and_then(JsonValue::as_str).is_some_and(is_secret_key)
+                && fields.get("kind").and_then(JsonValue::as_str) != Some("enum");
+            if secret_variable {
+                match fields.get_mut("default") {
+                    Some(JsonValue::Array(items)) => items.clear(),
+                    Some(default @ JsonValue::String(_)) => *default = JsonValue::String(String::new()),
+                    _ => {}
+                }
+            }
+        }
+        JsonValue::Array(items) => items.iter_mut().for_each(strip_secrets),
+        _ => {}
+    }
+}
+
+/// Whether a key or variable name looks like it holds a credential
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase().replace('-', "_");
+    SECRET_KEY_MARKERS.iter().any(|marker| key.contains(marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_secrets_removes_credentials_and_secret_variable_defaults() {
+        let mut settings = json!({
+            "title": "Ops",
+            "variables": [
+                { "name": "city", "default": "Oslo" },
+                { "name": "api_token", "default": "ghp_live" },
+                { "name": "auth_tokens", "kind": "list", "default": ["a", "b"] },
+                { "name": "token_scope", "kind": "enum", "options": ["read", "write"], "default": "read" }
+            ]
+        });
+        strip_secrets(&mut settings);
+        assert_eq!(
+            settings,
+            json!({
+                "title": "Ops",
+                "variables": [
+                    { "name": "city", "default": "Oslo" },
+                    { "name": "api_token", "default": "" },
+                    { "name": "auth_tokens", "kind": "list", "default": [] },
+                    { "name": "token_scope", "kind": "enum", "options": ["read", "write"], "default": "read" }
+                ]
+            })
+        );
+
+        let mut layout = json!({
+            "widgets": [{
+                "type": "http",
+                "config": {
+                    "url": "https://api.example.com",
+                    "headers": { "X-Session": "abc" },
+                    "api_key": "xyz",
+                    "nested": { "Password": "hunter2", "label": "kept" }
+                }
+            }]
+        });
+        strip_secrets(&mut layout);
+        assert_eq!(
+            layout,
+            json!({
+                "widgets": [{
+                    "type": "http",
+                    "config": { "url": "https://api.example.com", "nested": { "label": "kept" } }
+                }]
+            })
+        );
+    }
+
+    fn layout_with_secrets() -> JsonValue {
+        json!({
+            "widgets": [{
+                "type": "github",
+                "config": { "repo": "acme/api", "token": "ghp_live" }
+            }]
+        })
+    }
+
+    fn settings_with_secrets() -> JsonValue {
+        json!({
+            "refresh": 60,
+            "webhook_secret": "s3cret",
+            "variables": [{ "name": "api_key", "default": "live-key" }]
+        })
+    }
+
+    fn assert_stripped(document: &DashboardDocument) {
+        assert_eq!(document.dashboard.name, "Ops");
+        assert_eq!(
+            document.dashboard.layout,
+            json!({ "widgets": [{ "type": "github", "config": { "repo": "acme/api" } }] })
+        );
+        assert_eq!(
+            document.dashboard.settings,
+            json!({ "refresh": 60, "variables": [{ "name": "api_key", "default": "" }] })
+        );
+    }
+
+    #[test]
+    fn exported_documents_import_without_secrets() {
+        let now = Utc::now();
+        let dashboard = Dashboard {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: "Ops".to_string(),
+            layout_json: layout_with_secrets(),
+            settings_json: settings_with_secrets(),
+            source_dashboard_id: None,
+            folder_id: None,
+            organization_id: None,
+            created_at: now,
+            updated_at: now,
+            deleted_at: None,
+        };
+        let id = dashboard.id;
+        let document = DashboardDocument::from_dashboard(dashboard);
+
+        for (format, yaml) in [(ExportFormat::Json, false), (ExportFormat::Yaml, true)] {
+            let body = document.render(format).unwrap();
+            assert!(!body.contains("ghp_live") && !body.contains("s3cret") && !body.contains("live-key"));
+
+            let imported = DashboardDocument::parse(body.as_bytes(), yaml).unwrap();
+            assert_eq!(imported.version, DASHBOARD_DOCUMENT_VERSION);
+            assert_eq!(imported.source_id, Some(id));
+            assert_stripped(&imported);
+        }
+    }
+
+    #[test]
+    fn v1_documents_import_without_secrets() {
+        let v1 = json!({
+            "id": Uuid::new_v4(),
+            "name": "Ops",
+            "layout_json": layout_with_secrets(),
+            "settings_json": settings_with_secrets(),
+        });
+
+        let imported = DashboardDocument::parse(v1.to_string().as_bytes(), false).unwrap();
+        assert_eq!(imported.kind, DASHBOARD_DOCUMENT_KIND);
+        assert_eq!(imported.version, DASHBOARD_DOCUMENT_VERSION);
+        assert_stripped(&imported);
+    }
+}
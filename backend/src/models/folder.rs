@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Folder model; folders nest through `parent_id`
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Folder {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create folder request
+#[derive(Debug, Deserialize)]
+pub struct CreateFolderRequest {
+    pub name: String,
+    pub parent_id: Option<Uuid>,
+}
+
+/// Update folder request
+#[derive(Debug, Deserialize)]
+pub struct UpdateFolderRequest {
+    pub name: Option<String>,
+    /// New parent; omit to keep the current one, `null` to move to the root
+    #[serde(default, with = "double_option")]
+    pub parent_id: Option<Option<Uuid>>,
+}
+
+/// Move dashboard request
+#[derive(Debug, Deserialize)]
+pub struct MoveDashboardRequest {
+    /// Target folder, or `null` for the root
+    pub folder_id: Option<Uuid>,
+}
+
+/// Distinguish a missing field (`None`) from an explicit `null` (`Some(None)`)
+pub(crate) mod double_option {
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+    where
+        T: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Option::<T>::deserialize(deserializer).map(Some)
+    }
+}
@@ -0,0 +1,85 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+use crate::error::{AppError, Result};
+
+/// A code forge activity can be read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum ForgeKind {
+    Github,
+    Gitlab,
+    Gitea,
+}
+
+impl ForgeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ForgeKind::Github => "github",
+            ForgeKind::Gitlab => "gitlab",
+            ForgeKind::Gitea => "gitea",
+        }
+    }
+
+    /// Public instance used when no base URL is given (Gitea has none)
+    pub fn default_base_url(&self) -> Option<&'static str> {
+        match self {
+            ForgeKind::Github => Some("https://api.github.com"),
+            ForgeKind::Gitlab => Some("https://gitlab.com"),
+            ForgeKind::Gitea => None,
+        }
+    }
+}
+
+/// A user's token for a forge instance
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ForgeAccount {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub forge: ForgeKind,
+    pub base_url: String,
+    #[serde(skip_serializing)]
+    pub token_encrypted: Vec<u8>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create forge account request
+#[derive(Debug, Deserialize)]
+pub struct CreateForgeAccountRequest {
+    pub name: String,
+    pub forge: ForgeKind,
+    /// API root (`https://github.example.com/api/v3`) or instance URL for GitLab/Gitea
+    pub base_url: Option<String>,
+    /// Stored encrypted and never returned
+    pub token: String,
+}
+
+/// Update forge account request
+#[derive(Debug, Deserialize)]
+pub struct UpdateForgeAccountRequest {
+    pub name: Option<String>,
+    pub base_url: Option<String>,
+    pub token: Option<String>,
+}
+
+/// Resolve and normalise a forge base URL (no trailing slash)
+pub fn forge_base_url(forge: ForgeKind, base_url: Option<&str>) -> Result<String> {
+    let base_url = match base_url.map(str::trim).filter(|url| !url.is_empty()) {
+        Some(url) => url,
+        None => forge.default_base_url().ok_or_else(|| {
+            AppError::Validation(format!("A base URL is required for {}", forge.as_str()))
+        })?,
+    };
+
+    match Url::parse(base_url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.query().is_none() => {
+            Ok(base_url.trim_end_matches('/').to_string())
+        }
+        _ => Err(AppError::Validation(format!("Invalid base URL: {}", base_url))),
+    }
+}
@@ -0,0 +1,272 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, FieldError, Result},
+    widgets::{WidgetConfig, WidgetType},
+};
+
+use super::{referenced_variables, substitute_variables, DashboardVariable};
+
+/// Number of columns in the frontend's GridStack grid
+pub const GRID_COLUMNS: u32 = 12;
+
+/// Shortest refresh interval a dashboard may request (seconds)
+pub const MIN_REFRESH_INTERVAL: u32 = 10;
+
+/// A widget placed on the dashboard grid (mirrors the frontend's `WidgetLayout`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WidgetLayout {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub widget_type: WidgetType,
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+    #[serde(default)]
+    pub config: WidgetConfig,
+}
+
+impl WidgetLayout {
+    /// Whether two widgets occupy at least one common grid cell
+    fn overlaps(&self, other: &WidgetLayout) -> bool {
+        self.x < other.x.saturating_add(other.w)
+            && other.x < self.x.saturating_add(self.w)
+            && self.y < other.y.saturating_add(other.h)
+            && other.y < self.y.saturating_add(self.h)
+    }
+}
+
+/// Dashboard layout stored in `layout_json`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DashboardLayout {
+    #[serde(default)]
+    pub widgets: Vec<WidgetLayout>,
+}
+
+impl DashboardLayout {
+    /// Parse a layout from JSON.
+    ///
+    /// Accepts both the frontend's `{"widgets": [...]}` shape and a bare widget array
+    /// (the column default); `null` is treated as an empty layout.
+    pub fn from_json(value: JsonValue) -> std::result::Result<Self, Vec<FieldError>> {
+        let value = match value {
+            JsonValue::Null => return Ok(Self::default()),
+            JsonValue::Array(widgets) => json!({ "widgets": widgets }),
+            other => other,
+        };
+
+        deserialize_field("layout_json", value).map_err(|e| vec![e])
+    }
+
+    /// Check grid placement, widget ids and each widget's config.
+    ///
+    /// Configs are validated with `${var}` references replaced by the variables' defaults.
+    pub fn validate(&self, variables: &[DashboardVariable]) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        let mut seen_ids = HashSet::new();
+        let defaults = super::resolve_variables(variables, &Default::default()).unwrap_or_default();
+
+        for (index, widget) in self.widgets.iter().enumerate() {
+            let prefix = format!("layout_json.widgets[{}]", index);
+
+            if widget.id.trim().is_empty() {
+                errors.push(FieldError::new("id", "Widget id is required").nested(&prefix));
+            } else if !seen_ids.insert(widget.id.as_str()) {
+                errors.push(
+                    FieldError::new("id", format!("Duplicate widget id '{}'", widget.id))
+                        .nested(&prefix),
+                );
+            }
+
+            if widget.w == 0 {
+                errors.push(FieldError::new("w", "Width must be at least 1").nested(&prefix));
+            }
+            if widget.h == 0 {
+                errors.push(FieldError::new("h", "Height must be at least 1").nested(&prefix));
+            }
+            if widget.x.saturating_add(widget.w) > GRID_COLUMNS {
+                errors.push(
+                    FieldError::new(
+                        "x",
+                        format!("Widget extends past the {}-column grid", GRID_COLUMNS),
+                    )
+                    .nested(&prefix),
+                );
+            }
+
+            if let Some(other) = self.widgets[..index]
+                .iter()
+                .position(|other| widget.overlaps(other))
+            {
+                errors.push(
+                    FieldError::new(
+                        "y",
+                        format!("Widget overlaps layout_json.widgets[{}]", other),
+                    )
+                    .nested(&prefix),
+                );
+            }
+
+            let config_prefix = format!("{}.config", prefix);
+            let mut config_errors = Vec::new();
+            for name in referenced_variables(&widget.config) {
+                if !defaults.contains_key(&name) {
+                    config_errors.push(FieldError::new(
+                        config_prefix.clone(),
+                        format!("Unknown dashboard variable '${{{}}}'", name),
+                    ));
+                }
+            }
+
+            if config_errors.is_empty() {
+                let config = substitute_variables(&widget.config, &defaults);
+                config_errors.extend(
+                    widget
+                        .widget_type
+                        .validate_config(&config)
+                        .into_iter()
+                        .map(|e| e.nested(&config_prefix)),
+                );
+            }
+            errors.extend(config_errors);
+        }
+
+        errors
+    }
+}
+
+/// Dashboard color theme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+/// Dashboard settings stored in `settings_json` (mirrors the frontend's `DashboardSettings`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DashboardSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<Theme>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variables: Vec<DashboardVariable>,
+}
+
+impl DashboardSettings {
+    /// Parse settings from JSON; `null` is treated as empty settings
+    pub fn from_json(value: JsonValue) -> std::result::Result<Self, Vec<FieldError>> {
+        if value.is_null() {
+            return Ok(Self::default());
+        }
+
+        deserialize_field("settings_json", value).map_err(|e| vec![e])
+    }
+
+    /// Check value ranges
+    pub fn validate(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
+        if let Some(interval) = self.refresh_interval {
+            if interval < MIN_REFRESH_INTERVAL {
+                errors.push(FieldError::new(
+                    "settings_json.refresh_interval",
+                    format!("Refresh interval must be at least {} seconds", MIN_REFRESH_INTERVAL),
+                ));
+            }
+        }
+
+        let mut names = HashSet::new();
+        for (index, variable) in self.variables.iter().enumerate() {
+            let prefix = format!("settings_json.variables[{}]", index);
+            errors.extend(variable.validate(&prefix));
+            if !names.insert(variable.name.as_str()) {
+                errors.push(
+                    FieldError::new("name", format!("Duplicate variable '{}'", variable.name))
+                        .nested(&prefix),
+                );
+            }
+        }
+
+        errors
+    }
+}
+
+/// Generate a fresh widget id in the frontend's `widget-...` style
+pub fn new_widget_id() -> String {
+    format!("widget-{}", Uuid::new_v4().simple())
+}
+
+/// Give every widget in a layout (either stored shape) a fresh id
+pub fn regenerate_widget_ids(layout: &mut JsonValue) {
+    let widgets = match layout {
+        JsonValue::Array(widgets) => Some(widgets),
+        JsonValue::Object(fields) => fields.get_mut("widgets").and_then(JsonValue::as_array_mut),
+        _ => None,
+    };
+
+    for widget in widgets.into_iter().flatten() {
+        if let Some(fields) = widget.as_object_mut() {
+            fields.insert("id".to_string(), JsonValue::String(new_widget_id()));
+        }
+    }
+}
+
+/// Validate a dashboard's `layout_json` and `settings_json` together, returning both in
+/// normalized form (widget configs may reference the variables defined in the settings)
+pub fn validate_dashboard(layout_json: JsonValue, settings_json: JsonValue) -> Result<(JsonValue, JsonValue)> {
+    let layout = DashboardLayout::from_json(layout_json);
+    let settings = DashboardSettings::from_json(settings_json);
+
+    let (layout, settings) = match (layout, settings) {
+        (Ok(layout), Ok(settings)) => (layout, settings),
+        (layout, settings) => {
+            let errors = layout
+                .err()
+                .into_iter()
+                .chain(settings.err())
+                .flatten()
+                .collect();
+            return Err(AppError::InvalidFields(errors));
+        }
+    };
+
+    let mut errors = settings.validate();
+    errors.extend(layout.validate(&settings.variables));
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    let layout_json = serde_json::to_value(&layout)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize layout: {}", e)))?;
+    let settings_json = serde_json::to_value(&settings)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize settings: {}", e)))?;
+
+    Ok((layout_json, settings_json))
+}
+
+/// Deserialize a value, reporting the failing path below `root` on error
+fn deserialize_field<T: DeserializeOwned>(
+    root: &str,
+    value: JsonValue,
+) -> std::result::Result<T, FieldError> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        let path = e.path().to_string();
+        let field = if path == "." {
+            root.to_string()
+        } else {
+            format!("{}.{}", root, path)
+        };
+        FieldError::new(field, e.inner().to_string())
+    })
+}
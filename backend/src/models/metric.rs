@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::widgets::WidgetType;
+
+/// Most buckets a history query returns when the client doesn't pick a bucket size
+pub const MAX_HISTORY_POINTS: i64 = 200;
+
+/// Smallest bucket a history query may ask for, in seconds
+pub const MIN_BUCKET_SECONDS: i64 = 60;
+
+/// One numeric value taken from a widget fetch
+#[derive(Debug, Clone)]
+pub struct MetricPoint {
+    /// What the value is about: a coin symbol, a URL, a city
+    pub series: String,
+    pub metric: &'static str,
+    pub value: f64,
+}
+
+impl MetricPoint {
+    pub fn new(series: impl Into<String>, metric: &'static str, value: f64) -> Self {
+        Self {
+            series: series.into(),
+            metric,
+            value,
+        }
+    }
+}
+
+/// History range query
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    /// Coin symbol, URL or city, as passed to the data endpoint
+    pub series: String,
+    /// Defaults to the widget type's only metric
+    pub metric: Option<String>,
+    /// Defaults to 24 hours before `to`
+    pub from: Option<DateTime<Utc>>,
+    /// Defaults to now
+    pub to: Option<DateTime<Utc>>,
+    /// Bucket size; defaults to a size giving at most `MAX_HISTORY_POINTS` buckets
+    pub bucket_seconds: Option<i64>,
+}
+
+/// One downsampled bucket
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct HistoryPoint {
+    pub bucket: DateTime<Utc>,
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+    pub samples: i64,
+}
+
+/// History range response
+#[derive(Debug, Serialize)]
+pub struct HistoryResponse {
+    #[serde(rename = "type")]
+    pub widget_type: WidgetType,
+    pub series: String,
+    pub metric: String,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub bucket_seconds: i64,
+    pub points: Vec<HistoryPoint>,
+}
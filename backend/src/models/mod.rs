@@ -1,5 +1,37 @@
 pub mod user;
 pub mod dashboard;
+pub mod layout;
+pub mod export;
+pub mod template;
+pub mod folder;
+pub mod tag;
+pub mod variables;
+pub mod snapshot;
+pub mod metric;
+pub mod monitor;
+pub mod channel;
+pub mod alert;
+pub mod report;
+pub mod push;
+pub mod data_source;
+pub mod forge;
+pub mod organization;
 
 pub use user::*;
 pub use dashboard::*;
+pub use layout::*;
+pub use export::*;
+pub use template::*;
+pub use folder::*;
+pub use tag::*;
+pub use variables::*;
+pub use snapshot::*;
+pub use metric::*;
+pub use monitor::*;
+pub use channel::*;
+pub use alert::*;
+pub use report::*;
+pub use push::*;
+pub use data_source::*;
+pub use forge::*;
+pub use organization::*;
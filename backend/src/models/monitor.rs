@@ -0,0 +1,263 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::error::{AppError, FieldError, Result};
+
+/// Shortest allowed interval between checks
+pub const MIN_MONITOR_INTERVAL_SECONDS: i32 = 30;
+
+/// Longest allowed interval between checks (one day)
+pub const MAX_MONITOR_INTERVAL_SECONDS: i32 = 86_400;
+
+/// Longest allowed request timeout for a check
+pub const MAX_MONITOR_TIMEOUT_MS: i32 = 30_000;
+
+/// SLA windows reported when the client doesn't ask for specific ones
+pub const DEFAULT_SLA_WINDOWS: &str = "24h,7d,30d";
+
+/// Most SLA windows reported per request
+pub const MAX_SLA_WINDOWS: usize = 10;
+
+/// Uptime monitor model
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Monitor {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub url: String,
+    pub interval_seconds: i32,
+    /// Status code that counts as up; any 2xx/3xx when unset
+    pub expected_status: Option<i32>,
+    pub timeout_ms: i32,
+    pub enabled: bool,
+    /// Result of the most recent check, `None` until the first one runs
+    pub last_is_up: Option<bool>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    pub next_check_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Monitor {
+    /// Whether a response with this status code counts as up
+    pub fn accepts(&self, status_code: u16) -> bool {
+        match self.expected_status {
+            Some(expected) => i32::from(status_code) == expected,
+            None => (200..400).contains(&status_code),
+        }
+    }
+}
+
+/// Create monitor request
+#[derive(Debug, Deserialize)]
+pub struct CreateMonitorRequest {
+    pub name: String,
+    pub url: String,
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: i32,
+    pub expected_status: Option<i32>,
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: i32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_interval_seconds() -> i32 {
+    60
+}
+
+fn default_timeout_ms() -> i32 {
+    5_000
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Update monitor request
+#[derive(Debug, Deserialize)]
+pub struct UpdateMonitorRequest {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub interval_seconds: Option<i32>,
+    /// Omit to keep the current value, `null` to accept any 2xx/3xx
+    #[serde(default, with = "super::folder::double_option")]
+    pub expected_status: Option<Option<i32>>,
+    pub timeout_ms: Option<i32>,
+    pub enabled: Option<bool>,
+}
+
+/// Check a monitor's settings, returning every problem found
+pub fn validate_monitor(
+    name: &str,
+    url: &str,
+    interval_seconds: i32,
+    expected_status: Option<i32>,
+    timeout_ms: i32,
+) -> Result<()> {
+    let mut errors = Vec::new();
+
+    if name.trim().is_empty() {
+        errors.push(FieldError::new("name", "Monitor name is required"));
+    }
+
+    match reqwest::Url::parse(url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+        _ => errors.push(FieldError::new("url", "Must be an http(s) URL")),
+    }
+
+    if !(MIN_MONITOR_INTERVAL_SECONDS..=MAX_MONITOR_INTERVAL_SECONDS).contains(&interval_seconds) {
+        errors.push(FieldError::new(
+            "interval_seconds",
+            format!(
+                "Must be between {} and {}",
+                MIN_MONITOR_INTERVAL_SECONDS, MAX_MONITOR_INTERVAL_SECONDS
+            ),
+        ));
+    }
+
+    if let Some(status) = expected_status {
+        if !(100..=599).contains(&status) {
+            errors.push(FieldError::new("expected_status", "Must be an HTTP status code"));
+        }
+    }
+
+    if !(1..=MAX_MONITOR_TIMEOUT_MS).contains(&timeout_ms) {
+        errors.push(FieldError::new(
+            "timeout_ms",
+            format!("Must be between 1 and {}", MAX_MONITOR_TIMEOUT_MS),
+        ));
+    } else if i64::from(timeout_ms) >= i64::from(interval_seconds) * 1000 {
+        errors.push(FieldError::new("timeout_ms", "Must be shorter than the check interval"));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::InvalidFields(errors))
+    }
+}
+
+/// One recorded check
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct MonitorCheck {
+    pub id: i64,
+    pub checked_at: DateTime<Utc>,
+    pub is_up: bool,
+    pub status_code: Option<i32>,
+    pub response_time_ms: Option<i32>,
+    pub error: Option<String>,
+}
+
+/// An outage: opened on a failed check, resolved on the next successful one
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct Incident {
+    pub id: Uuid,
+    pub monitor_id: Uuid,
+    pub started_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub cause: String,
+}
+
+/// Check history query
+#[derive(Debug, Deserialize)]
+pub struct MonitorChecksQuery {
+    /// Only checks older than this
+    pub before: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+}
+
+/// SLA report query
+#[derive(Debug, Deserialize)]
+pub struct SlaQuery {
+    /// Comma-separated windows such as `24h,7d,30d` (units: m, h, d)
+    pub windows: Option<String>,
+}
+
+/// SLA figures for one window ending now
+#[derive(Debug, Serialize)]
+pub struct SlaWindow {
+    pub window: String,
+    pub from: DateTime<Utc>,
+    /// Share of checks that were up, `None` when nothing was checked in the window
+    pub uptime_percentage: Option<f64>,
+    pub checks: i64,
+    pub failed_checks: i64,
+    pub incidents: i64,
+    pub downtime_seconds: i64,
+    pub avg_response_time_ms: Option<f64>,
+}
+
+/// SLA report for a monitor
+#[derive(Debug, Serialize)]
+pub struct SlaResponse {
+    pub monitor_id: Uuid,
+    pub windows: Vec<SlaWindow>,
+}
+
+/// Parse an SLA window like `90m`, `24h` or `30d`
+pub fn parse_window(window: &str) -> Result<Duration> {
+    let window = window.trim();
+    let invalid = || AppError::Validation(format!("Invalid window '{}', expected e.g. 24h or 7d", window));
+
+    let unit = window.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = window[..window.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    if amount <= 0 || amount > 366 * 24 * 60 {
+        return Err(invalid());
+    }
+
+    let duration = match unit {
+        'm' => Duration::minutes(amount),
+        'h' => Duration::hours(amount),
+        'd' => Duration::days(amount),
+        _ => return Err(invalid()),
+    };
+
+    if duration > Duration::days(366) {
+        return Err(AppError::Validation("SLA windows are limited to 366 days".to_string()));
+    }
+
+    Ok(duration)
+}
+
+/// Parse a comma-separated list of distinct SLA windows, at most `MAX_SLA_WINDOWS` of them
+pub fn parse_windows(windows: &str) -> Result<Vec<(String, Duration)>> {
+    if windows.split(',').count() > MAX_SLA_WINDOWS {
+        return Err(AppError::Validation(format!(
+            "At most {} SLA windows can be requested",
+            MAX_SLA_WINDOWS
+        )));
+    }
+
+    let mut parsed: Vec<(String, Duration)> = Vec::new();
+    for window in windows.split(',') {
+        let duration = parse_window(window)?;
+        if parsed.iter().any(|(_, seen)| *seen == duration) {
+            return Err(AppError::Validation(format!("Duplicate window '{}'", window.trim())));
+        }
+        parsed.push((window.trim().to_string(), duration));
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_windows_rejects_duplicates_and_too_many() {
+        let windows = parse_windows(DEFAULT_SLA_WINDOWS).unwrap();
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows[1], ("7d".to_string(), Duration::days(7)));
+
+        assert!(matches!(parse_windows("24h, 24h"), Err(AppError::Validation(_))));
+        assert!(matches!(parse_windows("1d,24h"), Err(AppError::Validation(_))));
+
+        let too_many: Vec<String> = (1..=MAX_SLA_WINDOWS + 1).map(|days| format!("{}d", days)).collect();
+        assert!(matches!(parse_windows(&too_many.join(",")), Err(AppError::Validation(_))));
+        let most: Vec<String> = (1..=MAX_SLA_WINDOWS).map(|days| format!("{}d", days)).collect();
+        assert_eq!(parse_windows(&most.join(",")).unwrap().len(), MAX_SLA_WINDOWS);
+    }
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a member may do in an organization
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum OrganizationRole {
+    /// Manages membership
+    Owner,
+    /// Reads and clones the organization's dashboards
+    #[default]
+    Member,
+}
+
+/// Organization as seen by one of its members
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    /// The caller's role
+    pub role: OrganizationRole,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Member of an organization
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct OrganizationMember {
+    pub user_id: Uuid,
+    pub email: String,
+    pub role: OrganizationRole,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Create organization request; the caller becomes its owner
+#[derive(Debug, Deserialize)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+}
+
+/// Add or update a member by email
+#[derive(Debug, Deserialize)]
+pub struct AddMemberRequest {
+    pub email: String,
+    #[serde(default)]
+    pub role: OrganizationRole,
+}
+
+/// User a dashboard is shared with
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct DashboardShare {
+    pub user_id: Uuid,
+    pub email: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Share a dashboard with a user by email
+#[derive(Debug, Deserialize)]
+pub struct ShareDashboardRequest {
+    pub email: String,
+}
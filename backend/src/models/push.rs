@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+/// Most history points kept per push source
+pub const PUSH_HISTORY_LIMIT: i64 = 1000;
+
+/// Most points accepted in one ingest request
+pub const MAX_PUSH_POINTS: usize = 500;
+
+/// Header carrying the `sha256=<hex>` HMAC of the request body
+pub const PUSH_SIGNATURE_HEADER: &str = "x-signature-256";
+
+/// Push source model
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct PushSource {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub secret: String,
+    pub keep_history: bool,
+    pub latest: Option<JsonValue>,
+    pub latest_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create push source request
+#[derive(Debug, Deserialize)]
+pub struct CreatePushSourceRequest {
+    pub name: String,
+    #[serde(default = "default_keep_history")]
+    pub keep_history: bool,
+}
+
+fn default_keep_history() -> bool {
+    true
+}
+
+/// Push source response
+#[derive(Debug, Serialize)]
+pub struct PushSourceResponse {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    /// Path external systems POST to
+    pub ingest_url: String,
+    /// HMAC key; only returned when the source is created or its secret rotated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    pub keep_history: bool,
+    pub latest: Option<JsonValue>,
+    pub latest_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl PushSourceResponse {
+    /// Response including the secret, for create and rotate
+    pub fn with_secret(source: PushSource) -> Self {
+        let secret = source.secret.clone();
+        Self {
+            secret: Some(secret),
+            ..Self::from(source)
+        }
+    }
+}
+
+impl From<PushSource> for PushSourceResponse {
+    fn from(source: PushSource) -> Self {
+        Self {
+            id: source.id,
+            user_id: source.user_id,
+            name: source.name,
+            ingest_url: format!("/api/ingest/{}", source.id),
+            secret: None,
+            keep_history: source.keep_history,
+            latest: source.latest,
+            latest_at: source.latest_at,
+            created_at: source.created_at,
+            updated_at: source.updated_at,
+        }
+    }
+}
+
+/// Ingest body: a single value, or a series of timestamped points
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum PushPayload {
+    Series { points: Vec<PushPoint> },
+    Value { value: JsonValue },
+}
+
+/// One pushed point; the timestamp defaults to the time of receipt
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow)]
+pub struct PushPoint {
+    #[serde(default, alias = "t")]
+    pub timestamp: Option<DateTime<Utc>>,
+    pub value: JsonValue,
+}
+
+/// Random secret for signing pushes
+pub fn new_push_secret() -> String {
+    format!("whsec_{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
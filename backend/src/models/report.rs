@@ -0,0 +1,214 @@
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::error::{AppError, FieldError, Result};
+
+/// Report schedule model
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct ReportSchedule {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub dashboard_id: Uuid,
+    pub name: String,
+    /// Standard 5-field cron expression (seconds and year fields are also accepted)
+    pub cron: String,
+    /// IANA timezone the cron expression is evaluated in
+    pub timezone: String,
+    pub channel_ids: Vec<Uuid>,
+    pub enabled: bool,
+    pub next_run_at: DateTime<Utc>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Create or replace report schedule request
+#[derive(Debug, Deserialize)]
+pub struct ReportScheduleRequest {
+    pub dashboard_id: Uuid,
+    pub name: String,
+    pub cron: String,
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+    pub channel_ids: Vec<Uuid>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_timezone() -> String {
+    "UTC".to_string()
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl ReportScheduleRequest {
+    /// Check the schedule and return its first run time after now
+    pub fn validate(&self) -> Result<DateTime<Utc>> {
+        let mut errors = Vec::new();
+
+        if self.name.trim().is_empty() {
+            errors.push(FieldError::new("name", "Report name is required"));
+        }
+
+        if self.channel_ids.is_empty() {
+            errors.push(FieldError::new("channel_ids", "At least one channel is required"));
+        }
+
+        let next_run = next_run(&self.cron, &self.timezone, Utc::now());
+        if let Err(AppError::InvalidFields(mut schedule_errors)) = next_run {
+            errors.append(&mut schedule_errors);
+            return Err(AppError::InvalidFields(errors));
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::InvalidFields(errors));
+        }
+
+        next_run
+    }
+}
+
+/// Next time a cron expression fires after `after`, evaluated in `timezone`
+pub fn next_run(cron: &str, timezone: &str, after: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let mut errors = Vec::new();
+
+    // The cron crate wants a leading seconds field and numbers weekdays 1 (Sun) to 7 (Sat);
+    // standard expressions have five fields and number weekdays 0 (Sun) to 6, 7 also being Sunday
+    let fields: Vec<&str> = cron.split_whitespace().collect();
+    let expression = match fields.as_slice() {
+        [minute, hour, day, month, weekday] => format!(
+            "0 {} {} {} {} {}",
+            minute,
+            hour,
+            day,
+            month,
+            standard_weekdays(weekday)
+        ),
+        _ => fields.join(" "),
+    };
+    let schedule = Schedule::from_str(&expression)
+        .map_err(|e| errors.push(FieldError::new("cron", format!("Invalid cron expression: {}", e))))
+        .ok();
+
+    let tz = Tz::from_str(timezone)
+        .map_err(|_| errors.push(FieldError::new("timezone", format!("Unknown timezone: {}", timezone))))
+        .ok();
+
+    let (Some(schedule), Some(tz)) = (schedule, tz) else {
+        return Err(AppError::InvalidFields(errors));
+    };
+
+    schedule
+        .after(&after.with_timezone(&tz))
+        .next()
+        .map(|next| next.with_timezone(&Utc))
+        .ok_or_else(|| AppError::InvalidFields(vec![FieldError::new("cron", "Schedule never fires again")]))
+}
+
+/// Rewrite numeric weekdays of a standard cron field as names, e.g. `1-5` as `MON-FRI`
+fn standard_weekdays(field: &str) -> String {
+    const NAMES: [&str; 8] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+    field
+        .split(',')
+        .map(|item| {
+            // Only the range part is a weekday; a `/step` suffix stays numeric
+            let (range, step) = match item.split_once('/') {
+                Some((range, step)) => (range, Some(step)),
+                None => (item, None),
+            };
+            // `5-7` would become the invalid `FRI-SUN`; end the range on Saturday and add Sunday
+            if let (Some((start, "7")), None) = (range.split_once('-'), step) {
+                return format!("{}-SAT,SUN", standard_weekdays(start));
+            }
+            let range = range
+                .split('-')
+                .map(|day| match day.parse::<usize>() {
+                    Ok(day) if day < NAMES.len() => NAMES[day].to_string(),
+                    _ => day.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("-");
+            match step {
+                Some(step) => format!("{}/{}", range, step),
+                None => range,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// What started a report delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum ReportTrigger {
+    Schedule,
+    Manual,
+}
+
+/// Outcome of a report delivery
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Sent,
+    /// Delivered through some channels but not all
+    Partial,
+    Failed,
+}
+
+/// One entry of a schedule's delivery log
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ReportDelivery {
+    pub id: i64,
+    pub schedule_id: Uuid,
+    pub trigger: ReportTrigger,
+    pub status: DeliveryStatus,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn standard_weekdays_are_named() {
+        assert_eq!(standard_weekdays("0"), "SUN");
+        assert_eq!(standard_weekdays("7"), "SUN");
+        assert_eq!(standard_weekdays("1-5"), "MON-FRI");
+        assert_eq!(standard_weekdays("0-6"), "SUN-SAT");
+        assert_eq!(standard_weekdays("5-7"), "FRI-SAT,SUN");
+        assert_eq!(standard_weekdays("0,3,6"), "SUN,WED,SAT");
+        assert_eq!(standard_weekdays("1,3-5,7"), "MON,WED-FRI,SUN");
+        assert_eq!(standard_weekdays("1-5/2"), "MON-FRI/2");
+        assert_eq!(standard_weekdays("*"), "*");
+        assert_eq!(standard_weekdays("MON-FRI"), "MON-FRI");
+    }
+
+    #[test]
+    fn sunday_runs_whether_written_as_0_or_7() {
+        // Monday 2024-01-01
+        let monday = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let sunday = Utc.with_ymd_and_hms(2024, 1, 7, 9, 0, 0).unwrap();
+
+        assert_eq!(next_run("0 9 * * 0", "UTC", monday).unwrap(), sunday);
+        assert_eq!(next_run("0 9 * * 7", "UTC", monday).unwrap(), sunday);
+        assert_eq!(
+            next_run("0 9 * * 6-7", "UTC", monday).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 6, 9, 0, 0).unwrap()
+        );
+        assert_eq!(
+            next_run("30 8 * * 1-5", "UTC", monday).unwrap(),
+            Utc.with_ymd_and_hms(2024, 1, 1, 8, 30, 0).unwrap()
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+/// Snapshot model: an immutable copy of a dashboard and its resolved widget data
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct Snapshot {
+    pub id: Uuid,
+    pub dashboard_id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub layout_json: JsonValue,
+    pub settings_json: JsonValue,
+    pub variables: JsonValue,
+    pub widgets: JsonValue,
+    pub share_token: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Snapshot listing entry (without the frozen data)
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SnapshotSummary {
+    pub id: Uuid,
+    pub dashboard_id: Uuid,
+    pub name: String,
+    pub share_token: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Create snapshot request
+#[derive(Debug, Default, Deserialize)]
+pub struct CreateSnapshotRequest {
+    /// Defaults to "<dashboard name> @ <timestamp>"
+    pub name: Option<String>,
+    /// When the snapshot stops being viewable; omit to keep it forever
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Generate a share link right away
+    #[serde(default)]
+    pub share: bool,
+}
+
+/// Snapshot as seen through a share link (no owner details)
+#[derive(Debug, Serialize)]
+pub struct SharedSnapshotResponse {
+    pub name: String,
+    pub layout_json: JsonValue,
+    pub settings_json: JsonValue,
+    pub variables: JsonValue,
+    pub widgets: JsonValue,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Snapshot> for SharedSnapshotResponse {
+    fn from(snapshot: Snapshot) -> Self {
+        Self {
+            name: snapshot.name,
+            layout_json: snapshot.layout_json,
+            settings_json: snapshot.settings_json,
+            variables: snapshot.variables,
+            widgets: snapshot.widgets,
+            expires_at: snapshot.expires_at,
+            created_at: snapshot.created_at,
+        }
+    }
+}
+
+/// Random token used in snapshot share links
+pub fn new_share_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
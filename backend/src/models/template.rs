@@ -0,0 +1,354 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, FieldError, Result},
+    widgets::WidgetType,
+};
+
+use super::DashboardLayout;
+
+/// Version reported for built-in templates
+pub const BUILTIN_TEMPLATE_VERSION: i32 = 1;
+
+/// A placeholder a template expects, referenced as `{{name}}` in its strings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateVariable {
+    pub name: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// Stored user-published template
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct TemplateRow {
+    pub id: Uuid,
+    pub slug: String,
+    pub version: i32,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub variables: sqlx::types::Json<Vec<TemplateVariable>>,
+    pub widget_types: Vec<String>,
+    pub layout_json: JsonValue,
+    pub settings_json: JsonValue,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Template as listed in the gallery (built-in or user-published)
+#[derive(Debug, Clone, Serialize)]
+pub struct DashboardTemplate {
+    pub id: Option<Uuid>,
+    pub slug: String,
+    pub version: i32,
+    pub name: String,
+    pub description: String,
+    pub builtin: bool,
+    pub author_id: Option<Uuid>,
+    pub variables: Vec<TemplateVariable>,
+    pub widget_types: Vec<String>,
+    pub layout_json: JsonValue,
+    pub settings_json: JsonValue,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<TemplateRow> for DashboardTemplate {
+    fn from(row: TemplateRow) -> Self {
+        Self {
+            id: Some(row.id),
+            slug: row.slug,
+            version: row.version,
+            name: row.name,
+            description: row.description,
+            builtin: false,
+            author_id: Some(row.user_id),
+            variables: row.variables.0,
+            widget_types: row.widget_types,
+            layout_json: row.layout_json,
+            settings_json: row.settings_json,
+            created_at: Some(row.created_at),
+        }
+    }
+}
+
+/// Template gallery query parameters
+#[derive(Debug, Deserialize)]
+pub struct TemplateListQuery {
+    pub widget_type: Option<WidgetType>,
+}
+
+/// Template lookup query parameters
+#[derive(Debug, Deserialize)]
+pub struct TemplateVersionQuery {
+    pub version: Option<i32>,
+}
+
+/// Publish template request
+#[derive(Debug, Deserialize)]
+pub struct PublishTemplateRequest {
+    pub slug: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub variables: Vec<TemplateVariable>,
+    #[serde(default)]
+    pub layout_json: JsonValue,
+    #[serde(default)]
+    pub settings_json: JsonValue,
+}
+
+/// Instantiate template request
+#[derive(Debug, Deserialize)]
+pub struct FromTemplateRequest {
+    pub template: String,
+    pub version: Option<i32>,
+    pub name: Option<String>,
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+}
+
+impl DashboardTemplate {
+    /// Substitute variable values into the template, returning `(layout_json, settings_json)`
+    pub fn instantiate(&self, values: &HashMap<String, String>) -> Result<(JsonValue, JsonValue)> {
+        let mut resolved = HashMap::new();
+        let mut errors = Vec::new();
+
+        for variable in &self.variables {
+            match values.get(&variable.name).or(variable.default.as_ref()) {
+                Some(value) => {
+                    resolved.insert(variable.name.clone(), value.clone());
+                }
+                None => errors.push(FieldError::new(
+                    format!("variables.{}", variable.name),
+                    "A value is required for this template variable",
+                )),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(AppError::InvalidFields(errors));
+        }
+
+        let mut layout_json = self.layout_json.clone();
+        let mut settings_json = self.settings_json.clone();
+        substitute_placeholders(&mut layout_json, &resolved);
+        substitute_placeholders(&mut settings_json, &resolved);
+
+        Ok((layout_json, settings_json))
+    }
+}
+
+/// Check a template before publishing and derive its widget types.
+///
+/// Widget configs are not validated here since they usually hold placeholders;
+/// they are checked when the template is instantiated.
+pub fn check_template(request: &PublishTemplateRequest) -> Result<Vec<String>> {
+    let mut errors = Vec::new();
+
+    if !is_valid_slug(&request.slug) {
+        errors.push(FieldError::new(
+            "slug",
+            "Slug must be 1-64 lowercase letters, digits or hyphens",
+        ));
+    }
+    if request.name.trim().is_empty() {
+        errors.push(FieldError::new("name", "Template name is required"));
+    }
+
+    let mut used = Vec::new();
+    collect_placeholders(&request.layout_json, &mut used);
+    collect_placeholders(&request.settings_json, &mut used);
+    for name in used {
+        if !request.variables.iter().any(|variable| variable.name == name) {
+            errors.push(FieldError::new(
+                "variables",
+                format!("Placeholder '{{{{{}}}}}' is not declared as a variable", name),
+            ));
+        }
+    }
+
+    let layout = match DashboardLayout::from_json(request.layout_json.clone()) {
+        Ok(layout) => layout,
+        Err(layout_errors) => {
+            errors.extend(layout_errors);
+            DashboardLayout::default()
+        }
+    };
+
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    Ok(widget_types_of(&layout))
+}
+
+/// Distinct widget types used by a layout, in first-seen order
+pub fn widget_types_of(layout: &DashboardLayout) -> Vec<String> {
+    let mut types: Vec<String> = Vec::new();
+    for widget in &layout.widgets {
+        let widget_type = widget.widget_type.as_str().to_string();
+        if !types.contains(&widget_type) {
+            types.push(widget_type);
+        }
+    }
+    types
+}
+
+fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug.len() <= 64
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Replace `{{name}}` placeholders in every string of a JSON value
+fn substitute_placeholders(value: &mut JsonValue, values: &HashMap<String, String>) {
+    match value {
+        JsonValue::String(text) => *text = render_placeholders(text, values),
+        JsonValue::Array(items) => items
+            .iter_mut()
+            .for_each(|item| substitute_placeholders(item, values)),
+        JsonValue::Object(fields) => fields
+            .values_mut()
+            .for_each(|field| substitute_placeholders(field, values)),
+        _ => {}
+    }
+}
+
+/// Render a string, leaving unknown placeholders untouched
+fn render_placeholders(text: &str, values: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else { break };
+        let name = rest[start + 2..start + end].trim();
+
+        output.push_str(&rest[..start]);
+        match values.get(name) {
+            Some(value) => output.push_str(value),
+            None => output.push_str(&rest[start..start + end + 2]),
+        }
+        rest = &rest[start + end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Collect the names of all `{{name}}` placeholders in a JSON value
+fn collect_placeholders(value: &JsonValue, names: &mut Vec<String>) {
+    match value {
+        JsonValue::String(text) => {
+            let mut rest = text.as_str();
+            while let Some(start) = rest.find("{{") {
+                let Some(end) = rest[start..].find("}}") else { break };
+                let name = rest[start + 2..start + end].trim().to_string();
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+                rest = &rest[start + end + 2..];
+            }
+        }
+        JsonValue::Array(items) => items.iter().for_each(|item| collect_placeholders(item, names)),
+        JsonValue::Object(fields) => fields
+            .values()
+            .for_each(|field| collect_placeholders(field, names)),
+        _ => {}
+    }
+}
+
+/// Templates shipped with the backend
+pub fn builtin_templates() -> Vec<DashboardTemplate> {
+    let builtin = |slug: &str,
+                   name: &str,
+                   description: &str,
+                   variables: Vec<TemplateVariable>,
+                   layout_json: JsonValue,
+                   settings_json: JsonValue| {
+        let widget_types = DashboardLayout::from_json(layout_json.clone())
+            .map(|layout| widget_types_of(&layout))
+            .unwrap_or_default();
+
+        DashboardTemplate {
+            id: None,
+            slug: slug.to_string(),
+            version: BUILTIN_TEMPLATE_VERSION,
+            name: name.to_string(),
+            description: description.to_string(),
+            builtin: true,
+            author_id: None,
+            variables,
+            widget_types,
+            layout_json,
+            settings_json,
+            created_at: None,
+        }
+    };
+    let variable = |name: &str, label: &str, default: Option<&str>| TemplateVariable {
+        name: name.to_string(),
+        label: Some(label.to_string()),
+        default: default.map(str::to_string),
+    };
+
+    vec![
+        builtin(
+            "devops-status-wall",
+            "DevOps status wall",
+            "Uptime of your services next to your team's GitHub activity",
+            vec![
+                variable("status_urls", "Comma-separated URLs to monitor", None),
+                variable("github_user", "GitHub user or organization", None),
+            ],
+            json!({
+                "widgets": [
+                    { "id": "status", "type": "status", "x": 0, "y": 0, "w": 8, "h": 4,
+                      "config": { "urls": "{{status_urls}}" } },
+                    { "id": "github", "type": "github", "x": 8, "y": 0, "w": 4, "h": 4,
+                      "config": { "username": "{{github_user}}" } }
+                ]
+            }),
+            json!({ "title": "DevOps status wall", "theme": "dark", "refresh_interval": 60 }),
+        ),
+        builtin(
+            "crypto-watchlist",
+            "Crypto watchlist",
+            "Prices and 24h changes for the coins you follow, plus crypto news",
+            vec![variable("coins", "Comma-separated CoinGecko IDs", Some("bitcoin,ethereum,solana"))],
+            json!({
+                "widgets": [
+                    { "id": "prices", "type": "crypto", "x": 0, "y": 0, "w": 6, "h": 4,
+                      "config": { "cryptoIds": "{{coins}}" } },
+                    { "id": "news", "type": "news", "x": 6, "y": 0, "w": 6, "h": 4,
+                      "config": { "topic": "cryptocurrency" } }
+                ]
+            }),
+            json!({ "title": "Crypto watchlist", "refresh_interval": 60 }),
+        ),
+        builtin(
+            "morning-briefing",
+            "Morning briefing",
+            "Local weather and the headlines for a topic you care about",
+            vec![
+                variable("city", "City", None),
+                variable("topic", "News topic", Some("technology")),
+            ],
+            json!({
+                "widgets": [
+                    { "id": "weather", "type": "weather", "x": 0, "y": 0, "w": 4, "h": 3,
+                      "config": { "city": "{{city}}" } },
+                    { "id": "news", "type": "news", "x": 4, "y": 0, "w": 8, "h": 4,
+                      "config": { "topic": "{{topic}}" } }
+                ]
+            }),
+            json!({ "title": "Morning briefing", "theme": "light" }),
+        ),
+    ]
+}
@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+use crate::{
+    error::{AppError, FieldError, Result},
+    widgets::WidgetConfig,
+};
+
+/// Query parameter prefix viewers use to override a variable, e.g. `?var-city=Oslo`
+pub const VARIABLE_QUERY_PREFIX: &str = "var-";
+
+/// Kind of value a dashboard variable holds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableKind {
+    /// Free-form text
+    #[default]
+    Text,
+    /// One value out of `options`
+    Enum,
+    /// Several values, substituted comma-separated
+    List,
+}
+
+/// A dashboard-level variable, referenced in widget configs as `${name}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DashboardVariable {
+    pub name: String,
+    #[serde(default)]
+    pub kind: VariableKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Allowed values for `enum` variables
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    /// Default value; a list of strings for `list` variables
+    pub default: JsonValue,
+}
+
+impl DashboardVariable {
+    /// Check the definition itself (name, default, options)
+    pub fn validate(&self, prefix: &str) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
+        if self.name.is_empty()
+            || !self.name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            errors.push(
+                FieldError::new("name", "Variable names may only contain letters, digits and '_'")
+                    .nested(prefix),
+            );
+        }
+
+        match self.kind {
+            VariableKind::Enum if self.options.is_empty() => {
+                errors.push(
+                    FieldError::new("options", "Enum variables need at least one option").nested(prefix),
+                );
+            }
+            _ => {}
+        }
+
+        if let Err(message) = self.coerce(&self.default) {
+            errors.push(FieldError::new("default", message).nested(prefix));
+        }
+
+        errors
+    }
+
+    /// Turn a JSON value into the substitution text for this variable
+    fn coerce(&self, value: &JsonValue) -> std::result::Result<String, String> {
+        match (self.kind, value) {
+            (VariableKind::Text, JsonValue::String(text)) => Ok(text.clone()),
+            (VariableKind::Enum, JsonValue::String(text)) => {
+                if self.options.contains(text) {
+                    Ok(text.clone())
+                } else {
+                    Err(format!("'{}' is not one of {}", text, self.options.join(", ")))
+                }
+            }
+            (VariableKind::List, JsonValue::Array(items)) => items
+                .iter()
+                .map(|item| {
+                    item.as_str()
+                        .map(str::to_string)
+                        .ok_or_else(|| "List values must be strings".to_string())
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map(|items| items.join(",")),
+            (VariableKind::List, JsonValue::String(text)) => Ok(text.clone()),
+            (VariableKind::List, _) => Err("Expected a list of strings".to_string()),
+            (_, _) => Err("Expected a string".to_string()),
+        }
+    }
+
+    /// Substitution text for a viewer-supplied override (lists are comma-separated)
+    fn coerce_override(&self, value: &str) -> std::result::Result<String, String> {
+        self.coerce(&JsonValue::String(value.to_string()))
+    }
+}
+
+/// Resolve every variable to its substitution text, applying `?var-name=` overrides
+pub fn resolve_variables(
+    variables: &[DashboardVariable],
+    query: &HashMap<String, String>,
+) -> Result<HashMap<String, String>> {
+    let mut values = HashMap::new();
+    let mut errors = Vec::new();
+
+    for variable in variables {
+        let key = format!("{}{}", VARIABLE_QUERY_PREFIX, variable.name);
+        let value = match query.get(&key) {
+            Some(value) => variable.coerce_override(value),
+            None => variable.coerce(&variable.default),
+        };
+
+        match value {
+            Ok(value) => {
+                values.insert(variable.name.clone(), value);
+            }
+            Err(message) => errors.push(FieldError::new(key, message)),
+        }
+    }
+
+    for key in query.keys() {
+        if let Some(name) = key.strip_prefix(VARIABLE_QUERY_PREFIX) {
+            if !variables.iter().any(|variable| variable.name == name) {
+                errors.push(FieldError::new(key.clone(), "Unknown dashboard variable"));
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    Ok(values)
+}
+
+/// Replace `${name}` references in every string of a widget config
+pub fn substitute_variables(config: &WidgetConfig, values: &HashMap<String, String>) -> WidgetConfig {
+    config
+        .iter()
+        .map(|(key, value)| (key.clone(), substitute_value(value, values)))
+        .collect()
+}
+
+fn substitute_value(value: &JsonValue, values: &HashMap<String, String>) -> JsonValue {
+    match value {
+        JsonValue::String(text) => JsonValue::String(render_variables(text, values)),
+        JsonValue::Array(items) => {
+            JsonValue::Array(items.iter().map(|item| substitute_value(item, values)).collect())
+        }
+        JsonValue::Object(fields) => JsonValue::Object(substitute_variables(fields, values)),
+        other => other.clone(),
+    }
+}
+
+/// Render `${name}` references in a string; unknown names are left as-is
+fn render_variables(text: &str, values: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else { break };
+        let name = &rest[start + 2..start + end];
+
+        output.push_str(&rest[..start]);
+        match values.get(name) {
+            Some(value) => output.push_str(value),
+            None => output.push_str(&rest[start..start + end + 1]),
+        }
+        rest = &rest[start + end + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Names of all `${name}` references in a widget config
+pub fn referenced_variables(config: &WidgetConfig) -> Vec<String> {
+    fn collect(value: &JsonValue, names: &mut Vec<String>) {
+        match value {
+            JsonValue::String(text) => {
+                let mut rest = text.as_str();
+                while let Some(start) = rest.find("${") {
+                    let Some(end) = rest[start..].find('}') else { break };
+                    let name = rest[start + 2..start + end].to_string();
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                    rest = &rest[start + end + 1..];
+                }
+            }
+            JsonValue::Array(items) => items.iter().for_each(|item| collect(item, names)),
+            JsonValue::Object(fields) => fields.values().for_each(|field| collect(field, names)),
+            _ => {}
+        }
+    }
+
+    let mut names = Vec::new();
+    config.values().for_each(|value| collect(value, &mut names));
+    names
+}
@@ -0,0 +1,214 @@
+pub mod mailer;
+pub mod report;
+
+use serde_json::{json, Value as JsonValue};
+use std::time::Duration;
+use url::Url;
+
+use crate::{
+    config::Config,
+    egress::guarded_client,
+    error::{AppError, Result},
+    models::ChannelConfig,
+    notify::mailer::{EmailMessage, Mailer},
+};
+
+/// Timeout for webhook deliveries
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A message to deliver through one or more notification channels
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// Email subject and the headline of chat messages
+    pub title: String,
+    pub text: String,
+    /// Optional HTML body for email
+    pub html: Option<String>,
+    /// Structured body POSTed to generic webhooks
+    pub payload: JsonValue,
+}
+
+/// Deliver a notification through a channel; email goes through `mailer`.
+///
+/// Webhook and Slack URLs are user-supplied, so they are sent through the egress guard.
+pub async fn deliver(
+    config: &Config,
+    mailer: &dyn Mailer,
+    channel: &ChannelConfig,
+    notification: &Notification,
+) -> Result<()> {
+    match channel {
+        ChannelConfig::Webhook { url, headers } => {
+            let (client, url) = webhook_client(config, url).await?;
+            let mut request = client.post(url).json(&notification.payload);
+            for (name, value) in headers {
+                request = request.header(name, value);
+            }
+            send_webhook(request).await
+        }
+        ChannelConfig::Slack { url } => {
+            let body = json!({ "text": format!("*{}*\n{}", notification.title, notification.text) });
+            let (client, url) = webhook_client(config, url).await?;
+            send_webhook(client.post(url).json(&body)).await
+        }
+        ChannelConfig::Email { to } => {
+            mailer
+                .send(EmailMessage {
+                    to: to.clone(),
+                    subject: notification.title.clone(),
+                    text: notification.text.clone(),
+                    html: notification.html.clone(),
+                })
+                .await
+        }
+    }
+}
+
+/// A client pinned to the webhook's checked addresses; redirects are not followed
+async fn webhook_client(config: &Config, url: &str) -> Result<(reqwest::Client, Url)> {
+    let url = Url::parse(url).map_err(|e| AppError::Validation(format!("Invalid webhook URL: {}", e)))?;
+    let client = guarded_client(config, &url, WEBHOOK_TIMEOUT).await?;
+    Ok((client, url))
+}
+
+async fn send_webhook(request: reqwest::RequestBuilder) -> Result<()> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalApi(format!("Webhook error: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(format!(
+            "Webhook returned status: {}",
+            response.status()
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::BoxFuture;
+    use mockito::Matcher;
+    use std::{collections::HashMap, sync::Mutex};
+
+    /// Records messages instead of sending them
+    #[derive(Default)]
+    struct FakeMailer {
+        sent: Mutex<Vec<EmailMessage>>,
+    }
+
+    impl Mailer for FakeMailer {
+        fn send(&self, message: EmailMessage) -> BoxFuture<'_, Result<()>> {
+            self.sent.lock().unwrap().push(message);
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    /// Lets the tests reach the local mock server
+    fn config() -> Config {
+        Config { egress_allowed_hosts: vec!["127.0.0.1".to_string()], ..Config::for_tests() }
+    }
+
+    fn notification() -> Notification {
+        Notification {
+            title: "[FIRING] BTC drop".to_string(),
+            text: "BTC price < 50000 (current value: 40000)".to_string(),
+            html: None,
+            payload: json!({ "event": "alert", "value": 40000.0 }),
+        }
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_payload_with_headers() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/hook")
+            .match_header("x-signature", "secret")
+            .match_body(Matcher::Json(json!({ "event": "alert", "value": 40000.0 })))
+            .with_status(204)
+            .create_async()
+            .await;
+
+        let channel = ChannelConfig::Webhook {
+            url: format!("{}/hook", server.url()),
+            headers: HashMap::from([("X-Signature".to_string(), "secret".to_string())]),
+        };
+        deliver(&config(), &FakeMailer::default(), &channel, &notification()).await.unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn slack_posts_text_message() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/slack")
+            .match_body(Matcher::Json(json!({
+                "text": "*[FIRING] BTC drop*\nBTC price < 50000 (current value: 40000)"
+            })))
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let channel = ChannelConfig::Slack { url: format!("{}/slack", server.url()) };
+        deliver(&config(), &FakeMailer::default(), &channel, &notification()).await.unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn webhook_error_status_fails_delivery() {
+        let mut server = mockito::Server::new_async().await;
+        server.mock("POST", "/hook").with_status(500).create_async().await;
+
+        let channel = ChannelConfig::Webhook { url: format!("{}/hook", server.url()), headers: HashMap::new() };
+        let result = deliver(&config(), &FakeMailer::default(), &channel, &notification()).await;
+
+        assert!(matches!(result, Err(AppError::ExternalApi(_))));
+    }
+
+    #[tokio::test]
+    async fn webhook_to_private_address_is_refused() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("POST", "/hook").expect(0).create_async().await;
+
+        let channel = ChannelConfig::Webhook { url: format!("{}/hook", server.url()), headers: HashMap::new() };
+        let result = deliver(&Config::for_tests(), &FakeMailer::default(), &channel, &notification()).await;
+
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn webhook_redirects_are_not_followed() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/hook")
+            .with_status(307)
+            .with_header("location", "http://169.254.169.254/latest/meta-data/")
+            .create_async()
+            .await;
+
+        let channel = ChannelConfig::Slack { url: format!("{}/hook", server.url()) };
+        let result = deliver(&config(), &FakeMailer::default(), &channel, &notification()).await;
+
+        assert!(matches!(result, Err(AppError::ExternalApi(_))));
+    }
+
+    #[tokio::test]
+    async fn email_goes_through_mailer() {
+        let mailer = FakeMailer::default();
+        let channel = ChannelConfig::Email { to: vec!["ops@example.com".to_string()] };
+
+        deliver(&config(), &mailer, &channel, &notification()).await.unwrap();
+
+        let sent = mailer.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, vec!["ops@example.com".to_string()]);
+        assert_eq!(sent[0].subject, "[FIRING] BTC drop");
+        assert_eq!(sent[0].text, "BTC price < 50000 (current value: 40000)");
+    }
+}
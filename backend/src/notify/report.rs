@@ -0,0 +1,334 @@
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value as JsonValue};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::{
+    error::Result,
+    handlers::{dashboard::find_dashboard, widget_data::parse_dashboard},
+    models::{
+        DeliveryStatus, NotificationChannel, ReportDelivery, ReportSchedule, ReportTrigger,
+        WidgetLayout,
+    },
+    notify::{deliver, Notification},
+    widgets::{
+        calendar::CalendarEvent, crypto::CryptoPrice, github::{GitHubData, GitHubEvent}, http::HttpData,
+        news::NewsArticle, prometheus::{PromPoint, PromResult, PromSeries}, push::PushData,
+        resolve_widgets, sql::SqlData, status::StatusCheck, weather::WeatherData, ResolvedWidget, WidgetType,
+    },
+    AppState,
+};
+
+/// Most list items (articles, events) shown per widget
+const MAX_REPORT_ITEMS: usize = 5;
+
+/// Resolve a schedule's dashboard, render it and deliver it to every channel, logging the outcome
+pub async fn send_report(
+    state: &AppState,
+    schedule: &ReportSchedule,
+    trigger: ReportTrigger,
+) -> Result<ReportDelivery> {
+    let outcome = build_and_deliver(state, schedule).await;
+
+    let (status, error) = match outcome {
+        Ok(errors) if errors.is_empty() => (DeliveryStatus::Sent, None),
+        Ok(errors) if errors.len() < schedule.channel_ids.len() => {
+            (DeliveryStatus::Partial, Some(errors.join("; ")))
+        }
+        Ok(errors) => (DeliveryStatus::Failed, Some(errors.join("; "))),
+        Err(e) => {
+            tracing::error!("Failed to build report {}: {:?}", schedule.id, e);
+            (DeliveryStatus::Failed, Some(e.client_message()))
+        }
+    };
+
+    let delivery: ReportDelivery = sqlx::query_as(
+        "INSERT INTO report_deliveries (schedule_id, trigger, status, error)
+         VALUES ($1, $2, $3, $4)
+         RETURNING id, schedule_id, trigger, status, error, created_at"
+    )
+    .bind(schedule.id)
+    .bind(trigger)
+    .bind(status)
+    .bind(error)
+    .fetch_one(state.db.pool())
+    .await?;
+
+    sqlx::query("UPDATE report_schedules SET last_run_at = $1 WHERE id = $2")
+        .bind(delivery.created_at)
+        .bind(schedule.id)
+        .execute(state.db.pool())
+        .await?;
+
+    Ok(delivery)
+}
+
+/// Returns one error message per channel that failed
+async fn build_and_deliver(state: &AppState, schedule: &ReportSchedule) -> Result<Vec<String>> {
+    let dashboard = find_dashboard(state, schedule.user_id, schedule.dashboard_id).await?;
+    let (layout, variables) =
+        parse_dashboard(dashboard.layout_json, dashboard.settings_json, &HashMap::new())?;
+
+    let widgets = resolve_widgets(state, schedule.user_id, &layout.widgets, &variables).await;
+    let (text, html) = render_report(&dashboard.name, &layout.widgets, &widgets);
+
+    let channels: Vec<NotificationChannel> = sqlx::query_as(
+        "SELECT id, user_id, name, config, created_at, updated_at
+         FROM notification_channels
+         WHERE user_id = $1 AND id = ANY($2)"
+    )
+    .bind(schedule.user_id)
+    .bind(&schedule.channel_ids)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let notification = Notification {
+        title: format!("{} – {}", schedule.name, Utc::now().format("%Y-%m-%d")),
+        text,
+        html: Some(html),
+        payload: json!({
+            "event": "report",
+            "schedule_id": schedule.id,
+            "name": schedule.name,
+            "dashboard_id": dashboard.id,
+            "dashboard": dashboard.name,
+            "variables": variables,
+            "widgets": widgets,
+        }),
+    };
+
+    let mut errors = Vec::new();
+    for channel in &channels {
+        if let Err(e) = deliver(&state.config, state.mailer.as_ref(), &channel.config, &notification).await {
+            // The log is shown to the user, so keep internals (and probed URLs' answers) out of it
+            tracing::warn!("Failed to deliver report {} to channel {}: {:?}", schedule.id, channel.id, e);
+            errors.push(format!("{}: {}", channel.name, e.client_message()));
+        }
+    }
+    if channels.len() < schedule.channel_ids.len() {
+        errors.push("Some channels no longer exist".to_string());
+    }
+
+    Ok(errors)
+}
+
+/// Render resolved widget data as plain-text and HTML summaries
+pub fn render_report(
+    dashboard_name: &str,
+    layouts: &[WidgetLayout],
+    widgets: &[ResolvedWidget],
+) -> (String, String) {
+    let mut text = format!("{}\n{}\n", dashboard_name, "=".repeat(dashboard_name.chars().count()));
+    let mut html = format!(
+        "<html><body style=\"font-family: sans-serif\"><h1>{}</h1>",
+        escape_html(dashboard_name)
+    );
+
+    for (layout, widget) in layouts.iter().zip(widgets) {
+        let title = layout
+            .config
+            .get("title")
+            .and_then(JsonValue::as_str)
+            .unwrap_or(widget.widget_type.as_str());
+
+        let lines = match (&widget.data, &widget.error) {
+            (_, Some(error)) => vec![format!("Unavailable: {}", error)],
+            (Some(data), None) => summarize(widget.widget_type, data),
+            (None, None) => Vec::new(),
+        };
+
+        let _ = write!(text, "\n{}\n", title);
+        let _ = write!(html, "<h2>{}</h2><ul>", escape_html(title));
+        for line in &lines {
+            let _ = writeln!(text, "  - {}", line);
+            let _ = write!(html, "<li>{}</li>", escape_html(line));
+        }
+        html.push_str("</ul>");
+    }
+
+    html.push_str("</body></html>");
+    (text, html)
+}
+
+/// One line per item of a widget's data
+fn summarize(widget_type: WidgetType, data: &JsonValue) -> Vec<String> {
+    match widget_type {
+        WidgetType::Crypto => parse::<Vec<CryptoPrice>>(data)
+            .into_iter()
+            .flatten()
+            .map(|price| {
+                format!("{}: ${:.2} ({:+.2}% 24h)", price.symbol, price.price, price.change_percentage_24h)
+            })
+            .collect(),
+        WidgetType::Status => parse::<Vec<StatusCheck>>(data)
+            .into_iter()
+            .flatten()
+            .map(|check| match (check.status_code, check.response_time_ms) {
+                (Some(code), Some(ms)) => format!("{}: {} ({}, {} ms)", check.url, check.status, code, ms),
+                _ => format!("{}: {}", check.url, check.status),
+            })
+            .collect(),
+        WidgetType::Weather => parse::<WeatherData>(data)
+            .map(|weather| {
+                let unit = weather.units.temperature_symbol();
+                let mut lines = vec![format!(
+                    "{}: {:.1}{unit}, {} (feels like {:.1}{unit}, humidity {}%)",
+                    weather.city_name, weather.temp, weather.description, weather.feels_like, weather.humidity
+                )];
+                lines.extend(weather.alerts.iter().flatten().map(|alert| {
+                    format!("Alert: {} until {}", alert.event, alert.end.format("%Y-%m-%d %H:%M UTC"))
+                }));
+                lines.extend(weather.daily.iter().flatten().take(MAX_REPORT_ITEMS).map(|day| {
+                    format!(
+                        "{}: {:.0}-{:.0}{unit}, {}",
+                        day.date.format("%a %d %b"), day.temp_min, day.temp_max, day.description
+                    )
+                }));
+                lines
+            })
+            .unwrap_or_default(),
+        WidgetType::News | WidgetType::Feed => parse::<Vec<NewsArticle>>(data)
+            .into_iter()
+            .flatten()
+            .take(MAX_REPORT_ITEMS)
+            .map(|article| format!("{} ({})", article.title, article.source))
+            .collect(),
+        WidgetType::Github => match parse::<GitHubData>(data) {
+            Some(GitHubData::Events(events)) => events
+                .iter()
+                .take(MAX_REPORT_ITEMS)
+                .map(|event| format!("{} on {}", event.event_type, event.repo.name))
+                .collect(),
+            Some(GitHubData::Repo(repo)) => vec![format!(
+                "{}: {} stars, {} forks, {} open issues",
+                repo.full_name, repo.stars, repo.forks, repo.open_issues
+            )],
+            Some(GitHubData::Pulls { pulls }) => pulls
+                .iter()
+                .take(MAX_REPORT_ITEMS)
+                .map(|pull| format!("#{} {} by {} ({})", pull.number, pull.title, pull.author, pull.review_status.as_str()))
+                .collect(),
+            Some(GitHubData::Issues { issues }) => issues
+                .iter()
+                .take(MAX_REPORT_ITEMS)
+                .map(|issue| format!("#{} {} by {}", issue.number, issue.title, issue.author))
+                .collect(),
+            Some(GitHubData::Workflows { runs }) => runs
+                .iter()
+                .take(MAX_REPORT_ITEMS)
+                .map(|run| format!(
+                    "{} on {}: {}",
+                    run.workflow,
+                    run.branch,
+                    run.conclusion.as_deref().unwrap_or(&run.status)
+                ))
+                .collect(),
+            None => Vec::new(),
+        },
+        WidgetType::Activity => parse::<Vec<GitHubEvent>>(data)
+            .into_iter()
+            .flatten()
+            .take(MAX_REPORT_ITEMS)
+            .map(|event| format!("{} on {}", event.event_type, event.repo.name))
+            .collect(),
+        WidgetType::Push => parse::<PushData>(data)
+            .map(|push| match (push.latest, push.latest_at) {
+                (Some(latest), Some(at)) => vec![format!("{}: {} (at {})", push.name, latest, at.format("%Y-%m-%d %H:%M UTC"))],
+                _ => vec![format!("{}: no data pushed yet", push.name)],
+            })
+            .unwrap_or_default(),
+        WidgetType::Http => match parse::<HttpData>(data) {
+            Some(HttpData::Scalar { value }) => vec![value.to_string()],
+            Some(HttpData::List { values }) => {
+                values.iter().take(MAX_REPORT_ITEMS).map(JsonValue::to_string).collect()
+            }
+            Some(HttpData::Table { columns, rows }) => rows
+                .iter()
+                .take(MAX_REPORT_ITEMS)
+                .map(|row| {
+                    columns
+                        .iter()
+                        .zip(row)
+                        .map(|(column, value)| format!("{}: {}", column, value))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect(),
+            None => Vec::new(),
+        },
+        WidgetType::Calendar => parse::<Vec<CalendarEvent>>(data)
+            .into_iter()
+            .flatten()
+            .take(MAX_REPORT_ITEMS)
+            .map(|event| {
+                let when = match (event.ongoing, event.all_day) {
+                    (true, _) => format!("Now (until {})", event.end.format("%Y-%m-%d %H:%M")),
+                    (false, true) => event.start.format("%Y-%m-%d").to_string(),
+                    (false, false) => event.start.format("%Y-%m-%d %H:%M").to_string(),
+                };
+                format!("{}: {} ({})", when, event.summary, event.calendar)
+            })
+            .collect(),
+        WidgetType::Sql => parse::<SqlData>(data)
+            .map(|sql| {
+                sql.rows
+                    .iter()
+                    .take(MAX_REPORT_ITEMS)
+                    .map(|row| {
+                        sql.columns
+                            .iter()
+                            .zip(row)
+                            .map(|(column, value)| format!("{}: {}", column.name, value))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        WidgetType::Prometheus => match parse::<PromResult>(data) {
+            Some(PromResult::Scalar { point }) => vec![format_prom_value(&point)],
+            Some(PromResult::Vector { series } | PromResult::Matrix { series }) => series
+                .iter()
+                .take(MAX_REPORT_ITEMS)
+                .map(|series| {
+                    let latest = series.points.last().map(format_prom_value).unwrap_or_default();
+                    format!("{}: {}", format_prom_labels(series), latest)
+                })
+                .collect(),
+            None => Vec::new(),
+        },
+    }
+}
+
+/// A series' labels as `name{key="value", ...}`
+fn format_prom_labels(series: &PromSeries) -> String {
+    let name = series.labels.get("__name__").cloned().unwrap_or_default();
+    let labels = series
+        .labels
+        .iter()
+        .filter(|(key, _)| key.as_str() != "__name__")
+        .map(|(key, value)| format!("{}=\"{}\"", key, value))
+        .collect::<Vec<_>>();
+
+    if labels.is_empty() {
+        name
+    } else {
+        format!("{}{{{}}}", name, labels.join(", "))
+    }
+}
+
+fn format_prom_value(point: &PromPoint) -> String {
+    point.value.map(|value| value.to_string()).unwrap_or_else(|| "NaN".to_string())
+}
+
+fn parse<T: DeserializeOwned>(data: &JsonValue) -> Option<T> {
+    serde_json::from_value(data.clone()).ok()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
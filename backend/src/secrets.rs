@@ -0,0 +1,54 @@
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Nonce,
+};
+use base64::Engine;
+
+use crate::{
+    config::Config,
+    error::{AppError, Result},
+};
+
+/// Length of the random nonce stored in front of every ciphertext
+const NONCE_LEN: usize = 12;
+
+/// Encrypt a secret with `DATA_SOURCE_KEY`; the output is the nonce followed by the ciphertext
+pub fn encrypt(config: &Config, plaintext: &str) -> Result<Vec<u8>> {
+    let cipher = cipher(config)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| AppError::Internal("Failed to encrypt secret".to_string()))?;
+
+    let mut output = nonce.to_vec();
+    output.extend_from_slice(&ciphertext);
+    Ok(output)
+}
+
+/// Decrypt a secret produced by `encrypt`
+pub fn decrypt(config: &Config, data: &[u8]) -> Result<String> {
+    if data.len() < NONCE_LEN {
+        return Err(AppError::Internal("Encrypted secret is truncated".to_string()));
+    }
+
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    let plaintext = cipher(config)?
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| AppError::Internal("Failed to decrypt secret (was DATA_SOURCE_KEY changed?)".to_string()))?;
+
+    String::from_utf8(plaintext)
+        .map_err(|_| AppError::Internal("Decrypted secret is not UTF-8".to_string()))
+}
+
+fn cipher(config: &Config) -> Result<Aes256Gcm> {
+    let key = config.data_source_key.as_ref()
+        .ok_or_else(|| AppError::Internal("DATA_SOURCE_KEY not configured".to_string()))?;
+
+    let key = base64::engine::general_purpose::STANDARD
+        .decode(key.trim())
+        .map_err(|_| AppError::Internal("DATA_SOURCE_KEY is not valid base64".to_string()))?;
+
+    Aes256Gcm::new_from_slice(&key)
+        .map_err(|_| AppError::Internal("DATA_SOURCE_KEY must decode to 32 bytes".to_string()))
+}
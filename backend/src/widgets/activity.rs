@@ -0,0 +1,390 @@
+use axum::{extract::State, response::IntoResponse, Json};
+use chrono::DateTime;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::time::Duration;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    egress::guarded_client,
+    error::{AppError, FieldError, Result},
+    handlers::forge::find_forge_account,
+    models::{forge_base_url, ForgeKind},
+    secrets,
+    widgets::{
+        github::{request_user_events, GitHubEvent, GitHubRepo},
+        WidgetConfig,
+    },
+    AppState,
+};
+
+/// Timeout for each forge API request
+const FORGE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a source's events are cached
+const ACTIVITY_CACHE_TTL: usize = 300;
+
+/// Most sources per widget
+const MAX_SOURCES: usize = 10;
+
+/// Default and largest number of merged events
+const DEFAULT_LIMIT: usize = 30;
+const MAX_LIMIT: usize = 100;
+
+/// Events requested from GitLab and Gitea per user
+const EVENTS_PER_PAGE: usize = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActivityQuery {
+    pub sources: Vec<ActivitySource>,
+    pub limit: Option<usize>,
+}
+
+/// One forge user whose activity is shown
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ActivitySource {
+    /// Required unless `account_id` is given
+    pub forge: Option<ForgeKind>,
+    pub username: String,
+    /// Instance URL (GitHub: API root); defaults to the public instance
+    pub base_url: Option<String>,
+    /// The dashboard owner's forge account whose base URL and token are used
+    pub account_id: Option<Uuid>,
+}
+
+/// Activity for the JSON body's sources, using the caller's forge accounts
+pub async fn fetch_activity_data(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(query): Json<ActivityQuery>,
+) -> Result<impl IntoResponse> {
+    let errors = check_query(&query);
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    Ok(Json(fetch_activity(&state, user_ctx.user_id, &query).await?))
+}
+
+/// Merge recent events from several forges, newest first
+pub async fn fetch_activity(state: &AppState, owner_id: Uuid, query: &ActivityQuery) -> Result<Vec<GitHubEvent>> {
+    let results = join_all(query.sources.iter().map(|source| fetch_source(state, owner_id, source))).await;
+
+    let mut events = Vec::new();
+    let mut last_error = None;
+    for (source, result) in query.sources.iter().zip(results) {
+        match result {
+            Ok(source_events) => events.extend(source_events),
+            Err(e) => {
+                tracing::warn!("Failed to fetch activity for {}: {}", source.username, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    // Only fail when no source could be read at all
+    if events.is_empty() {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+
+    // Forges format timestamps differently, so compare them parsed
+    events.sort_by_key(|event| {
+        let created_at = DateTime::parse_from_rfc3339(&event.created_at).map(|date| date.timestamp_millis());
+        std::cmp::Reverse(created_at.unwrap_or(0))
+    });
+    events.truncate(query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT));
+
+    Ok(events)
+}
+
+/// Fetch one source's events (cached)
+async fn fetch_source(state: &AppState, owner_id: Uuid, source: &ActivitySource) -> Result<Vec<GitHubEvent>> {
+    let (forge, base_url, token) = match source.account_id {
+        Some(account_id) => {
+            let account = find_forge_account(state, owner_id, account_id).await?;
+            if source.forge.is_some_and(|forge| forge != account.forge) {
+                return Err(AppError::Validation(format!(
+                    "Forge account '{}' is a {} account",
+                    account.name,
+                    account.forge.as_str()
+                )));
+            }
+            let token = secrets::decrypt(&state.config, &account.token_encrypted)?;
+            (account.forge, account.base_url, Some(token))
+        }
+        None => {
+            let forge = source
+                .forge
+                .ok_or_else(|| AppError::Validation("forge is required without an account".to_string()))?;
+            (forge, forge_base_url(forge, source.base_url.as_deref())?, None)
+        }
+    };
+
+    let account = source.account_id.map(|id| id.to_string()).unwrap_or_else(|| "anonymous".to_string());
+    let cache_key = format!("activity:{}:{}:{}:{}", forge.as_str(), base_url, source.username, account);
+
+    // Check cache first
+    if let Some(cached) = state.cache.get::<Vec<GitHubEvent>>(&cache_key).await.ok().flatten() {
+        tracing::debug!("Cache hit for {} activity: {}", forge.as_str(), source.username);
+        return Ok(cached);
+    }
+
+    let url = Url::parse(&base_url).map_err(|e| AppError::Validation(format!("Invalid base URL: {}", e)))?;
+    let client = guarded_client(&state.config, &url, FORGE_TIMEOUT).await?;
+    let token = token.as_deref();
+
+    let mut events = match forge {
+        ForgeKind::Github => request_user_events(&client, &state.cache, &base_url, &source.username, token).await?,
+        ForgeKind::Gitlab => fetch_gitlab_events(&client, &base_url, &source.username, token).await?,
+        ForgeKind::Gitea => fetch_gitea_events(&client, &base_url, &source.username, token).await?,
+    };
+    for event in &mut events {
+        event.forge = Some(forge.as_str().to_string());
+    }
+
+    let _ = state.cache.set(&cache_key, &events, ACTIVITY_CACHE_TTL).await;
+
+    Ok(events)
+}
+
+/// A GitLab user's recent events, with project IDs resolved to `group/project` names
+async fn fetch_gitlab_events(
+    client: &reqwest::Client,
+    base_url: &str,
+    username: &str,
+    token: Option<&str>,
+) -> Result<Vec<GitHubEvent>> {
+    let users = get_json(
+        client,
+        &format!("{}/api/v4/users?username={}", base_url, username),
+        gitlab_auth(token),
+        "GitLab",
+    )
+    .await?;
+    let user_id = users[0]["id"]
+        .as_i64()
+        .ok_or_else(|| AppError::NotFound(format!("GitLab user '{}' not found", username)))?;
+
+    let events = get_json(
+        client,
+        &format!("{}/api/v4/users/{}/events?per_page={}", base_url, user_id, EVENTS_PER_PAGE),
+        gitlab_auth(token),
+        "GitLab",
+    )
+    .await?;
+    let events = events.as_array().cloned().unwrap_or_default();
+
+    let mut project_ids: Vec<i64> = events.iter().filter_map(|event| event["project_id"].as_i64()).collect();
+    project_ids.sort();
+    project_ids.dedup();
+
+    // Projects the token can't see keep their numeric ID
+    let project_urls: Vec<String> = project_ids
+        .iter()
+        .map(|id| format!("{}/api/v4/projects/{}", base_url, id))
+        .collect();
+    let projects = join_all(project_urls.iter().map(|url| get_json(client, url, gitlab_auth(token), "GitLab"))).await;
+    let project_names: HashMap<i64, String> = project_ids
+        .iter()
+        .zip(projects)
+        .filter_map(|(id, project)| {
+            let project = project.ok()?;
+            Some((*id, project["path_with_namespace"].as_str()?.to_string()))
+        })
+        .collect();
+
+    Ok(events
+        .iter()
+        .map(|event| {
+            let project_id = event["project_id"].as_i64();
+            GitHubEvent {
+                id: json_id(&event["id"]),
+                event_type: gitlab_event_type(
+                    event["action_name"].as_str().unwrap_or(""),
+                    event["target_type"].as_str(),
+                ),
+                repo: GitHubRepo {
+                    name: project_id
+                        .map(|id| project_names.get(&id).cloned().unwrap_or_else(|| format!("project/{}", id)))
+                        .unwrap_or_default(),
+                },
+                created_at: event["created_at"].as_str().unwrap_or("").to_string(),
+                forge: None,
+            }
+        })
+        .collect())
+}
+
+/// A Gitea user's activity feed (Gitea 1.20+)
+async fn fetch_gitea_events(
+    client: &reqwest::Client,
+    base_url: &str,
+    username: &str,
+    token: Option<&str>,
+) -> Result<Vec<GitHubEvent>> {
+    let auth = token.map(|token| ("Authorization", format!("token {}", token)));
+    let events = get_json(
+        client,
+        &format!(
+            "{}/api/v1/users/{}/activities/feeds?only-performed-by=true&limit={}",
+            base_url, username, EVENTS_PER_PAGE
+        ),
+        auth,
+        "Gitea",
+    )
+    .await?;
+
+    Ok(events
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|event| GitHubEvent {
+            id: json_id(&event["id"]),
+            event_type: gitea_event_type(event["op_type"].as_str().unwrap_or("")),
+            repo: GitHubRepo {
+                name: event["repo"]["full_name"].as_str().unwrap_or("").to_string(),
+            },
+            created_at: event["created"].as_str().unwrap_or("").to_string(),
+            forge: None,
+        })
+        .collect())
+}
+
+fn gitlab_auth(token: Option<&str>) -> Option<(&'static str, String)> {
+    token.map(|token| ("PRIVATE-TOKEN", token.to_string()))
+}
+
+async fn get_json(
+    client: &reqwest::Client,
+    url: &str,
+    auth: Option<(&'static str, String)>,
+    forge: &str,
+) -> Result<JsonValue> {
+    let mut request = client.get(url);
+    if let Some((header, value)) = auth {
+        request = request.header(header, value);
+    }
+
+    let response = request.send().await
+        .map_err(|e| AppError::ExternalApi(format!("{} API error: {}", forge, e)))?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(AppError::NotFound(format!("{} returned 404 for {}", forge, url)));
+    }
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(
+            format!("{} API returned status: {}", forge, response.status())
+        ));
+    }
+
+    response.json().await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse {} response: {}", forge, e)))
+}
+
+fn json_id(id: &JsonValue) -> String {
+    match id {
+        JsonValue::String(id) => id.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Map a GitLab event onto the closest GitHub event type
+fn gitlab_event_type(action: &str, target_type: Option<&str>) -> String {
+    let event_type = match (action, target_type) {
+        ("pushed to" | "pushed new", _) => "PushEvent",
+        ("deleted", None) => "DeleteEvent",
+        ("commented on", _) => "IssueCommentEvent",
+        (_, Some("MergeRequest")) => "PullRequestEvent",
+        (_, Some("Issue")) => "IssuesEvent",
+        ("created", None) => "CreateEvent",
+        ("joined" | "left", _) => "MemberEvent",
+        (other, _) => return other.to_string(),
+    };
+    event_type.to_string()
+}
+
+/// Map a Gitea `op_type` onto the closest GitHub event type
+fn gitea_event_type(op_type: &str) -> String {
+    let event_type = match op_type {
+        "commit_repo" | "push_tag" | "mirror_sync_push" => "PushEvent",
+        "create_repo" => "CreateEvent",
+        "delete_tag" | "delete_branch" => "DeleteEvent",
+        "fork_repo" => "ForkEvent",
+        "star_repo" => "WatchEvent",
+        "create_issue" | "close_issue" | "reopen_issue" => "IssuesEvent",
+        "comment_issue" | "comment_pull" => "IssueCommentEvent",
+        "create_pull_request" | "merge_pull_request" | "close_pull_request"
+        | "reopen_pull_request" | "auto_merge_pull_request" => "PullRequestEvent",
+        "approve_pull_request" | "reject_pull_request" => "PullRequestReviewEvent",
+        "publish_release" => "ReleaseEvent",
+        other => return other.to_string(),
+    };
+    event_type.to_string()
+}
+
+/// Forge usernames: letters, digits, `-`, `_` and `.` (also keeps them safe in URL paths)
+fn is_valid_username(username: &str) -> bool {
+    !username.is_empty()
+        && username.len() <= 255
+        && !username.starts_with('.')
+        && username.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Validate a dashboard widget's config for the multi-forge activity provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    match serde_path_to_error::deserialize::<_, ActivityQuery>(JsonValue::Object(config.clone())) {
+        Ok(query) => check_query(&query),
+        Err(e) => {
+            let path = e.path().to_string();
+            let field = if path == "." { "config".to_string() } else { path };
+            vec![FieldError::new(field, e.inner().to_string())]
+        }
+    }
+}
+
+fn check_query(query: &ActivityQuery) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if query.sources.is_empty() {
+        errors.push(FieldError::new("sources", "At least one source is required"));
+    } else if query.sources.len() > MAX_SOURCES {
+        errors.push(FieldError::new("sources", format!("At most {} sources are allowed", MAX_SOURCES)));
+    }
+
+    for (index, source) in query.sources.iter().enumerate() {
+        let prefix = format!("sources[{}]", index);
+
+        if !is_valid_username(&source.username) {
+            errors.push(FieldError::new("username", "Invalid username format").nested(&prefix));
+        }
+
+        match (source.forge, source.account_id) {
+            (None, None) => {
+                errors.push(FieldError::new("forge", "forge is required without an account").nested(&prefix));
+            }
+            (Some(_), Some(_)) | (None, Some(_)) if source.base_url.is_some() => {
+                errors.push(FieldError::new("base_url", "The account's base URL is used").nested(&prefix));
+            }
+            (Some(forge), None) => {
+                if let Err(AppError::Validation(message)) = forge_base_url(forge, source.base_url.as_deref()) {
+                    errors.push(FieldError::new("base_url", message).nested(&prefix));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if query.limit.is_some_and(|limit| limit == 0 || limit > MAX_LIMIT) {
+        errors.push(FieldError::new("limit", format!("Must be between 1 and {}", MAX_LIMIT)));
+    }
+
+    errors
+}
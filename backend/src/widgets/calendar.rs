@@ -0,0 +1,229 @@
+use axum::{extract::{Query, State}, response::IntoResponse, Json};
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use chrono_tz::Tz;
+use futures::future::join_all;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::{
+    auth::UserCtx,
+    egress::{guarded_get, read_limited, MAX_RESPONSE_BYTES},
+    error::{AppError, FieldError, Result},
+    widgets::{ics::IcsCalendar, required_str, WidgetConfig},
+    AppState,
+};
+
+/// Timeout for each calendar request
+const CALENDAR_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// How long a fetched ICS document is cached
+const CALENDAR_CACHE_TTL: usize = 300;
+
+/// Most calendars per widget
+const MAX_CALENDARS: usize = 10;
+
+/// Default and largest look-ahead window, in days
+const DEFAULT_DAYS: i64 = 7;
+const MAX_DAYS: i64 = 90;
+
+/// Default and largest number of events returned
+const DEFAULT_LIMIT: usize = 50;
+const MAX_LIMIT: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarQuery {
+    pub urls: String, // Comma-separated ICS feed URLs (webcal:// is fetched over https)
+    /// Viewer's IANA timezone; event times are returned in it
+    pub timezone: Option<String>,
+    pub days: Option<i64>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CalendarEvent {
+    pub calendar: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub start: DateTime<FixedOffset>,
+    pub end: DateTime<FixedOffset>,
+    pub all_day: bool,
+    /// Whether the event is happening right now (e.g. the current on-call shift)
+    pub ongoing: bool,
+}
+
+pub async fn fetch_calendar_data(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Query(query): Query<CalendarQuery>,
+) -> Result<impl IntoResponse> {
+    Ok(Json(fetch_calendar_events(&state, &query).await?))
+}
+
+/// Upcoming (and ongoing) events from several ICS feeds, in the viewer's timezone
+pub async fn fetch_calendar_events(state: &AppState, query: &CalendarQuery) -> Result<Vec<CalendarEvent>> {
+    let timezone = parse_timezone(query.timezone.as_deref()).map_err(AppError::Validation)?;
+    let days = query.days.unwrap_or(DEFAULT_DAYS).clamp(1, MAX_DAYS);
+
+    let urls = calendar_urls(&query.urls);
+    if urls.is_empty() {
+        return Err(AppError::Validation("At least one calendar URL is required".to_string()));
+    }
+
+    let documents = join_all(urls.iter().map(|url| fetch_ics(state, url))).await;
+
+    let now = Utc::now();
+    let until = now + Duration::days(days);
+    let mut events = Vec::new();
+    let mut last_error = None;
+
+    for (url, document) in urls.iter().zip(documents) {
+        let calendar = document.and_then(|text| {
+            IcsCalendar::parse(&text, timezone)
+                .map_err(|e| AppError::ExternalApi(format!("Failed to parse calendar {}: {}", url, e)))
+        });
+
+        let calendar = match calendar {
+            Ok(calendar) => calendar,
+            Err(e) => {
+                tracing::warn!("Failed to load calendar {}: {}", url, e);
+                last_error = Some(e);
+                continue;
+            }
+        };
+
+        let name = calendar
+            .name
+            .clone()
+            .or_else(|| Url::parse(url).ok().and_then(|url| url.host_str().map(str::to_string)))
+            .unwrap_or_else(|| url.clone());
+
+        events.extend(calendar.occurrences(now, until).into_iter().map(|occurrence| CalendarEvent {
+            calendar: name.clone(),
+            summary: occurrence.summary,
+            description: occurrence.description,
+            location: occurrence.location,
+            start: occurrence.start.with_timezone(&timezone).fixed_offset(),
+            end: occurrence.end.with_timezone(&timezone).fixed_offset(),
+            all_day: occurrence.all_day,
+            ongoing: occurrence.start <= now && now < occurrence.end,
+        }));
+    }
+
+    // Only fail when no calendar could be read at all
+    if events.is_empty() {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+
+    events.sort_by_key(|event| event.start);
+    events.truncate(query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT));
+
+    Ok(events)
+}
+
+/// Fetch an ICS document (cached briefly; expansion happens per request so `ongoing` stays fresh)
+async fn fetch_ics(state: &AppState, url: &str) -> Result<String> {
+    let cache_key = format!("calendar:{}", url);
+
+    // Check cache first
+    if let Some(cached) = state.cache.get::<String>(&cache_key).await.ok().flatten() {
+        tracing::debug!("Cache hit for calendar: {}", url);
+        return Ok(cached);
+    }
+
+    let parsed = fetch_url(url).map_err(AppError::Validation)?;
+    let response = guarded_get(&state.config, &parsed, HeaderMap::new(), CALENDAR_TIMEOUT).await?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(
+            format!("{} returned status: {}", url, response.status())
+        ));
+    }
+
+    let body = read_limited(response, MAX_RESPONSE_BYTES).await?;
+    let text = String::from_utf8_lossy(&body).into_owned();
+
+    let _ = state.cache.set(&cache_key, &text, CALENDAR_CACHE_TTL).await;
+
+    Ok(text)
+}
+
+/// The URL to request; `webcal://` is the same feed over https
+fn fetch_url(url: &str) -> std::result::Result<Url, String> {
+    let url = match url.strip_prefix("webcal://") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    };
+
+    match Url::parse(&url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(parsed),
+        _ => Err(format!("Not an http(s) or webcal URL: {}", url)),
+    }
+}
+
+fn parse_timezone(timezone: Option<&str>) -> std::result::Result<Tz, String> {
+    match timezone.map(str::trim).filter(|timezone| !timezone.is_empty()) {
+        Some(timezone) => timezone
+            .parse::<Tz>()
+            .map_err(|_| format!("Unknown timezone '{}'", timezone)),
+        None => Ok(Tz::UTC),
+    }
+}
+
+/// Split and trim the comma-separated calendar URLs
+fn calendar_urls(urls: &str) -> Vec<String> {
+    let mut unique = Vec::new();
+    for url in urls.split(',').map(str::trim).filter(|url| !url.is_empty()) {
+        if !unique.iter().any(|existing| existing == url) {
+            unique.push(url.to_string());
+        }
+    }
+    unique
+}
+
+/// Validate a dashboard widget's config for the calendar provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if let Some(urls) = required_str(config, "urls", &mut errors) {
+        let urls = calendar_urls(urls);
+        if urls.is_empty() {
+            errors.push(FieldError::new("urls", "At least one calendar URL is required"));
+        } else if urls.len() > MAX_CALENDARS {
+            errors.push(FieldError::new("urls", format!("At most {} calendars are allowed", MAX_CALENDARS)));
+        }
+
+        for url in urls {
+            if let Err(message) = fetch_url(&url) {
+                errors.push(FieldError::new("urls", message));
+            }
+        }
+    }
+
+    match config.get("timezone") {
+        None => {}
+        Some(serde_json::Value::String(timezone)) => {
+            if let Err(message) = parse_timezone(Some(timezone)) {
+                errors.push(FieldError::new("timezone", message));
+            }
+        }
+        Some(_) => errors.push(FieldError::new("timezone", "timezone must be a string")),
+    }
+
+    match config.get("days") {
+        None => {}
+        Some(days) if days.as_i64().is_some_and(|days| (1..=MAX_DAYS).contains(&days)) => {}
+        Some(_) => errors.push(FieldError::new("days", format!("Must be between 1 and {}", MAX_DAYS))),
+    }
+
+    match config.get("limit") {
+        None => {}
+        Some(limit) if limit.as_u64().is_some_and(|limit| (1..=MAX_LIMIT as u64).contains(&limit)) => {}
+        Some(_) => errors.push(FieldError::new("limit", format!("Must be between 1 and {}", MAX_LIMIT))),
+    }
+
+    errors
+}
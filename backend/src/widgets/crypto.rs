@@ -1,11 +1,17 @@
 use axum::{extract::{Query, State}, response::IntoResponse, Json};
 use serde::{Deserialize, Serialize};
 
-use crate::{auth::UserCtx, error::{AppError, Result}, AppState};
+use crate::{
+    auth::UserCtx,
+    error::{AppError, FieldError, Result},
+    models::MetricPoint,
+    widgets::{history::record_metrics, required_str, WidgetConfig, WidgetType},
+    AppState,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct CryptoQuery {
-    #[serde(default = "default_symbols")]
+    #[serde(default = "default_symbols", alias = "cryptoIds")]
     pub symbols: String, // Comma-separated, e.g., "BTC,ETH,SOL"
 }
 
@@ -27,12 +33,17 @@ pub async fn fetch_crypto_data(
     State(state): State<AppState>,
     Query(query): Query<CryptoQuery>,
 ) -> Result<impl IntoResponse> {
+    Ok(Json(fetch_crypto_prices(&state, &query).await?))
+}
+
+/// Fetch prices for a list of coins (cached)
+pub async fn fetch_crypto_prices(state: &AppState, query: &CryptoQuery) -> Result<Vec<CryptoPrice>> {
     let cache_key = format!("crypto:{}", query.symbols);
     
     // Check cache first
     if let Some(cached) = state.cache.get::<Vec<CryptoPrice>>(&cache_key).await.ok().flatten() {
         tracing::debug!("Cache hit for crypto data: {}", query.symbols);
-        return Ok(Json(cached));
+        return Ok(cached);
     }
     
     // For now, use CoinGecko API (free, no key required)
@@ -71,8 +82,30 @@ pub async fn fetch_crypto_data(
         }
     }
     
+    let points = prices
+        .iter()
+        .map(|price| MetricPoint::new(&price.symbol, "price", price.price))
+        .collect();
+    record_metrics(state, WidgetType::Crypto, points).await;
+    
     // Cache for 5 minutes
     let _ = state.cache.set(&cache_key, &prices, 300).await;
     
-    Ok(Json(prices))
+    Ok(prices)
+}
+
+/// Validate a dashboard widget's config for the crypto provider
+///
+/// The frontend stores the coin list as `cryptoIds`; `symbols` matches the query parameter.
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    let key = if config.contains_key("symbols") { "symbols" } else { "cryptoIds" };
+
+    if let Some(ids) = required_str(config, key, &mut errors) {
+        if ids.split(',').all(|id| id.trim().is_empty()) {
+            errors.push(FieldError::new(key, "At least one cryptocurrency ID is required"));
+        }
+    }
+
+    errors
 }
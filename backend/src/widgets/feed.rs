@@ -0,0 +1,369 @@
+use axum::{extract::{Query, State}, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use reqwest::{header, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Duration;
+use url::Url;
+
+use crate::{
+    auth::UserCtx,
+    egress::{guarded_get, read_limited, MAX_RESPONSE_BYTES},
+    error::{AppError, FieldError, Result},
+    widgets::{news::NewsArticle, required_str, WidgetConfig},
+    AppState,
+};
+
+/// Timeout for each feed request
+const FEED_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a fetched feed is served before it is revalidated
+const FEED_REFRESH_SECONDS: i64 = 600;
+
+/// How long a feed (and its ETag/Last-Modified) is kept for revalidation
+const FEED_CACHE_TTL: usize = 86_400;
+
+/// Most feeds per widget
+const MAX_FEEDS: usize = 10;
+
+/// Default and largest number of merged items
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+
+/// Longest item description, in characters
+const MAX_DESCRIPTION_CHARS: usize = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct FeedQuery {
+    pub urls: String, // Comma-separated RSS/Atom feed URLs
+    pub limit: Option<usize>,
+}
+
+/// A parsed feed plus the validators needed for a conditional GET
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedFeed {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: DateTime<Utc>,
+    items: Vec<FeedItem>,
+}
+
+/// An article plus the entry's guid, which identifies it across feeds even when links differ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FeedItem {
+    guid: String,
+    #[serde(flatten)]
+    article: NewsArticle,
+}
+
+pub async fn fetch_feed_data(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Query(query): Query<FeedQuery>,
+) -> Result<impl IntoResponse> {
+    Ok(Json(fetch_feed_articles(&state, &query).await?))
+}
+
+/// Fetch, merge and deduplicate the items of several feeds, newest first
+pub async fn fetch_feed_articles(state: &AppState, query: &FeedQuery) -> Result<Vec<NewsArticle>> {
+    let urls = feed_urls(&query.urls);
+    let errors = check_urls(&urls);
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    let results = join_all(urls.iter().map(|url| fetch_feed(state, url))).await;
+
+    let mut items = Vec::new();
+    let mut last_error = None;
+    for (url, result) in urls.iter().zip(results) {
+        match result {
+            Ok(feed) => items.extend(feed),
+            Err(e) => {
+                tracing::warn!("Failed to fetch feed {}: {}", url, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    // Only fail when no feed could be read at all
+    if items.is_empty() {
+        if let Some(e) = last_error {
+            return Err(e);
+        }
+    }
+
+    // RFC 3339 timestamps in UTC sort chronologically as strings
+    items.sort_by(|a, b| b.article.published_at.cmp(&a.article.published_at));
+
+    Ok(dedup_items(items)
+        .into_iter()
+        .take(query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT))
+        .map(|item| item.article)
+        .collect())
+}
+
+/// Drop items whose link or guid was already seen, keeping the first occurrence
+fn dedup_items(items: Vec<FeedItem>) -> Vec<FeedItem> {
+    let mut seen_urls = HashSet::new();
+    let mut seen_guids = HashSet::new();
+    items
+        .into_iter()
+        .filter(|item| {
+            let duplicate = seen_urls.contains(&item.article.url) || seen_guids.contains(&item.guid);
+            seen_urls.insert(item.article.url.clone());
+            seen_guids.insert(item.guid.clone());
+            !duplicate
+        })
+        .collect()
+}
+
+/// Fetch one feed, revalidating a stale cached copy with a conditional GET
+async fn fetch_feed(state: &AppState, url: &str) -> Result<Vec<FeedItem>> {
+    let cache_key = format!("feed:{}", url);
+    let cached = state.cache.get::<CachedFeed>(&cache_key).await.ok().flatten();
+
+    if let Some(cached) = &cached {
+        if (Utc::now() - cached.fetched_at).num_seconds() < FEED_REFRESH_SECONDS {
+            tracing::debug!("Cache hit for feed: {}", url);
+            return Ok(cached.items.clone());
+        }
+    }
+
+    let parsed = Url::parse(url).map_err(|e| AppError::Validation(format!("Invalid feed URL: {}", e)))?;
+
+    let mut headers = header::HeaderMap::new();
+    if let Some(cached) = &cached {
+        if let Some(value) = cached.etag.as_deref().and_then(|etag| etag.parse().ok()) {
+            headers.insert(header::IF_NONE_MATCH, value);
+        }
+        if let Some(value) = cached.last_modified.as_deref().and_then(|date| date.parse().ok()) {
+            headers.insert(header::IF_MODIFIED_SINCE, value);
+        }
+    }
+
+    let response = guarded_get(&state.config, &parsed, headers, FEED_TIMEOUT).await?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        if let Some(mut cached) = cached {
+            tracing::debug!("Feed not modified: {}", url);
+            cached.fetched_at = Utc::now();
+            let _ = state.cache.set(&cache_key, &cached, FEED_CACHE_TTL).await;
+            return Ok(cached.items);
+        }
+    }
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(
+            format!("{} returned status: {}", url, response.status())
+        ));
+    }
+
+    let header_value = |name: header::HeaderName| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header_value(header::ETAG);
+    let last_modified = header_value(header::LAST_MODIFIED);
+
+    let body = read_limited(response, MAX_RESPONSE_BYTES).await?;
+    let feed = feed_rs::parser::parse(body.as_slice())
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse feed {}: {}", url, e)))?;
+
+    let source = feed
+        .title
+        .as_ref()
+        .map(|title| title.content.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .or_else(|| parsed.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string());
+
+    let items: Vec<FeedItem> = feed
+        .entries
+        .into_iter()
+        .map(|entry| FeedItem { guid: entry.id.clone(), article: to_article(entry, &source) })
+        .filter(|item| !item.article.url.is_empty())
+        .collect();
+
+    let cached = CachedFeed { etag, last_modified, fetched_at: Utc::now(), items };
+    let _ = state.cache.set(&cache_key, &cached, FEED_CACHE_TTL).await;
+
+    Ok(cached.items)
+}
+
+/// Map a feed entry onto the news widget's article shape
+fn to_article(entry: feed_rs::model::Entry, source: &str) -> NewsArticle {
+    // Prefer the entry's alternate link; fall back to its guid when it has none
+    let url = entry
+        .links
+        .iter()
+        .find(|link| link.rel.as_deref().is_none_or(|rel| rel == "alternate"))
+        .or_else(|| entry.links.first())
+        .map(|link| link.href.clone())
+        .unwrap_or_else(|| entry.id.clone());
+
+    let description = entry
+        .summary
+        .map(|summary| summary.content)
+        .or_else(|| entry.content.and_then(|content| content.body))
+        .map(|text| plain_text(&text))
+        .filter(|text| !text.is_empty());
+
+    let url_to_image = entry
+        .media
+        .iter()
+        .flat_map(|media| media.thumbnails.iter())
+        .map(|thumbnail| thumbnail.image.uri.clone())
+        .chain(entry.media.iter().flat_map(|media| media.content.iter()).filter_map(|content| {
+            let is_image = content
+                .content_type
+                .as_ref()
+                .is_some_and(|content_type| content_type.ty().as_str() == "image");
+            content.url.as_ref().filter(|_| is_image).map(|url| url.to_string())
+        }))
+        .chain(entry.links.iter().filter_map(|link| {
+            let is_image = link.media_type.as_deref().is_some_and(|media_type| media_type.starts_with("image/"));
+            is_image.then(|| link.href.clone())
+        }))
+        .next();
+
+    NewsArticle {
+        title: entry.title.map(|title| title.content.trim().to_string()).unwrap_or_default(),
+        description,
+        url,
+        source: source.to_string(),
+        published_at: entry
+            .published
+            .or(entry.updated)
+            .map(|date| date.to_rfc3339())
+            .unwrap_or_default(),
+        url_to_image,
+    }
+}
+
+/// Strip tags from an HTML snippet, collapse whitespace and truncate it
+fn plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    let text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    match text.char_indices().nth(MAX_DESCRIPTION_CHARS) {
+        Some((index, _)) => format!("{}…", &text[..index]),
+        None => text,
+    }
+}
+
+/// Split and trim the comma-separated feed URLs
+fn feed_urls(urls: &str) -> Vec<String> {
+    let mut unique = Vec::new();
+    for url in urls.split(',').map(str::trim).filter(|url| !url.is_empty()) {
+        if !unique.iter().any(|existing| existing == url) {
+            unique.push(url.to_string());
+        }
+    }
+    unique
+}
+
+/// Check the feed count and that every URL is http(s)
+fn check_urls(urls: &[String]) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if urls.is_empty() {
+        errors.push(FieldError::new("urls", "At least one feed URL is required"));
+    } else if urls.len() > MAX_FEEDS {
+        errors.push(FieldError::new("urls", format!("At most {} feeds are allowed", MAX_FEEDS)));
+    }
+
+    for url in urls {
+        match Url::parse(url) {
+            Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+            _ => errors.push(FieldError::new("urls", format!("Not an http(s) URL: {}", url))),
+        }
+    }
+
+    errors
+}
+
+/// Validate a dashboard widget's config for the RSS/Atom feed provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if let Some(urls) = required_str(config, "urls", &mut errors) {
+        errors.extend(check_urls(&feed_urls(urls)));
+    }
+
+    match config.get("limit") {
+        None => {}
+        Some(limit) if limit.as_u64().is_some_and(|limit| (1..=MAX_LIMIT as u64).contains(&limit)) => {}
+        Some(_) => errors.push(FieldError::new("limit", format!("Must be between 1 and {}", MAX_LIMIT))),
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(guid: &str, url: &str) -> FeedItem {
+        FeedItem {
+            guid: guid.to_string(),
+            article: NewsArticle {
+                title: guid.to_string(),
+                description: None,
+                url: url.to_string(),
+                source: "Example".to_string(),
+                published_at: String::new(),
+                url_to_image: None,
+            },
+        }
+    }
+
+    #[test]
+    fn dedup_matches_on_link_or_guid() {
+        let items = vec![
+            item("a", "https://example.com/a"),
+            item("a-mirror", "https://example.com/a"),
+            item("b", "https://example.com/b"),
+            item("b", "https://mirror.example.com/b?utm_source=feed"),
+            item("c", "https://example.com/c"),
+        ];
+
+        let guids: Vec<_> = dedup_items(items).into_iter().map(|item| item.guid).collect();
+        assert_eq!(guids, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn check_urls_enforces_count_and_scheme() {
+        assert!(check_urls(&["https://example.com/feed.xml".to_string()]).is_empty());
+        assert_eq!(check_urls(&[]).len(), 1);
+        assert_eq!(check_urls(&["file:///etc/passwd".to_string()]).len(), 1);
+
+        let too_many: Vec<_> = (0..=MAX_FEEDS).map(|i| format!("https://example.com/{}.xml", i)).collect();
+        assert_eq!(check_urls(&too_many).len(), 1);
+    }
+}
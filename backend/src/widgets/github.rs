@@ -1,11 +1,80 @@
 use axum::{extract::{Query, State}, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use futures::{stream, StreamExt};
+use reqwest::{header, header::HeaderMap, StatusCode};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
 
-use crate::{auth::UserCtx, error::Result, AppState};
+use crate::{
+    auth::{AdminCtx, UserCtx},
+    cache::Cache,
+    error::{AppError, FieldError, Result},
+    widgets::WidgetConfig,
+    AppState,
+};
+
+/// Public GitHub API root
+pub(crate) const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// How long a stored ETag and response body are kept for conditional requests
+const ETAG_CACHE_TTL: usize = 86_400;
+
+/// Widget data is cached this many times longer while the quota is low
+const LOW_QUOTA_TTL_FACTOR: usize = 4;
+
+/// Most pages followed for one listing
+const MAX_PAGES: usize = 5;
+
+/// Most reviews read per pull request, and how many pull requests have theirs fetched at once
+const MAX_REVIEWS: usize = 100;
+const REVIEW_CONCURRENCY: usize = 4;
+
+/// Default and largest number of items returned by the repository modes
+const DEFAULT_LIMIT: usize = 20;
+const MAX_LIMIT: usize = 100;
+
+/// What the GitHub widget shows
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitHubMode {
+    /// A user's public events
+    #[default]
+    Events,
+    /// Stars, forks and open issues of a repository
+    Repo,
+    /// Open pull requests with their review status
+    Pulls,
+    /// Open issues, optionally filtered by label
+    Issues,
+    /// The latest Actions workflow run per workflow and branch
+    Workflows,
+}
+
+impl GitHubMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GitHubMode::Events => "events",
+            GitHubMode::Repo => "repo",
+            GitHubMode::Pulls => "pulls",
+            GitHubMode::Issues => "issues",
+            GitHubMode::Workflows => "workflows",
+        }
+    }
+}
 
 #[derive(Debug, Deserialize)]
 pub struct GitHubQuery {
-    pub username: String,
+    #[serde(default)]
+    pub mode: GitHubMode,
+    /// Required for `events`
+    pub username: Option<String>,
+    /// `owner/name`, required for every other mode
+    pub repo: Option<String>,
+    pub labels: Option<String>, // Comma-separated, `issues` only
+    pub branch: Option<String>, // `workflows` only
+    pub limit: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,6 +84,9 @@ pub struct GitHubEvent {
     pub event_type: String,
     pub repo: GitHubRepo,
     pub created_at: String,
+    /// Forge the event came from, set by the multi-forge activity widget
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forge: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,43 +94,757 @@ pub struct GitHubRepo {
     pub name: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepoStats {
+    pub full_name: String,
+    pub description: Option<String>,
+    pub html_url: String,
+    pub stars: u64,
+    pub forks: u64,
+    pub watchers: u64,
+    /// GitHub counts open pull requests as issues too
+    pub open_issues: u64,
+    pub default_branch: String,
+    pub pushed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewStatus {
+    Approved,
+    ChangesRequested,
+    ReviewRequested,
+    Pending,
+}
+
+impl ReviewStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReviewStatus::Approved => "approved",
+            ReviewStatus::ChangesRequested => "changes requested",
+            ReviewStatus::ReviewRequested => "review requested",
+            ReviewStatus::Pending => "pending",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub html_url: String,
+    pub draft: bool,
+    pub review_status: ReviewStatus,
+    pub approvals: usize,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub author: String,
+    pub html_url: String,
+    pub labels: Vec<String>,
+    pub comments: u64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WorkflowRun {
+    pub id: u64,
+    pub workflow: String,
+    pub branch: String,
+    pub event: String,
+    /// `queued`, `in_progress` or `completed`
+    pub status: String,
+    /// `success`, `failure`, `cancelled`, ... once completed
+    pub conclusion: Option<String>,
+    pub html_url: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The GitHub widget's output; events stay a bare list for existing dashboards
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GitHubData {
+    Events(Vec<GitHubEvent>),
+    Pulls { pulls: Vec<PullRequest> },
+    Issues { issues: Vec<Issue> },
+    Workflows { runs: Vec<WorkflowRun> },
+    Repo(RepoStats),
+}
+
+/// The rate-limit window last reported for a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubQuota {
+    pub limit: u64,
+    pub remaining: u64,
+    pub used: u64,
+    pub reset_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl GitHubQuota {
+    /// Read the `X-RateLimit-*` headers of a response
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let number = |name: &str| -> Option<u64> {
+            headers.get(name)?.to_str().ok()?.trim().parse().ok()
+        };
+
+        let limit = number("x-ratelimit-limit")?;
+        let remaining = number("x-ratelimit-remaining")?;
+        let reset_at = DateTime::from_timestamp(number("x-ratelimit-reset")? as i64, 0)?;
+
+        Some(Self {
+            limit,
+            remaining,
+            used: number("x-ratelimit-used").unwrap_or(limit.saturating_sub(remaining)),
+            reset_at,
+            updated_at: Utc::now(),
+        })
+    }
+
+    /// Less than a tenth of the window is left
+    pub fn is_low(&self) -> bool {
+        self.reset_at > Utc::now() && self.remaining * 10 < self.limit
+    }
+
+    /// Nothing is left until the window resets
+    pub fn is_exhausted(&self) -> bool {
+        self.reset_at > Utc::now() && self.remaining == 0
+    }
+
+    fn exhausted_error(&self) -> AppError {
+        AppError::RateLimited(format!(
+            "GitHub API rate limit exhausted until {}",
+            self.reset_at.format("%H:%M UTC")
+        ))
+    }
+}
+
+/// Quota of the deployment's GitHub token, for the admin endpoint
+#[derive(Debug, Serialize)]
+pub struct GitHubQuotaStatus {
+    pub authenticated: bool,
+    /// `None` until a GitHub response has been seen
+    pub quota: Option<GitHubQuota>,
+}
+
+/// A response body kept with its ETag so unchanged resources cost no rate limit
+#[derive(Debug, Serialize, Deserialize)]
+struct ConditionalResponse {
+    etag: String,
+    body: JsonValue,
+    next: Option<String>,
+}
+
 pub async fn fetch_github_data(
     _user_ctx: UserCtx,
     State(state): State<AppState>,
     Query(query): Query<GitHubQuery>,
 ) -> Result<impl IntoResponse> {
-    let cache_key = format!("github:{}", query.username);
-    
+    let errors = check_query(&query);
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    Ok(Json(fetch_github(&state, &query).await?))
+}
+
+/// Current rate-limit quota of the configured GitHub token (admins only)
+pub async fn fetch_github_quota(
+    _admin_ctx: AdminCtx,
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse> {
+    let token = state.config.github_api_token.as_deref();
+
+    // `/rate_limit` itself does not count against the quota
+    let mut request = reqwest::Client::new()
+        .get(format!("{}/rate_limit", GITHUB_API_URL))
+        .header(header::USER_AGENT, "InsightBoard");
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("token {}", token));
+    }
+
+    match request.send().await {
+        Ok(response) => record_quota(&state.cache, token, response.headers()).await,
+        Err(e) => tracing::warn!("Failed to refresh GitHub quota: {}", e),
+    }
+
+    Ok(Json(GitHubQuotaStatus {
+        authenticated: token.is_some(),
+        quota: current_quota(&state.cache, token).await,
+    }))
+}
+
+/// Fetch the data for a GitHub widget in any mode (cached per mode)
+pub async fn fetch_github(state: &AppState, query: &GitHubQuery) -> Result<GitHubData> {
+    let subject = match query.mode {
+        GitHubMode::Events => query.username.as_deref(),
+        _ => query.repo.as_deref(),
+    }
+    .map(str::trim)
+    .filter(|subject| !subject.is_empty())
+    .ok_or_else(|| AppError::Validation(match query.mode {
+        GitHubMode::Events => "username is required".to_string(),
+        mode => format!("repo is required in {} mode", mode.as_str()),
+    }))?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let labels = normalized_labels(query.labels.as_deref());
+    let branch = query.branch.as_deref().map(str::trim).unwrap_or_default();
+
+    // Events keep their original key; each other mode gets its own
+    let cache_key = match query.mode {
+        GitHubMode::Events => format!("github:{}", subject),
+        GitHubMode::Repo => format!("github:repo:{}", subject.to_lowercase()),
+        GitHubMode::Pulls => format!("github:pulls:{}:{}", subject.to_lowercase(), limit),
+        GitHubMode::Issues => format!("github:issues:{}:{}:{}", subject.to_lowercase(), labels, limit),
+        GitHubMode::Workflows => format!("github:workflows:{}:{}:{}", subject.to_lowercase(), branch, limit),
+    };
+
     // Check cache first
-    if let Some(cached) = state.cache.get::<Vec<GitHubEvent>>(&cache_key).await.ok().flatten() {
-        tracing::debug!("Cache hit for GitHub data: {}", query.username);
-        return Ok(Json(cached));
+    if let Some(cached) = state.cache.get::<GitHubData>(&cache_key).await.ok().flatten() {
+        tracing::debug!("Cache hit for GitHub {} data: {}", query.mode.as_str(), subject);
+        return Ok(cached);
     }
-    
-    // Fetch from GitHub API
-    let client = reqwest::Client::new();
+
+    let result = match query.mode {
+        GitHubMode::Events => fetch_github_events(state, subject).await.map(|events| (GitHubData::Events(events), 300)),
+        GitHubMode::Repo => fetch_repo_stats(state, subject).await.map(|repo| (GitHubData::Repo(repo), 600)),
+        GitHubMode::Pulls => fetch_pulls(state, subject, limit)
+            .await
+            .map(|pulls| (GitHubData::Pulls { pulls }, 300)),
+        GitHubMode::Issues => fetch_issues(state, subject, &labels, limit)
+            .await
+            .map(|issues| (GitHubData::Issues { issues }, 300)),
+        GitHubMode::Workflows => fetch_workflow_runs(state, subject, branch, limit)
+            .await
+            .map(|runs| (GitHubData::Workflows { runs }, 60)),
+    };
+
+    let (data, mut ttl) = result.map_err(|e| match e {
+        AppError::NotFound(_) if query.mode == GitHubMode::Events => {
+            AppError::NotFound(format!("GitHub user '{}' not found", subject))
+        }
+        AppError::NotFound(_) => AppError::NotFound(format!("GitHub repository '{}' not found", subject)),
+        e => e,
+    })?;
+
+    // Refresh less often while the token is running out
+    let token = state.config.github_api_token.as_deref();
+    if current_quota(&state.cache, token).await.is_some_and(|quota| quota.is_low()) {
+        ttl *= LOW_QUOTA_TTL_FACTOR;
+    }
+
+    let _ = state.cache.set(&cache_key, &data, ttl).await;
+
+    Ok(data)
+}
+
+/// A user's public events (the most recent page)
+async fn fetch_github_events(state: &AppState, username: &str) -> Result<Vec<GitHubEvent>> {
+    let url = format!("{}/users/{}/events/public", GITHUB_API_URL, username);
+    let (body, _) = conditional_get(state, &url).await?;
+
+    serde_json::from_value(body)
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse GitHub response: {}", e)))
+}
+
+async fn fetch_repo_stats(state: &AppState, repo: &str) -> Result<RepoStats> {
+    #[derive(Deserialize)]
+    struct ApiRepo {
+        full_name: String,
+        description: Option<String>,
+        html_url: String,
+        stargazers_count: u64,
+        forks_count: u64,
+        subscribers_count: Option<u64>,
+        open_issues_count: u64,
+        default_branch: String,
+        pushed_at: Option<DateTime<Utc>>,
+    }
+
+    let (body, _) = conditional_get(state, &format!("{}/repos/{}", GITHUB_API_URL, repo)).await?;
+    let api: ApiRepo = parse_body(body)?;
+
+    Ok(RepoStats {
+        full_name: api.full_name,
+        description: api.description,
+        html_url: api.html_url,
+        stars: api.stargazers_count,
+        forks: api.forks_count,
+        watchers: api.subscribers_count.unwrap_or_default(),
+        open_issues: api.open_issues_count,
+        default_branch: api.default_branch,
+        pushed_at: api.pushed_at,
+    })
+}
+
+#[derive(Deserialize)]
+struct ApiUser {
+    login: String,
+}
+
+async fn fetch_pulls(state: &AppState, repo: &str, limit: usize) -> Result<Vec<PullRequest>> {
+    #[derive(Deserialize)]
+    struct ApiPull {
+        number: u64,
+        title: String,
+        user: Option<ApiUser>,
+        html_url: String,
+        #[serde(default)]
+        draft: bool,
+        #[serde(default)]
+        requested_reviewers: Vec<ApiUser>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    }
+
+    #[derive(Deserialize)]
+    struct ApiReview {
+        user: Option<ApiUser>,
+        state: String,
+    }
+
+    let url = format!("{}/repos/{}/pulls?state=open&sort=updated&direction=desc", GITHUB_API_URL, repo);
+    let pulls: Vec<ApiPull> = fetch_pages(state, &url, limit)
+        .await?
+        .into_iter()
+        .map(parse_body)
+        .collect::<Result<_>>()?;
+
+    let review_urls: Vec<String> = pulls
+        .iter()
+        .map(|pull| format!("{}/repos/{}/pulls/{}/reviews", GITHUB_API_URL, repo, pull.number))
+        .collect();
+    let reviews: Vec<_> = stream::iter(review_urls)
+        .map(|url| async move { fetch_pages(state, &url, MAX_REVIEWS).await })
+        .buffered(REVIEW_CONCURRENCY)
+        .collect()
+        .await;
+
+    pulls
+        .into_iter()
+        .zip(reviews)
+        .map(|(pull, reviews)| {
+            let reviews: Vec<ApiReview> = reviews?.into_iter().map(parse_body).collect::<Result<_>>()?;
+
+            // Only each reviewer's latest approving or blocking review counts
+            let mut latest: BTreeMap<String, String> = BTreeMap::new();
+            for review in reviews {
+                if matches!(review.state.as_str(), "APPROVED" | "CHANGES_REQUESTED" | "DISMISSED") {
+                    if let Some(user) = review.user {
+                        latest.insert(user.login, review.state);
+                    }
+                }
+            }
+
+            let approvals = latest.values().filter(|state| *state == "APPROVED").count();
+            let review_status = if latest.values().any(|state| state == "CHANGES_REQUESTED") {
+                ReviewStatus::ChangesRequested
+            } else if approvals > 0 {
+                ReviewStatus::Approved
+            } else if !pull.requested_reviewers.is_empty() {
+                ReviewStatus::ReviewRequested
+            } else {
+                ReviewStatus::Pending
+            };
+
+            Ok(PullRequest {
+                number: pull.number,
+                title: pull.title,
+                author: pull.user.map(|user| user.login).unwrap_or_default(),
+                html_url: pull.html_url,
+                draft: pull.draft,
+                review_status,
+                approvals,
+                created_at: pull.created_at,
+                updated_at: pull.updated_at,
+            })
+        })
+        .collect()
+}
+
+async fn fetch_issues(state: &AppState, repo: &str, labels: &str, limit: usize) -> Result<Vec<Issue>> {
+    #[derive(Deserialize)]
+    struct ApiLabel {
+        name: String,
+    }
+
+    #[derive(Deserialize)]
+    struct ApiIssue {
+        number: u64,
+        title: String,
+        user: Option<ApiUser>,
+        html_url: String,
+        #[serde(default)]
+        labels: Vec<ApiLabel>,
+        comments: u64,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    }
+
+    let mut url = format!("{}/repos/{}/issues?state=open&sort=updated&direction=desc", GITHUB_API_URL, repo);
+    if !labels.is_empty() {
+        url.push_str(&format!("&labels={}", url::form_urlencoded::byte_serialize(labels.as_bytes()).collect::<String>()));
+    }
+
+    // The issues endpoint lists pull requests too
+    let items = fetch_pages_where(state, &url, limit, |item| item.get("pull_request").is_none()).await?;
+
+    items
+        .into_iter()
+        .map(|item| {
+            let issue: ApiIssue = parse_body(item)?;
+            Ok(Issue {
+                number: issue.number,
+                title: issue.title,
+                author: issue.user.map(|user| user.login).unwrap_or_default(),
+                html_url: issue.html_url,
+                labels: issue.labels.into_iter().map(|label| label.name).collect(),
+                comments: issue.comments,
+                created_at: issue.created_at,
+                updated_at: issue.updated_at,
+            })
+        })
+        .collect()
+}
+
+async fn fetch_workflow_runs(state: &AppState, repo: &str, branch: &str, limit: usize) -> Result<Vec<WorkflowRun>> {
+    #[derive(Deserialize)]
+    struct ApiRuns {
+        workflow_runs: Vec<ApiRun>,
+    }
+
+    #[derive(Deserialize)]
+    struct ApiRun {
+        id: u64,
+        name: Option<String>,
+        head_branch: Option<String>,
+        event: String,
+        status: Option<String>,
+        conclusion: Option<String>,
+        html_url: String,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    }
+
+    let mut url = format!("{}/repos/{}/actions/runs?per_page=100", GITHUB_API_URL, repo);
+    if !branch.is_empty() {
+        url.push_str(&format!("&branch={}", url::form_urlencoded::byte_serialize(branch.as_bytes()).collect::<String>()));
+    }
+
+    // One page of the newest runs is enough to find the latest per workflow and branch
+    let (body, _) = conditional_get(state, &url).await?;
+    let api: ApiRuns = parse_body(body)?;
+
+    let mut seen = HashSet::new();
+    let mut runs = Vec::new();
+    for run in api.workflow_runs {
+        let workflow = run.name.unwrap_or_default();
+        let head_branch = run.head_branch.unwrap_or_default();
+        if !seen.insert((workflow.clone(), head_branch.clone())) {
+            continue;
+        }
+
+        runs.push(WorkflowRun {
+            id: run.id,
+            workflow,
+            branch: head_branch,
+            event: run.event,
+            status: run.status.unwrap_or_default(),
+            conclusion: run.conclusion,
+            html_url: run.html_url,
+            created_at: run.created_at,
+            updated_at: run.updated_at,
+        });
+        if runs.len() == limit {
+            break;
+        }
+    }
+
+    Ok(runs)
+}
+
+/// Follow `Link: rel="next"` pages until `limit` items or `MAX_PAGES` pages have been read
+async fn fetch_pages(state: &AppState, url: &str, limit: usize) -> Result<Vec<JsonValue>> {
+    fetch_pages_where(state, url, limit, |_| true).await
+}
+
+/// Like `fetch_pages`, counting only the items `keep` accepts
+async fn fetch_pages_where(
+    state: &AppState,
+    url: &str,
+    limit: usize,
+    keep: impl Fn(&JsonValue) -> bool,
+) -> Result<Vec<JsonValue>> {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    let mut next = Some(format!("{}{}per_page=100", url, separator));
+    let mut items = Vec::new();
+
+    for _ in 0..MAX_PAGES {
+        let Some(url) = next.take() else { break };
+        let (body, next_url) = conditional_get(state, &url).await?;
+
+        match body {
+            JsonValue::Array(page) => items.extend(page.into_iter().filter(|item| keep(item))),
+            _ => return Err(AppError::ExternalApi("GitHub returned a non-list page".to_string())),
+        }
+        if items.len() >= limit {
+            items.truncate(limit);
+            break;
+        }
+        next = next_url;
+    }
+
+    Ok(items)
+}
+
+/// GET a GitHub API URL, revalidating a stored copy with `If-None-Match`.
+///
+/// Returns the JSON body and the `rel="next"` page link, if any. A 304 answer is free of rate limit,
+/// and while the quota is low a stored copy is served without asking at all.
+async fn conditional_get(state: &AppState, url: &str) -> Result<(JsonValue, Option<String>)> {
+    let cache_key = format!("github:etag:{}", url);
+    let stored = state.cache.get::<ConditionalResponse>(&cache_key).await.ok().flatten();
+    let token = state.config.github_api_token.as_deref();
+
+    if let Some(quota) = current_quota(&state.cache, token).await {
+        match stored {
+            Some(stored) if quota.is_low() => {
+                tracing::debug!("GitHub quota low ({} left), serving stored copy of {}", quota.remaining, url);
+                return Ok((stored.body, stored.next));
+            }
+            None if quota.is_exhausted() => return Err(quota.exhausted_error()),
+            _ => {}
+        }
+    }
+
+    let mut request = reqwest::Client::new()
+        .get(url)
+        .header(header::USER_AGENT, "InsightBoard")
+        .header(header::ACCEPT, "application/vnd.github+json");
+
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("token {}", token));
+    }
+    if let Some(stored) = &stored {
+        request = request.header(header::IF_NONE_MATCH, &stored.etag);
+    }
+
+    let response = request.send().await
+        .map_err(|e| AppError::ExternalApi(format!("GitHub API error: {}", e)))?;
+
+    if response.status() == StatusCode::NOT_MODIFIED {
+        if let Some(stored) = stored {
+            tracing::debug!("GitHub resource not modified: {}", url);
+            return Ok((stored.body, stored.next));
+        }
+    }
+
+    check_response(&state.cache, token, &response).await?;
+
+    let etag = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let next = response
+        .headers()
+        .get(header::LINK)
+        .and_then(|value| value.to_str().ok())
+        .and_then(next_link);
+
+    let body: JsonValue = response.json().await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse GitHub response: {}", e)))?;
+
+    if let Some(etag) = etag {
+        let stored = ConditionalResponse { etag, body, next };
+        let _ = state.cache.set(&cache_key, &stored, ETAG_CACHE_TTL).await;
+        return Ok((stored.body, stored.next));
+    }
+
+    Ok((body, next))
+}
+
+/// Record the response's quota and turn error statuses into errors
+async fn check_response(cache: &Cache, token: Option<&str>, response: &reqwest::Response) -> Result<()> {
+    record_quota(cache, token, response.headers()).await;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    if status == StatusCode::NOT_FOUND {
+        return Err(AppError::NotFound("GitHub resource not found".to_string()));
+    }
+
+    if matches!(status, StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS) {
+        if let Some(quota) = GitHubQuota::from_headers(response.headers()).filter(|quota| quota.remaining == 0) {
+            return Err(quota.exhausted_error());
+        }
+    }
+
+    Err(AppError::ExternalApi(format!("GitHub API returned status: {}", status)))
+}
+
+fn quota_key(token: Option<&str>) -> String {
+    match token {
+        // Keyed by a fingerprint so the token itself never reaches Redis
+        Some(token) => format!("github:quota:{}", &hex::encode(Sha256::digest(token.as_bytes()))[..16]),
+        None => "github:quota:anonymous".to_string(),
+    }
+}
+
+/// Store the quota reported by a GitHub response until its window resets
+pub(crate) async fn record_quota(cache: &Cache, token: Option<&str>, headers: &HeaderMap) {
+    let Some(quota) = GitHubQuota::from_headers(headers) else { return };
+
+    let ttl = (quota.reset_at - Utc::now()).num_seconds().max(0) as usize + 60;
+    let _ = cache.set(&quota_key(token), &quota, ttl).await;
+}
+
+/// The last quota seen for a token
+pub(crate) async fn current_quota(cache: &Cache, token: Option<&str>) -> Option<GitHubQuota> {
+    cache.get::<GitHubQuota>(&quota_key(token)).await.ok().flatten()
+}
+
+/// The `rel="next"` target of a `Link` header
+fn next_link(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| param.trim() == r#"rel="next""#)
+            .then(|| target.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+fn parse_body<T: serde::de::DeserializeOwned>(body: JsonValue) -> Result<T> {
+    serde_json::from_value(body)
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse GitHub response: {}", e)))
+}
+
+/// Trimmed, de-duplicated labels joined the way the issues endpoint expects
+fn normalized_labels(labels: Option<&str>) -> String {
+    let mut unique: Vec<&str> = Vec::new();
+    for label in labels.unwrap_or_default().split(',').map(str::trim).filter(|label| !label.is_empty()) {
+        if !unique.contains(&label) {
+            unique.push(label);
+        }
+    }
+    unique.join(",")
+}
+
+/// Request a user's public events from a GitHub (or GitHub Enterprise) API root
+pub(crate) async fn request_user_events(
+    client: &reqwest::Client,
+    cache: &Cache,
+    api_url: &str,
+    username: &str,
+    token: Option<&str>,
+) -> Result<Vec<GitHubEvent>> {
+    if let Some(quota) = current_quota(cache, token).await.filter(GitHubQuota::is_exhausted) {
+        return Err(quota.exhausted_error());
+    }
+
     let mut request = client
-        .get(format!("https://api.github.com/users/{}/events/public", query.username))
+        .get(format!("{}/users/{}/events/public", api_url, username))
         .header("User-Agent", "InsightBoard");
     
-    if let Some(token) = &state.config.github_api_token {
+    if let Some(token) = token {
         request = request.header("Authorization", format!("token {}", token));
     }
     
     let response = request.send().await
-        .map_err(|e| crate::error::AppError::ExternalApi(format!("GitHub API error: {}", e)))?;
-    
-    if !response.status().is_success() {
-        return Err(crate::error::AppError::ExternalApi(
-            format!("GitHub API returned status: {}", response.status())
-        ));
-    }
-    
-    let events: Vec<GitHubEvent> = response.json().await
-        .map_err(|e| crate::error::AppError::ExternalApi(format!("Failed to parse GitHub response: {}", e)))?;
+        .map_err(|e| AppError::ExternalApi(format!("GitHub API error: {}", e)))?;
     
-    // Cache for 5 minutes
-    let _ = state.cache.set(&cache_key, &events, 300).await;
+    check_response(cache, token, &response).await.map_err(|e| match e {
+        AppError::NotFound(_) => AppError::NotFound(format!("GitHub user '{}' not found", username)),
+        e => e,
+    })?;
     
-    Ok(Json(events))
+    response.json().await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse GitHub response: {}", e)))
+}
+
+/// Validate a dashboard widget's config for the GitHub provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    match serde_path_to_error::deserialize::<_, GitHubQuery>(JsonValue::Object(config.clone())) {
+        Ok(query) => check_query(&query),
+        Err(e) => {
+            let path = e.path().to_string();
+            let field = if path == "." { "config".to_string() } else { path };
+            vec![FieldError::new(field, e.inner().to_string())]
+        }
+    }
+}
+
+fn check_query(query: &GitHubQuery) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    match query.mode {
+        GitHubMode::Events => match query.username.as_deref().map(str::trim) {
+            None | Some("") => errors.push(FieldError::new("username", "username is required")),
+            Some(username) if !is_valid_username(username) => {
+                errors.push(FieldError::new("username", "Invalid GitHub username format"));
+            }
+            Some(_) => {}
+        },
+        mode => match query.repo.as_deref().map(str::trim) {
+            None | Some("") => {
+                errors.push(FieldError::new("repo", format!("repo is required in {} mode", mode.as_str())));
+            }
+            Some(repo) if !is_valid_repo(repo) => {
+                errors.push(FieldError::new("repo", "Repository must look like owner/name"));
+            }
+            Some(_) => {}
+        },
+    }
+
+    if query.labels.is_some() && query.mode != GitHubMode::Issues {
+        errors.push(FieldError::new("labels", "labels only apply in issues mode"));
+    }
+
+    if query.branch.is_some() && query.mode != GitHubMode::Workflows {
+        errors.push(FieldError::new("branch", "branch only applies in workflows mode"));
+    }
+
+    if query.limit.is_some_and(|limit| limit == 0 || limit > MAX_LIMIT) {
+        errors.push(FieldError::new("limit", format!("Must be between 1 and {}", MAX_LIMIT)));
+    }
+
+    errors
+}
+
+/// GitHub usernames are 1-39 alphanumerics with single, non-leading/trailing hyphens
+fn is_valid_username(username: &str) -> bool {
+    username.len() <= 39
+        && !username.starts_with('-')
+        && !username.ends_with('-')
+        && !username.contains("--")
+        && username.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+/// `owner/name`, where names are alphanumerics, `-`, `_` and `.`
+fn is_valid_repo(repo: &str) -> bool {
+    match repo.split_once('/') {
+        Some((owner, name)) => {
+            !owner.is_empty()
+                && is_valid_username(owner)
+                && !name.is_empty()
+                && name.len() <= 100
+                && name != "."
+                && name != ".."
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        }
+        None => false,
+    }
 }
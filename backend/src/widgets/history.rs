@@ -0,0 +1,165 @@
+use axum::{extract::{Path, Query, State}, response::IntoResponse, Json};
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, Result},
+    handlers::widget_data::parse_dashboard,
+    models::{
+        substitute_variables, HistoryPoint, HistoryQuery, HistoryResponse, MetricPoint, MAX_HISTORY_POINTS,
+        MIN_BUCKET_SECONDS,
+    },
+    widgets::WidgetType,
+    AppState,
+};
+
+/// Store numeric values from an upstream fetch; failures are logged, never surfaced
+pub async fn record_metrics(state: &AppState, widget_type: WidgetType, points: Vec<MetricPoint>) {
+    if points.is_empty() {
+        return;
+    }
+
+    let series: Vec<String> = points.iter().map(|point| widget_type.normalize_series(&point.series)).collect();
+    let metrics: Vec<&str> = points.iter().map(|point| point.metric).collect();
+    let values: Vec<f64> = points.iter().map(|point| point.value).collect();
+
+    let result = sqlx::query(
+        "INSERT INTO widget_metrics (widget_type, series, metric, value)
+         SELECT $1, * FROM UNNEST($2::text[], $3::text[], $4::float8[])"
+    )
+    .bind(widget_type.as_str())
+    .bind(&series)
+    .bind(&metrics)
+    .bind(&values)
+    .execute(state.db.pool())
+    .await;
+
+    if let Err(e) = result {
+        tracing::warn!("Failed to record {} metrics: {:?}", widget_type.as_str(), e);
+    }
+}
+
+/// Serve a downsampled range of recorded values (min/max/avg per bucket)
+pub async fn fetch_history(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Path(widget_type): Path<WidgetType>,
+    Query(mut query): Query<HistoryQuery>,
+) -> Result<impl IntoResponse> {
+    query.series = widget_type.normalize_series(&query.series);
+
+    let default_metric = widget_type.history_metric().ok_or_else(|| {
+        AppError::Validation(format!("No history is recorded for {} widgets", widget_type.as_str()))
+    })?;
+
+    let metric = query.metric.unwrap_or_else(|| default_metric.to_string());
+    if metric != default_metric {
+        return Err(AppError::Validation(format!(
+            "Unknown metric '{}' for {} widgets",
+            metric,
+            widget_type.as_str()
+        )));
+    }
+
+    // Metrics are recorded per upstream fetch, not per user, so only series the caller watches are served
+    if !series_on_dashboards(&state, user_ctx.user_id, widget_type, &query.series).await? {
+        return Err(AppError::NotFound(format!(
+            "No {} widget on your dashboards records series '{}'",
+            widget_type.as_str(),
+            query.series
+        )));
+    }
+
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query.from.unwrap_or(to - Duration::hours(24));
+    if from >= to {
+        return Err(AppError::Validation("'from' must be before 'to'".to_string()));
+    }
+
+    let span = (to - from).num_seconds();
+    let bucket_seconds = match query.bucket_seconds {
+        Some(bucket) if bucket < MIN_BUCKET_SECONDS => {
+            return Err(AppError::Validation(format!(
+                "bucket_seconds must be at least {}",
+                MIN_BUCKET_SECONDS
+            )));
+        }
+        Some(bucket) if span / bucket > MAX_HISTORY_POINTS * 10 => {
+            return Err(AppError::Validation("bucket_seconds is too small for this range".to_string()));
+        }
+        Some(bucket) => bucket,
+        None => (span / MAX_HISTORY_POINTS).max(MIN_BUCKET_SECONDS),
+    };
+
+    // Raw points and hourly rollups are merged, rollups weighted by their sample count
+    let points: Vec<HistoryPoint> = sqlx::query_as(
+        "WITH points AS (
+             SELECT recorded_at AS at, value AS min_value, value AS max_value, value AS sum_value, 1::bigint AS samples
+             FROM widget_metrics
+             WHERE widget_type = $1 AND series = $2 AND metric = $3 AND recorded_at >= $4 AND recorded_at < $5
+             UNION ALL
+             SELECT bucket_start, min_value, max_value, avg_value * sample_count, sample_count
+             FROM widget_metric_rollups
+             WHERE widget_type = $1 AND series = $2 AND metric = $3 AND bucket_start >= $4 AND bucket_start < $5
+         )
+         SELECT to_timestamp(floor(extract(epoch FROM at)::float8 / $6) * $6) AS bucket,
+                MIN(min_value) AS min,
+                MAX(max_value) AS max,
+                SUM(sum_value) / SUM(samples) AS avg,
+                SUM(samples)::bigint AS samples
+         FROM points
+         GROUP BY 1
+         ORDER BY 1"
+    )
+    .bind(widget_type.as_str())
+    .bind(&query.series)
+    .bind(&metric)
+    .bind(from)
+    .bind(to)
+    .bind(bucket_seconds as f64)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    Ok(Json(HistoryResponse {
+        widget_type,
+        series: query.series,
+        metric,
+        from,
+        to,
+        bucket_seconds,
+        points,
+    }))
+}
+
+/// Whether one of the user's dashboards has a widget of this type recording the series.
+///
+/// Variables take their default values, as when the dashboard is opened without overrides.
+async fn series_on_dashboards(state: &AppState, user_id: Uuid, widget_type: WidgetType, series: &str) -> Result<bool> {
+    let dashboards: Vec<(serde_json::Value, serde_json::Value)> = sqlx::query_as(
+        "SELECT layout_json, settings_json FROM dashboards WHERE user_id = $1 AND deleted_at IS NULL"
+    )
+    .bind(user_id)
+    .fetch_all(state.db.pool())
+    .await?;
+
+    let no_overrides = HashMap::new();
+    for (layout_json, settings_json) in dashboards {
+        let Ok((layout, variables)) = parse_dashboard(layout_json, settings_json, &no_overrides) else {
+            continue;
+        };
+
+        let found = layout
+            .widgets
+            .iter()
+            .filter(|widget| widget.widget_type == widget_type)
+            .flat_map(|widget| widget_type.history_series(&substitute_variables(&widget.config, &variables)))
+            .any(|candidate| candidate == series);
+        if found {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
@@ -0,0 +1,261 @@
+use axum::{extract::State, response::IntoResponse, Json};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use serde_json_path::JsonPath;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::{
+    auth::UserCtx,
+    egress::{guarded_client, read_limited, MAX_RESPONSE_BYTES},
+    error::{AppError, FieldError, Result},
+    widgets::WidgetConfig,
+    AppState,
+};
+
+/// Default and largest request timeouts
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+const MAX_TIMEOUT_MS: u64 = 15_000;
+
+/// Default, smallest and largest cache lifetimes
+const DEFAULT_CACHE_TTL: usize = 60;
+const MIN_CACHE_TTL: usize = 10;
+const MAX_CACHE_TTL: usize = 3_600;
+
+/// Most list items or table rows returned
+const MAX_ITEMS: usize = 500;
+
+/// A user-configured request and how to extract data from its JSON response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpQuery {
+    pub url: String,
+    #[serde(default)]
+    pub method: HttpMethod,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// JSON body (or raw text when a string) for POST requests
+    #[serde(default)]
+    pub body: Option<JsonValue>,
+    #[serde(default)]
+    pub output: HttpOutput,
+    /// JSONPath selecting the value (scalar), the items (list) or the rows (table)
+    pub path: String,
+    /// Table columns, each a JSONPath evaluated against a row
+    #[serde(default)]
+    pub columns: Vec<HttpColumn>,
+    pub timeout_ms: Option<u64>,
+    pub cache_ttl: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    #[default]
+    Get,
+    Post,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpOutput {
+    /// First match of `path`
+    #[default]
+    Scalar,
+    /// Every match of `path`
+    List,
+    /// One row per match of `path`, one column per entry of `columns`
+    Table,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HttpColumn {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum HttpData {
+    Table { columns: Vec<String>, rows: Vec<Vec<JsonValue>> },
+    List { values: Vec<JsonValue> },
+    Scalar { value: JsonValue },
+}
+
+/// The config carries headers and a body, so this endpoint takes it as a JSON body
+pub async fn fetch_http_data(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(query): Json<HttpQuery>,
+) -> Result<impl IntoResponse> {
+    let errors = check_query(&query);
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    Ok(Json(fetch_http(&state, &query).await?))
+}
+
+/// Call the configured URL and extract the configured output (cached)
+pub async fn fetch_http(state: &AppState, query: &HttpQuery) -> Result<HttpData> {
+    // Headers may hold credentials, so the key is a digest of the whole query
+    let serialized = serde_json::to_vec(query)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize HTTP widget query: {}", e)))?;
+    let cache_key = format!("http:{}", hex::encode(Sha256::digest(&serialized)));
+
+    // Check cache first
+    if let Some(cached) = state.cache.get::<HttpData>(&cache_key).await.ok().flatten() {
+        tracing::debug!("Cache hit for HTTP widget: {}", query.url);
+        return Ok(cached);
+    }
+
+    let url = url::Url::parse(&query.url)
+        .map_err(|e| AppError::Validation(format!("Invalid URL: {}", e)))?;
+    let timeout = Duration::from_millis(query.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+    let client = guarded_client(&state.config, &url, timeout).await?;
+
+    let mut request = match query.method {
+        HttpMethod::Get => client.get(url),
+        HttpMethod::Post => client.post(url),
+    };
+    for (name, value) in &query.headers {
+        request = request.header(name, value);
+    }
+    request = match &query.body {
+        Some(JsonValue::String(text)) => request.body(text.clone()),
+        Some(body) => request.json(body),
+        None => request,
+    };
+
+    let response = request.send().await
+        .map_err(|e| AppError::ExternalApi(format!("HTTP widget request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(
+            format!("{} returned status: {}", query.url, response.status())
+        ));
+    }
+
+    let body = read_limited(response, MAX_RESPONSE_BYTES).await?;
+    let json: JsonValue = serde_json::from_slice(&body)
+        .map_err(|e| AppError::ExternalApi(format!("Response is not JSON: {}", e)))?;
+
+    let data = extract(query, &json)?;
+
+    let ttl = query.cache_ttl.unwrap_or(DEFAULT_CACHE_TTL);
+    let _ = state.cache.set(&cache_key, &data, ttl).await;
+
+    Ok(data)
+}
+
+/// Apply the query's JSONPath expressions to a response
+fn extract(query: &HttpQuery, json: &JsonValue) -> Result<HttpData> {
+    let path = parse_path(&query.path).map_err(AppError::Validation)?;
+    let nodes = path.query(json).all();
+
+    let data = match query.output {
+        HttpOutput::Scalar => HttpData::Scalar {
+            value: nodes.first().map(|value| (*value).clone()).unwrap_or(JsonValue::Null),
+        },
+        HttpOutput::List => HttpData::List {
+            values: nodes.into_iter().take(MAX_ITEMS).cloned().collect(),
+        },
+        HttpOutput::Table => {
+            let columns = query
+                .columns
+                .iter()
+                .map(|column| parse_path(&column.path).map_err(AppError::Validation))
+                .collect::<Result<Vec<_>>>()?;
+
+            HttpData::Table {
+                columns: query.columns.iter().map(|column| column.name.clone()).collect(),
+                rows: nodes
+                    .into_iter()
+                    .take(MAX_ITEMS)
+                    .map(|row| {
+                        columns
+                            .iter()
+                            .map(|column| column.query(row).first().cloned().unwrap_or(JsonValue::Null))
+                            .collect()
+                    })
+                    .collect(),
+            }
+        }
+    };
+
+    Ok(data)
+}
+
+fn parse_path(path: &str) -> std::result::Result<JsonPath, String> {
+    JsonPath::parse(path).map_err(|e| format!("Invalid JSONPath '{}': {}", path, e))
+}
+
+/// Validate a dashboard widget's config for the generic HTTP provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    match serde_path_to_error::deserialize::<_, HttpQuery>(JsonValue::Object(config.clone())) {
+        Ok(query) => check_query(&query),
+        Err(e) => {
+            let path = e.path().to_string();
+            let field = if path == "." { "config".to_string() } else { path };
+            vec![FieldError::new(field, e.inner().to_string())]
+        }
+    }
+}
+
+fn check_query(query: &HttpQuery) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    match url::Url::parse(&query.url) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.host().is_some() => {}
+        _ => errors.push(FieldError::new("url", "Must be an http(s) URL")),
+    }
+
+    for name in query.headers.keys() {
+        if reqwest::header::HeaderName::from_bytes(name.as_bytes()).is_err() {
+            errors.push(FieldError::new("headers", format!("Invalid header name: {}", name)));
+        }
+    }
+
+    if query.body.is_some() && matches!(query.method, HttpMethod::Get) {
+        errors.push(FieldError::new("body", "A body can only be sent with POST"));
+    }
+
+    if let Err(message) = parse_path(&query.path) {
+        errors.push(FieldError::new("path", message));
+    }
+
+    match query.output {
+        HttpOutput::Table if query.columns.is_empty() => {
+            errors.push(FieldError::new("columns", "Table output needs at least one column"));
+        }
+        HttpOutput::Table => {
+            for (index, column) in query.columns.iter().enumerate() {
+                if let Err(message) = parse_path(&column.path) {
+                    errors.push(FieldError::new(format!("columns[{}].path", index), message));
+                }
+            }
+        }
+        HttpOutput::Scalar | HttpOutput::List if !query.columns.is_empty() => {
+            errors.push(FieldError::new("columns", "Columns are only used by table output"));
+        }
+        HttpOutput::Scalar | HttpOutput::List => {}
+    }
+
+    if query.timeout_ms.is_some_and(|timeout| timeout == 0 || timeout > MAX_TIMEOUT_MS) {
+        errors.push(FieldError::new(
+            "timeout_ms",
+            format!("Must be between 1 and {}", MAX_TIMEOUT_MS),
+        ));
+    }
+
+    if query.cache_ttl.is_some_and(|ttl| !(MIN_CACHE_TTL..=MAX_CACHE_TTL).contains(&ttl)) {
+        errors.push(FieldError::new(
+            "cache_ttl",
+            format!("Must be between {} and {}", MIN_CACHE_TTL, MAX_CACHE_TTL),
+        ));
+    }
+
+    errors
+}
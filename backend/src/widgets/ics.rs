@@ -0,0 +1,724 @@
+// Minimal iCalendar (RFC 5545) reader: VEVENTs with RRULE/RDATE/EXDATE expansion and
+// RECURRENCE-ID overrides. VTIMEZONE blocks are ignored; TZIDs must be IANA names.
+
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, Months, NaiveDate, NaiveDateTime, TimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use std::collections::HashSet;
+
+/// Most recurrence periods walked per event, so a bad rule can't spin forever
+const MAX_PERIODS: u32 = 50_000;
+
+/// A parsed calendar
+#[derive(Debug)]
+pub struct IcsCalendar {
+    pub name: Option<String>,
+    events: Vec<IcsEvent>,
+}
+
+/// One occurrence of an event
+#[derive(Debug, Clone)]
+pub struct Occurrence {
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub all_day: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct IcsTime {
+    local: NaiveDateTime,
+    tz: Tz,
+    all_day: bool,
+}
+
+impl IcsTime {
+    fn instant(&self) -> DateTime<Utc> {
+        resolve_local(self.tz, self.local)
+    }
+
+    fn with_local(&self, local: NaiveDateTime) -> IcsTime {
+        IcsTime { local, ..*self }
+    }
+}
+
+#[derive(Debug, Default)]
+struct IcsEvent {
+    uid: String,
+    summary: String,
+    description: Option<String>,
+    location: Option<String>,
+    start: Option<IcsTime>,
+    end: Option<IcsTime>,
+    duration: Option<Duration>,
+    rrule: Option<String>,
+    rdates: Vec<IcsTime>,
+    exdates: Vec<IcsTime>,
+    recurrence_id: Option<IcsTime>,
+    cancelled: bool,
+}
+
+struct Property<'a> {
+    name: String,
+    params: Vec<(String, &'a str)>,
+    value: &'a str,
+}
+
+impl Property<'_> {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim_matches('"'))
+    }
+}
+
+impl IcsCalendar {
+    /// Parse an ICS document; floating times (no TZID, no `Z`) use `X-WR-TIMEZONE` or `default_tz`
+    pub fn parse(text: &str, default_tz: Tz) -> Result<IcsCalendar, String> {
+        let lines = unfold(text);
+        if !lines.first().is_some_and(|line| line.eq_ignore_ascii_case("BEGIN:VCALENDAR")) {
+            return Err("Not an iCalendar document".to_string());
+        }
+
+        let default_tz = lines
+            .iter()
+            .filter_map(|line| parse_property(line))
+            .find(|property| property.name == "X-WR-TIMEZONE")
+            .and_then(|property| property.value.trim().parse::<Tz>().ok())
+            .unwrap_or(default_tz);
+
+        let mut name = None;
+        let mut events = Vec::new();
+        let mut event: Option<IcsEvent> = None;
+        // Components nested in a VEVENT (VALARM) whose properties must be ignored
+        let mut nested = 0;
+
+        for line in &lines {
+            let Some(property) = parse_property(line) else { continue };
+            let value = property.value;
+
+            match (property.name.as_str(), event.as_mut()) {
+                ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => event = Some(IcsEvent::default()),
+                ("BEGIN", Some(_)) => nested += 1,
+                ("END", Some(_)) if nested > 0 => nested -= 1,
+                ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                    events.extend(event.take().filter(|event| event.start.is_some()));
+                }
+                ("X-WR-CALNAME", None) => name = Some(unescape(value)),
+                (_, Some(_)) if nested > 0 => {}
+                (_, Some(event)) => apply_property(event, &property, default_tz),
+                _ => {}
+            }
+        }
+
+        Ok(IcsCalendar { name, events })
+    }
+
+    /// Occurrences overlapping `[from, to)`, sorted by start
+    pub fn occurrences(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<Occurrence> {
+        // Instances moved or cancelled by a RECURRENCE-ID override, keyed by UID and original start
+        let overridden: HashSet<(&str, i64)> = self
+            .events
+            .iter()
+            .filter_map(|event| {
+                event.recurrence_id.map(|id| (event.uid.as_str(), id.instant().timestamp()))
+            })
+            .collect();
+
+        let mut occurrences = Vec::new();
+
+        for event in self.events.iter().filter(|event| !event.cancelled) {
+            let Some(start) = event.start else { continue };
+            let length = event_length(event, start);
+
+            let mut starts = match (&event.rrule, event.recurrence_id) {
+                (Some(rule), None) => match RecurrenceRule::parse(rule, start) {
+                    Ok(rule) => rule.expand(start, to),
+                    Err(e) => {
+                        tracing::debug!("Skipping unsupported RRULE '{}': {}", rule, e);
+                        vec![start]
+                    }
+                },
+                _ => vec![start],
+            };
+            if event.recurrence_id.is_none() {
+                starts.extend(event.rdates.iter().copied());
+            }
+
+            let excluded: HashSet<i64> = event.exdates.iter().map(|time| time.instant().timestamp()).collect();
+            let mut seen = HashSet::new();
+
+            for instance in starts {
+                let key = instance.instant().timestamp();
+                if !seen.insert(key) || excluded.contains(&key) {
+                    continue;
+                }
+                if event.recurrence_id.is_none() && overridden.contains(&(event.uid.as_str(), key)) {
+                    continue;
+                }
+
+                // A DURATION can push the end past the representable range; such an event is unusable
+                let Some(end_local) = instance.local.checked_add_signed(length) else {
+                    tracing::debug!("Skipping event '{}': its end is out of range", event.uid);
+                    continue;
+                };
+                let start = instance.instant();
+                let end = instance.with_local(end_local).instant();
+                let overlaps = if end > start { end > from && start < to } else { start >= from && start < to };
+
+                if overlaps {
+                    occurrences.push(Occurrence {
+                        summary: event.summary.clone(),
+                        description: event.description.clone(),
+                        location: event.location.clone(),
+                        start,
+                        end,
+                        all_day: instance.all_day,
+                    });
+                }
+            }
+        }
+
+        occurrences.sort_by_key(|occurrence| occurrence.start);
+        occurrences
+    }
+}
+
+fn apply_property(event: &mut IcsEvent, property: &Property, default_tz: Tz) {
+    let value = property.value;
+    match property.name.as_str() {
+        "UID" => event.uid = value.to_string(),
+        "SUMMARY" => event.summary = unescape(value),
+        "DESCRIPTION" => event.description = Some(unescape(value)).filter(|text| !text.is_empty()),
+        "LOCATION" => event.location = Some(unescape(value)).filter(|text| !text.is_empty()),
+        "DTSTART" => event.start = parse_time(value, property, default_tz),
+        "DTEND" => event.end = parse_time(value, property, default_tz),
+        "DURATION" => event.duration = parse_duration(value),
+        "RRULE" => event.rrule = Some(value.to_string()),
+        "RDATE" => event.rdates.extend(parse_times(value, property, default_tz)),
+        "EXDATE" => event.exdates.extend(parse_times(value, property, default_tz)),
+        "RECURRENCE-ID" => event.recurrence_id = parse_time(value, property, default_tz),
+        "STATUS" => event.cancelled = value.eq_ignore_ascii_case("CANCELLED"),
+        _ => {}
+    }
+}
+
+/// Length of every occurrence: DTEND - DTSTART, DURATION, or a day for all-day events
+fn event_length(event: &IcsEvent, start: IcsTime) -> Duration {
+    match (event.end, event.duration) {
+        (Some(end), _) if start.all_day => end.local - start.local,
+        (Some(end), _) => end.instant() - start.instant(),
+        (None, Some(duration)) => duration,
+        (None, None) if start.all_day => Duration::days(1),
+        (None, None) => Duration::zero(),
+    }
+}
+
+/// Join folded lines (continuations start with a space or tab)
+fn unfold(text: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in text.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ if line.trim().is_empty() => {}
+            _ => lines.push(line.trim_end_matches('\r').to_string()),
+        }
+    }
+    lines
+}
+
+/// Split `NAME;PARAM=value;...:VALUE` (colons inside quoted parameters don't count)
+fn parse_property(line: &str) -> Option<Property<'_>> {
+    let mut in_quotes = false;
+    let split = line.char_indices().find(|&(_, c)| {
+        if c == '"' {
+            in_quotes = !in_quotes;
+        }
+        c == ':' && !in_quotes
+    })?;
+
+    let (head, value) = (&line[..split.0], &line[split.0 + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| param.split_once('='))
+        .map(|(key, value)| (key.trim().to_ascii_uppercase(), value))
+        .collect();
+
+    Some(Property { name, params, value })
+}
+
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match (c, c == '\\') {
+            (_, true) => match chars.next() {
+                Some('n' | 'N') => output.push('\n'),
+                Some(other) => output.push(other),
+                None => {}
+            },
+            (c, false) => output.push(c),
+        }
+    }
+    output.trim().to_string()
+}
+
+fn parse_times(value: &str, property: &Property, default_tz: Tz) -> Vec<IcsTime> {
+    value
+        .split(',')
+        .filter_map(|value| parse_time(value, property, default_tz))
+        .collect()
+}
+
+/// Parse a DATE (`20250101`), UTC (`20250101T090000Z`) or local date-time value
+fn parse_time(value: &str, property: &Property, default_tz: Tz) -> Option<IcsTime> {
+    let value = value.trim();
+
+    if property.param("VALUE").is_some_and(|kind| kind.eq_ignore_ascii_case("DATE")) || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some(IcsTime { local: date.and_hms_opt(0, 0, 0)?, tz: default_tz, all_day: true });
+    }
+
+    if let Some(utc) = value.strip_suffix(['Z', 'z']) {
+        let local = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(IcsTime { local, tz: Tz::UTC, all_day: false });
+    }
+
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let tz = property
+        .param("TZID")
+        .and_then(|tzid| tzid.trim_start_matches('/').parse::<Tz>().ok())
+        .unwrap_or(default_tz);
+
+    Some(IcsTime { local, tz, all_day: false })
+}
+
+/// Parse a DURATION like `PT1H30M`, `P1D` or `P2W`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in value.strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+                let part = match unit {
+                    'W' => Duration::try_weeks(amount),
+                    'D' => Duration::try_days(amount),
+                    'H' => Duration::try_hours(amount),
+                    'M' => Duration::try_minutes(amount),
+                    'S' => Duration::try_seconds(amount),
+                    _ => None,
+                }?;
+                total = total.checked_add(&part)?;
+            }
+        }
+    }
+
+    Some(if negative { -total } else { total })
+}
+
+/// Convert a local time to UTC; times skipped by a DST change move forward an hour
+fn resolve_local(tz: Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    match tz.from_local_datetime(&local) {
+        LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => time.with_timezone(&Utc),
+        LocalResult::None => local
+            .checked_add_signed(Duration::hours(1))
+            .and_then(|local| tz.from_local_datetime(&local).earliest())
+            .map(|time| time.with_timezone(&Utc))
+            .unwrap_or_else(|| Utc.from_utc_datetime(&local)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of RRULE supported: FREQ (daily to yearly), INTERVAL, COUNT, UNTIL,
+/// BYDAY (with ordinals), BYMONTHDAY, BYMONTH, BYSETPOS and WKST
+#[derive(Debug)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<IcsTime>,
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+    by_set_pos: Vec<i32>,
+    week_start: Weekday,
+}
+
+impl RecurrenceRule {
+    fn parse(rule: &str, start: IcsTime) -> Result<RecurrenceRule, String> {
+        let mut frequency = None;
+        let mut parsed = RecurrenceRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+
+        let number = |value: &str| value.parse::<i32>().map_err(|_| format!("invalid number '{}'", value));
+        let numbers = |value: &str| value.split(',').map(number).collect::<Result<Vec<_>, _>>();
+
+        for part in rule.split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part.split_once('=').ok_or_else(|| format!("invalid part '{}'", part))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        other => return Err(format!("unsupported FREQ {}", other)),
+                    })
+                }
+                "INTERVAL" => parsed.interval = number(value)?.max(1) as u32,
+                "COUNT" => parsed.count = Some(number(value)?.max(0) as u32),
+                "UNTIL" => {
+                    let property = Property { name: "UNTIL".to_string(), params: Vec::new(), value };
+                    parsed.until = parse_time(value, &property, start.tz);
+                }
+                "BYDAY" => {
+                    parsed.by_day = value
+                        .split(',')
+                        .map(|day| {
+                            // The weekday is the last two characters; anything before is the ordinal
+                            let split = day.char_indices().rev().nth(1).map_or(0, |(index, _)| index);
+                            let weekday = parse_weekday(&day[split..])?;
+                            let ordinal = match &day[..split] {
+                                "" | "+" => None,
+                                ordinal => Some(number(ordinal.trim_start_matches('+'))?),
+                            };
+                            Ok((ordinal, weekday))
+                        })
+                        .collect::<Result<_, String>>()?
+                }
+                "BYMONTHDAY" => parsed.by_month_day = numbers(value)?,
+                "BYMONTH" => parsed.by_month = numbers(value)?.into_iter().map(|month| month as u32).collect(),
+                "BYSETPOS" => parsed.by_set_pos = numbers(value)?,
+                "WKST" => parsed.week_start = parse_weekday(value)?,
+                // BYHOUR, BYMINUTE and friends are rare in calendar feeds; occurrences keep DTSTART's time
+                _ => {}
+            }
+        }
+
+        parsed.frequency = frequency.ok_or("missing FREQ")?;
+        Ok(parsed)
+    }
+
+    /// Every occurrence start from DTSTART until `to` (or COUNT/UNTIL)
+    fn expand(&self, start: IcsTime, to: DateTime<Utc>) -> Vec<IcsTime> {
+        let mut instances = vec![start];
+        let time = start.local.time();
+        let first_day = start.local.date();
+
+        for period in 0..MAX_PERIODS {
+            let Some(step) = period.checked_mul(self.interval) else { break };
+            let Some((period_start, days)) = self.period(first_day, step) else { break };
+
+            if start.with_local(period_start.and_time(time)).instant() >= to {
+                break;
+            }
+
+            for day in days {
+                let instance = start.with_local(day.and_time(time));
+                if instance.local <= start.local {
+                    continue;
+                }
+                if self.count.is_some_and(|count| instances.len() as u32 >= count) {
+                    return instances;
+                }
+                if self.until.is_some_and(|until| past_until(instance, until)) {
+                    return instances;
+                }
+                if instance.instant() >= to {
+                    return instances;
+                }
+                instances.push(instance);
+            }
+        }
+
+        instances
+    }
+
+    /// First day of the `step`-th period after DTSTART's and the rule's days within it
+    fn period(&self, first_day: NaiveDate, step: u32) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+        let (period_start, mut days) = match self.frequency {
+            Frequency::Daily => {
+                let day = first_day.checked_add_signed(Duration::days(i64::from(step)))?;
+                let matches = (self.by_month.is_empty() || self.by_month.contains(&day.month()))
+                    && (self.by_month_day.is_empty() || month_day_matches(day, &self.by_month_day))
+                    && (self.by_day.is_empty() || self.by_day.iter().any(|(_, weekday)| *weekday == day.weekday()));
+                (day, if matches { vec![day] } else { Vec::new() })
+            }
+            Frequency::Weekly => {
+                let offset = (7 + first_day.weekday().num_days_from_monday()
+                    - self.week_start.num_days_from_monday())
+                    % 7;
+                let week = first_day
+                    .checked_sub_signed(Duration::days(i64::from(offset)))?
+                    .checked_add_signed(Duration::weeks(i64::from(step)))?;
+                let days = (0..7)
+                    .filter_map(|day| week.checked_add_signed(Duration::days(day)))
+                    .filter(|day| match self.by_day.is_empty() {
+                        true => day.weekday() == first_day.weekday(),
+                        false => self.by_day.iter().any(|(_, weekday)| *weekday == day.weekday()),
+                    })
+                    .filter(|day| self.by_month.is_empty() || self.by_month.contains(&day.month()))
+                    .collect();
+                (week, days)
+            }
+            Frequency::Monthly => {
+                let month = first_day.with_day(1)?.checked_add_months(Months::new(step))?;
+                let days = if self.by_month.is_empty() || self.by_month.contains(&month.month()) {
+                    self.days_in_month(month, first_day.day())
+                } else {
+                    Vec::new()
+                };
+                (month, days)
+            }
+            Frequency::Yearly => {
+                let year = NaiveDate::from_ymd_opt(first_day.year().checked_add(i32::try_from(step).ok()?)?, 1, 1)?;
+                let days = if !self.by_month.is_empty() {
+                    self.by_month
+                        .iter()
+                        .filter_map(|&month| NaiveDate::from_ymd_opt(year.year(), month, 1))
+                        .flat_map(|month| self.days_in_month(month, first_day.day()))
+                        .collect()
+                } else if !self.by_month_day.is_empty() {
+                    (1..=12)
+                        .filter_map(|month| NaiveDate::from_ymd_opt(year.year(), month, 1))
+                        .flat_map(|month| self.days_in_month(month, first_day.day()))
+                        .collect()
+                } else if !self.by_day.is_empty() {
+                    let last = NaiveDate::from_ymd_opt(year.year(), 12, 31)?;
+                    self.by_day
+                        .iter()
+                        .flat_map(|&(ordinal, weekday)| weekdays_between(year, last, weekday, ordinal))
+                        .collect()
+                } else {
+                    NaiveDate::from_ymd_opt(year.year(), first_day.month(), first_day.day())
+                        .into_iter()
+                        .collect()
+                };
+                (year, days)
+            }
+        };
+
+        days.sort();
+        days.dedup();
+        Some((period_start, apply_set_pos(days, &self.by_set_pos)))
+    }
+
+    /// Days of one month selected by BYMONTHDAY/BYDAY, or DTSTART's day of month
+    fn days_in_month(&self, month: NaiveDate, default_day: u32) -> Vec<NaiveDate> {
+        let Some(last) = month.checked_add_months(Months::new(1)).map(|next| next - Duration::days(1)) else {
+            return Vec::new();
+        };
+
+        if !self.by_month_day.is_empty() {
+            return self
+                .by_month_day
+                .iter()
+                .filter_map(|&day| resolve_month_day(month, last, day))
+                .filter(|day| {
+                    self.by_day.is_empty() || self.by_day.iter().any(|(_, weekday)| *weekday == day.weekday())
+                })
+                .collect();
+        }
+
+        if !self.by_day.is_empty() {
+            return self
+                .by_day
+                .iter()
+                .flat_map(|&(ordinal, weekday)| weekdays_between(month, last, weekday, ordinal))
+                .collect();
+        }
+
+        month.with_day(default_day).into_iter().collect()
+    }
+}
+
+fn parse_weekday(value: &str) -> Result<Weekday, String> {
+    match value.to_ascii_uppercase().as_str() {
+        "MO" => Ok(Weekday::Mon),
+        "TU" => Ok(Weekday::Tue),
+        "WE" => Ok(Weekday::Wed),
+        "TH" => Ok(Weekday::Thu),
+        "FR" => Ok(Weekday::Fri),
+        "SA" => Ok(Weekday::Sat),
+        "SU" => Ok(Weekday::Sun),
+        other => Err(format!("invalid weekday '{}'", other)),
+    }
+}
+
+/// Whether an occurrence falls after UNTIL (dates compare by day, times by instant)
+fn past_until(instance: IcsTime, until: IcsTime) -> bool {
+    if until.all_day {
+        instance.local.date() > until.local.date()
+    } else {
+        instance.instant() > until.instant()
+    }
+}
+
+/// BYMONTHDAY values count from the end of the month when negative
+fn resolve_month_day(first: NaiveDate, last: NaiveDate, day: i32) -> Option<NaiveDate> {
+    match day {
+        1..=31 => first.with_day(day as u32).filter(|date| *date <= last),
+        -31..=-1 => last.checked_sub_signed(Duration::days(i64::from(-day - 1))).filter(|date| *date >= first),
+        _ => None,
+    }
+}
+
+fn month_day_matches(day: NaiveDate, by_month_day: &[i32]) -> bool {
+    let first = day.with_day(1).unwrap_or(day);
+    let last = first
+        .checked_add_months(Months::new(1))
+        .map(|next| next - Duration::days(1))
+        .unwrap_or(day);
+    by_month_day
+        .iter()
+        .any(|&month_day| resolve_month_day(first, last, month_day) == Some(day))
+}
+
+/// Every `weekday` between two dates, or only the `ordinal`-th (negative counts from the end)
+fn weekdays_between(first: NaiveDate, last: NaiveDate, weekday: Weekday, ordinal: Option<i32>) -> Vec<NaiveDate> {
+    let offset = (7 + weekday.num_days_from_monday() - first.weekday().num_days_from_monday()) % 7;
+    let days: Vec<NaiveDate> = (0..)
+        .map_while(|week| first.checked_add_signed(Duration::days(i64::from(offset) + 7 * week)))
+        .take_while(|day| *day <= last)
+        .collect();
+
+    match ordinal {
+        None => days,
+        Some(ordinal) => pick_position(&days, ordinal).into_iter().collect(),
+    }
+}
+
+/// BYSETPOS keeps only the given positions (1-based, negative from the end) of a period's days
+fn apply_set_pos(days: Vec<NaiveDate>, by_set_pos: &[i32]) -> Vec<NaiveDate> {
+    if by_set_pos.is_empty() {
+        return days;
+    }
+
+    let mut picked: Vec<NaiveDate> = by_set_pos
+        .iter()
+        .filter_map(|&position| pick_position(&days, position))
+        .collect();
+    picked.sort();
+    picked.dedup();
+    picked
+}
+
+fn pick_position(days: &[NaiveDate], position: i32) -> Option<NaiveDate> {
+    let index = match position {
+        1.. => position as usize - 1,
+        ..=-1 => days.len().checked_sub(position.unsigned_abs() as usize)?,
+        0 => return None,
+    };
+    days.get(index).copied()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_event(event: &str) -> IcsCalendar {
+        let text = format!("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:1\r\nSUMMARY:Standup\r\n{}\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n", event);
+        IcsCalendar::parse(&text, Tz::UTC).unwrap()
+    }
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text).unwrap().with_timezone(&Utc)
+    }
+
+    fn starts(calendar: &IcsCalendar, from: &str, to: &str) -> Vec<DateTime<Utc>> {
+        calendar.occurrences(utc(from), utc(to)).into_iter().map(|occurrence| occurrence.start).collect()
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("PT1H30M"), Some(Duration::minutes(90)));
+        assert_eq!(parse_duration("P1D"), Some(Duration::days(1)));
+        assert_eq!(parse_duration("-P2W"), Some(-Duration::weeks(2)));
+        assert_eq!(parse_duration("P1Y"), None);
+        assert_eq!(parse_duration("PT"), Some(Duration::zero()));
+    }
+
+    #[test]
+    fn out_of_range_durations_are_rejected() {
+        assert_eq!(parse_duration("P9999999999999W"), None);
+        assert_eq!(parse_duration("PT99999999999999999S"), None);
+        assert_eq!(parse_duration("P100000000000DT100000000000D"), None);
+    }
+
+    #[test]
+    fn event_ending_out_of_range_is_skipped() {
+        let calendar = parse_event("DTSTART:20250101T090000Z\r\nDURATION:P15000000000W");
+        assert!(calendar.occurrences(utc("2025-01-01T00:00:00Z"), utc("2025-01-02T00:00:00Z")).is_empty());
+
+        let calendar = parse_event("DTSTART:20250101T090000Z\r\nDURATION:PT30M");
+        assert_eq!(starts(&calendar, "2025-01-01T00:00:00Z", "2025-01-02T00:00:00Z"), vec![utc("2025-01-01T09:00:00Z")]);
+    }
+
+    #[test]
+    fn non_ascii_byday_is_rejected() {
+        let start = IcsTime { local: utc("2025-01-01T09:00:00Z").naive_utc(), tz: Tz::UTC, all_day: false };
+        for by_day in ["éa", "1é", "+é", "é"] {
+            let rule = format!("FREQ=WEEKLY;BYDAY={}", by_day);
+            assert!(RecurrenceRule::parse(&rule, start).is_err(), "{} should be rejected", by_day);
+        }
+
+        // The event still shows its first occurrence
+        let calendar = parse_event("DTSTART:20250101T090000Z\r\nRRULE:FREQ=WEEKLY;BYDAY=éa");
+        assert_eq!(starts(&calendar, "2025-01-01T00:00:00Z", "2025-02-01T00:00:00Z"), vec![utc("2025-01-01T09:00:00Z")]);
+    }
+
+    #[test]
+    fn huge_interval_stops_expansion() {
+        for frequency in ["DAILY", "WEEKLY", "MONTHLY", "YEARLY"] {
+            let calendar = parse_event(&format!("DTSTART:20250101T090000Z\r\nRRULE:FREQ={};INTERVAL=2147483647", frequency));
+            assert_eq!(starts(&calendar, "2025-01-01T00:00:00Z", "9999-01-01T00:00:00Z"), vec![utc("2025-01-01T09:00:00Z")]);
+        }
+    }
+
+    #[test]
+    fn expands_monthly_rule_with_ordinal_days() {
+        let calendar = parse_event("DTSTART:20250106T090000Z\r\nRRULE:FREQ=MONTHLY;BYDAY=+1MO,-1FR;COUNT=4");
+        assert_eq!(
+            starts(&calendar, "2025-01-01T00:00:00Z", "2026-01-01T00:00:00Z"),
+            vec![
+                utc("2025-01-06T09:00:00Z"),
+                utc("2025-01-31T09:00:00Z"),
+                utc("2025-02-03T09:00:00Z"),
+                utc("2025-02-28T09:00:00Z"),
+            ]
+        );
+    }
+}
@@ -1,11 +1,368 @@
 pub mod github;
+pub mod activity;
 pub mod weather;
 pub mod news;
+pub mod feed;
 pub mod crypto;
 pub mod status;
+pub mod push;
+pub mod http;
+pub mod prometheus;
+pub mod sql;
+pub mod calendar;
+mod ics;
+mod openweather;
+mod open_meteo;
+pub mod history;
 
-pub use github::*;
-pub use weather::*;
-pub use news::*;
-pub use crypto::*;
-pub use status::*;
+use futures::future::join_all;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::{
+    error::{AppError, FieldError, Result},
+    models::{substitute_variables, WidgetLayout},
+    AppState,
+};
+
+/// Widget config object as stored in a dashboard layout
+pub type WidgetConfig = Map<String, JsonValue>;
+
+/// Widget types the backend knows how to serve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "text", rename_all = "lowercase")]
+pub enum WidgetType {
+    Github,
+    Weather,
+    News,
+    Crypto,
+    Status,
+    /// Data POSTed by external systems to a push source
+    Push,
+    /// User-configured HTTP request with JSONPath extraction
+    Http,
+    /// RSS/Atom feeds, as news articles
+    Feed,
+    /// PromQL instant and range queries
+    Prometheus,
+    /// Read-only SELECT against a registered data source
+    Sql,
+    /// Upcoming events from ICS feeds
+    Calendar,
+    /// Combined GitHub, GitLab and Gitea activity
+    Activity,
+}
+
+impl WidgetType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WidgetType::Github => "github",
+            WidgetType::Weather => "weather",
+            WidgetType::News => "news",
+            WidgetType::Crypto => "crypto",
+            WidgetType::Status => "status",
+            WidgetType::Push => "push",
+            WidgetType::Http => "http",
+            WidgetType::Feed => "feed",
+            WidgetType::Prometheus => "prometheus",
+            WidgetType::Sql => "sql",
+            WidgetType::Calendar => "calendar",
+            WidgetType::Activity => "activity",
+        }
+    }
+
+    /// Numeric metric recorded for history on every upstream fetch, if any
+    pub fn history_metric(&self) -> Option<&'static str> {
+        match self {
+            WidgetType::Weather => Some("temp"),
+            WidgetType::Crypto => Some("price"),
+            WidgetType::Status => Some("response_time_ms"),
+            WidgetType::Github
+            | WidgetType::News
+            | WidgetType::Feed
+            | WidgetType::Push
+            | WidgetType::Http
+            | WidgetType::Prometheus
+            | WidgetType::Sql
+            | WidgetType::Calendar
+            | WidgetType::Activity => None,
+        }
+    }
+
+    /// History series a widget config records under (symbols, URLs or a location)
+    pub fn history_series(&self, config: &WidgetConfig) -> Vec<String> {
+        let split = |list: &str| -> Vec<String> {
+            list.split(',')
+                .map(|item| self.normalize_series(item))
+                .filter(|item| !item.is_empty())
+                .collect()
+        };
+
+        match self {
+            WidgetType::Crypto => parse_config::<crypto::CryptoQuery>(config)
+                .map(|query| split(&query.symbols))
+                .unwrap_or_default(),
+            WidgetType::Status => parse_config::<status::StatusQuery>(config)
+                .map(|query| split(&query.urls))
+                .unwrap_or_default(),
+            WidgetType::Weather => parse_config::<weather::WeatherQuery>(config)
+                .map(|query| vec![self.normalize_series(&query.series())])
+                .unwrap_or_default(),
+            WidgetType::Github
+            | WidgetType::News
+            | WidgetType::Feed
+            | WidgetType::Push
+            | WidgetType::Http
+            | WidgetType::Prometheus
+            | WidgetType::Sql
+            | WidgetType::Calendar
+            | WidgetType::Activity => Vec::new(),
+        }
+    }
+
+    /// Canonical form of a history series, used both when recording and when querying
+    pub fn normalize_series(&self, series: &str) -> String {
+        match self {
+            WidgetType::Crypto => series.trim().to_uppercase(),
+            WidgetType::Weather
+            | WidgetType::Status
+            | WidgetType::Github
+            | WidgetType::News
+            | WidgetType::Feed
+            | WidgetType::Push
+            | WidgetType::Http
+            | WidgetType::Prometheus
+            | WidgetType::Sql
+            | WidgetType::Calendar
+            | WidgetType::Activity => series.trim().to_string(),
+        }
+    }
+
+    /// Numeric metrics alert rules can watch, keyed by series (symbol, URL or location)
+    pub fn alert_metrics(&self) -> &'static [&'static str] {
+        match self {
+            WidgetType::Weather => &["temp", "feels_like", "humidity"],
+            WidgetType::Crypto => &["price", "change_percentage_24h"],
+            WidgetType::Status => &["up", "response_time_ms"],
+            WidgetType::Github
+            | WidgetType::News
+            | WidgetType::Feed
+            | WidgetType::Push
+            | WidgetType::Http
+            | WidgetType::Prometheus
+            | WidgetType::Sql
+            | WidgetType::Calendar
+            | WidgetType::Activity => &[],
+        }
+    }
+
+    /// Current value of an alert metric for one series, `None` if the series isn't in the data
+    pub async fn read_metric(&self, state: &AppState, series: &str, metric: &str) -> Result<Option<f64>> {
+        let value = match self {
+            WidgetType::Crypto => {
+                let query = crypto::CryptoQuery { symbols: series.to_string() };
+                crypto::fetch_crypto_prices(state, &query)
+                    .await?
+                    .into_iter()
+                    .find(|price| price.symbol.eq_ignore_ascii_case(series))
+                    .map(|price| match metric {
+                        "change_percentage_24h" => price.change_percentage_24h,
+                        _ => price.price,
+                    })
+            }
+            WidgetType::Status => {
+                let query = status::StatusQuery { urls: series.to_string() };
+                status::fetch_status_checks(state, &query)
+                    .await?
+                    .into_iter()
+                    .next()
+                    .and_then(|check| match metric {
+                        "response_time_ms" => check.response_time_ms.map(|ms| ms as f64),
+                        _ => Some(if check.is_up() { 1.0 } else { 0.0 }),
+                    })
+            }
+            WidgetType::Weather => {
+                let query = weather::WeatherQuery::for_series(series);
+                let weather = weather::fetch_weather(state, &query).await?;
+                Some(match metric {
+                    "feels_like" => weather.feels_like,
+                    "humidity" => f64::from(weather.humidity),
+                    _ => weather.temp,
+                })
+            }
+            WidgetType::Github
+            | WidgetType::News
+            | WidgetType::Feed
+            | WidgetType::Push
+            | WidgetType::Http
+            | WidgetType::Prometheus
+            | WidgetType::Sql
+            | WidgetType::Calendar
+            | WidgetType::Activity => None,
+        };
+
+        Ok(value)
+    }
+
+    /// Validate a widget config with the widget's provider.
+    ///
+    /// Field names in the returned errors are relative to the config object.
+    pub fn validate_config(&self, config: &WidgetConfig) -> Vec<FieldError> {
+        match self {
+            WidgetType::Github => github::validate_config(config),
+            WidgetType::Weather => weather::validate_config(config),
+            WidgetType::News => news::validate_config(config),
+            WidgetType::Crypto => crypto::validate_config(config),
+            WidgetType::Status => status::validate_config(config),
+            WidgetType::Push => push::validate_config(config),
+            WidgetType::Http => http::validate_config(config),
+            WidgetType::Feed => feed::validate_config(config),
+            WidgetType::Prometheus => prometheus::validate_config(config),
+            WidgetType::Sql => sql::validate_config(config),
+            WidgetType::Calendar => calendar::validate_config(config),
+            WidgetType::Activity => activity::validate_config(config),
+        }
+    }
+
+    /// Fetch a widget's data from its provider, given a fully substituted config.
+    ///
+    /// `owner_id` is the dashboard owner; widgets reading stored data only see the owner's own.
+    pub async fn resolve(&self, state: &AppState, owner_id: Uuid, config: &WidgetConfig) -> Result<JsonValue> {
+        match self {
+            WidgetType::Github => {
+                to_json(github::fetch_github(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Weather => {
+                to_json(weather::fetch_weather(state, &parse_config(config)?).await?)
+            }
+            WidgetType::News => {
+                to_json(news::fetch_news_articles(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Crypto => {
+                to_json(crypto::fetch_crypto_prices(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Status => {
+                to_json(status::fetch_status_checks(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Push => {
+                to_json(push::fetch_push(state, owner_id, &parse_config(config)?).await?)
+            }
+            WidgetType::Http => {
+                to_json(http::fetch_http(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Feed => {
+                to_json(feed::fetch_feed_articles(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Prometheus => {
+                to_json(prometheus::fetch_prometheus(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Sql => {
+                to_json(sql::fetch_sql(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Calendar => {
+                to_json(calendar::fetch_calendar_events(state, &parse_config(config)?).await?)
+            }
+            WidgetType::Activity => {
+                to_json(activity::fetch_activity(state, owner_id, &parse_config(config)?).await?)
+            }
+        }
+    }
+}
+
+/// Data (or the error) for one widget of a dashboard
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedWidget {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub widget_type: WidgetType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Resolve one widget after substituting dashboard variable values into its config
+pub async fn resolve_widget(
+    state: &AppState,
+    owner_id: Uuid,
+    widget: &WidgetLayout,
+    variables: &HashMap<String, String>,
+) -> ResolvedWidget {
+    let config = substitute_variables(&widget.config, variables);
+    let result = widget.widget_type.resolve(state, owner_id, &config).await;
+
+    if let Err(e) = &result {
+        tracing::warn!("Failed to resolve widget {}: {}", widget.id, e);
+    }
+
+    ResolvedWidget {
+        id: widget.id.clone(),
+        widget_type: widget.widget_type,
+        // Resolved widgets end up in public snapshots and emailed reports, so never leak internals
+        error: result.as_ref().err().map(AppError::client_message),
+        data: result.ok(),
+    }
+}
+
+/// Resolve every widget of a layout concurrently; failures are reported per widget
+pub async fn resolve_widgets(
+    state: &AppState,
+    owner_id: Uuid,
+    widgets: &[WidgetLayout],
+    variables: &HashMap<String, String>,
+) -> Vec<ResolvedWidget> {
+    join_all(
+        widgets
+            .iter()
+            .map(|widget| resolve_widget(state, owner_id, widget, variables)),
+    )
+    .await
+}
+
+fn parse_config<T: DeserializeOwned>(config: &WidgetConfig) -> Result<T> {
+    serde_json::from_value(JsonValue::Object(config.clone()))
+        .map_err(|e| AppError::Validation(format!("Invalid widget config: {}", e)))
+}
+
+fn to_json<T: Serialize>(value: T) -> Result<JsonValue> {
+    serde_json::to_value(value)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize widget data: {}", e)))
+}
+
+/// Read a required, non-empty string from a widget config
+pub(crate) fn required_str<'a>(
+    config: &'a WidgetConfig,
+    key: &str,
+    errors: &mut Vec<FieldError>,
+) -> Option<&'a str> {
+    match config.get(key) {
+        Some(JsonValue::String(value)) if !value.trim().is_empty() => Some(value.trim()),
+        Some(JsonValue::String(_)) | None | Some(JsonValue::Null) => {
+            errors.push(FieldError::new(key, format!("{} is required", key)));
+            None
+        }
+        Some(_) => {
+            errors.push(FieldError::new(key, format!("{} must be a string", key)));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn crypto_series_are_recorded_and_queried_in_one_form() {
+        let config = json!({ "symbols": "btc, Eth" }).as_object().cloned().unwrap();
+
+        let series = WidgetType::Crypto.history_series(&config);
+        assert_eq!(series, vec!["BTC".to_string(), "ETH".to_string()]);
+        assert_eq!(WidgetType::Crypto.normalize_series(" eth "), "ETH");
+        assert_eq!(WidgetType::Status.normalize_series(" https://Example.com/ "), "https://Example.com/");
+    }
+}
@@ -1,7 +1,7 @@
 use axum::{extract::{Query, State}, response::IntoResponse, Json};
 use serde::{Deserialize, Serialize};
 
-use crate::{auth::UserCtx, error::{AppError, Result}, AppState};
+use crate::{auth::UserCtx, error::{AppError, FieldError, Result}, widgets::{required_str, WidgetConfig}, AppState};
 
 #[derive(Debug, Deserialize)]
 pub struct NewsQuery {
@@ -13,7 +13,7 @@ fn default_topic() -> String {
     "technology".to_string()
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewsArticle {
     pub title: String,
     pub description: Option<String>,
@@ -28,6 +28,11 @@ pub async fn fetch_news_data(
     State(state): State<AppState>,
     Query(query): Query<NewsQuery>,
 ) -> Result<impl IntoResponse> {
+    Ok(Json(fetch_news_articles(&state, &query).await?))
+}
+
+/// Fetch recent news articles for a topic (cached)
+pub async fn fetch_news_articles(state: &AppState, query: &NewsQuery) -> Result<Vec<NewsArticle>> {
     let api_key = state.config.newsapi_api_key.as_ref()
         .ok_or_else(|| AppError::Internal("NewsAPI key not configured".to_string()))?;
     
@@ -36,7 +41,7 @@ pub async fn fetch_news_data(
     // Check cache first
     if let Some(cached) = state.cache.get::<Vec<NewsArticle>>(&cache_key).await.ok().flatten() {
         tracing::debug!("Cache hit for news data: {}", query.topic);
-        return Ok(Json(cached));
+        return Ok(cached);
     }
     
     // Fetch from NewsAPI
@@ -74,5 +79,12 @@ pub async fn fetch_news_data(
     // Cache for 15 minutes
     let _ = state.cache.set(&cache_key, &articles, 900).await;
     
-    Ok(Json(articles))
+    Ok(articles)
+}
+
+/// Validate a dashboard widget's config for the news provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    required_str(config, "topic", &mut errors);
+    errors
 }
@@ -0,0 +1,276 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+
+use crate::{
+    error::{AppError, Result},
+    widgets::weather::{
+        DailyForecast, HourlyForecast, Location, WeatherData, WeatherLocation, WeatherProvider, WeatherQuery,
+        WeatherUnits, HOURLY_ENTRIES,
+    },
+};
+
+/// Open-Meteo forecast API root
+const FORECAST_API_URL: &str = "https://api.open-meteo.com/v1/forecast";
+
+/// Open-Meteo geocoding API root
+const GEOCODING_API_URL: &str = "https://geocoding-api.open-meteo.com/v1/search";
+
+/// Kelvin offset; Open-Meteo has no Kelvin unit, so `standard` is converted from °C
+const KELVIN_OFFSET: f64 = 273.15;
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResponse {
+    #[serde(default)]
+    results: Vec<GeocodingResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeocodingResult {
+    name: String,
+    latitude: f64,
+    longitude: f64,
+    country_code: Option<String>,
+    admin1: Option<String>,
+}
+
+/// Current weather and forecasts from Open-Meteo (no API key; alerts are not offered)
+pub(super) async fn fetch(query: &WeatherQuery) -> Result<WeatherData> {
+    let (lat, lon, place) = match query.location()? {
+        Location::Coordinates(lat, lon) => (lat, lon, None),
+        Location::City(search) | Location::Zip(search) => {
+            let (name, country) = match search.split_once(',') {
+                Some((name, country)) => (name.trim(), Some(country.trim())),
+                None => (search, None),
+            };
+            let place = geocode(name, country, 1)
+                .await?
+                .into_iter()
+                .next()
+                .ok_or_else(|| AppError::NotFound("Weather location not found".to_string()))?;
+            (place.latitude, place.longitude, Some(place))
+        }
+    };
+
+    let (temperature_unit, wind_speed_unit) = match query.units {
+        WeatherUnits::Imperial => ("fahrenheit", "mph"),
+        WeatherUnits::Metric | WeatherUnits::Standard => ("celsius", "ms"),
+    };
+
+    let mut params = vec![
+        ("latitude", lat.to_string()),
+        ("longitude", lon.to_string()),
+        (
+            "current",
+            "temperature_2m,apparent_temperature,relative_humidity_2m,weather_code,wind_speed_10m,is_day".to_string(),
+        ),
+        ("temperature_unit", temperature_unit.to_string()),
+        ("wind_speed_unit", wind_speed_unit.to_string()),
+        ("timeformat", "unixtime".to_string()),
+        ("timezone", "auto".to_string()),
+        ("forecast_days", if query.daily { "5" } else { "2" }.to_string()),
+    ];
+    if query.hourly {
+        params.push((
+            "hourly",
+            "temperature_2m,apparent_temperature,relative_humidity_2m,weather_code,precipitation_probability,is_day"
+                .to_string(),
+        ));
+    }
+    if query.daily {
+        params.push((
+            "daily",
+            "weather_code,temperature_2m_max,temperature_2m_min,precipitation_probability_max".to_string(),
+        ));
+    }
+
+    let response = reqwest::Client::new()
+        .get(FORECAST_API_URL)
+        .query(&params)
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalApi(format!("Open-Meteo API error: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(
+            format!("Open-Meteo API returned status: {}", response.status())
+        ));
+    }
+
+    let json: JsonValue = response.json().await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse Open-Meteo response: {}", e)))?;
+
+    let temperature = |celsius_or_fahrenheit: f64| match query.units {
+        WeatherUnits::Standard => celsius_or_fahrenheit + KELVIN_OFFSET,
+        WeatherUnits::Metric | WeatherUnits::Imperial => celsius_or_fahrenheit,
+    };
+
+    let current = &json["current"];
+    let (description, icon) = describe(
+        current["weather_code"].as_i64().unwrap_or(0),
+        current["is_day"].as_i64() != Some(0),
+    );
+
+    let mut weather_data = WeatherData {
+        temp: temperature(current["temperature_2m"].as_f64().unwrap_or(0.0)),
+        feels_like: temperature(current["apparent_temperature"].as_f64().unwrap_or(0.0)),
+        humidity: current["relative_humidity_2m"].as_i64().unwrap_or(0) as i32,
+        description: description.to_string(),
+        icon,
+        city_name: place
+            .as_ref()
+            .map(|place| place.name.clone())
+            .unwrap_or_else(|| format!("{:.2}, {:.2}", lat, lon)),
+        country: place.and_then(|place| place.country_code),
+        lat,
+        lon,
+        wind_speed: current["wind_speed_10m"].as_f64().unwrap_or(0.0),
+        units: query.units,
+        provider: WeatherProvider::OpenMeteo,
+        hourly: None,
+        daily: None,
+        // Open-Meteo has no alerts; None tells clients they are unavailable rather than absent
+        alerts: None,
+    };
+
+    if query.hourly {
+        weather_data.hourly = Some(hourly_forecast(&json["hourly"], &temperature));
+    }
+    if query.daily {
+        let offset = Duration::seconds(json["utc_offset_seconds"].as_i64().unwrap_or(0));
+        weather_data.daily = Some(daily_forecast(&json["daily"], offset, &temperature));
+    }
+
+    Ok(weather_data)
+}
+
+/// Search locations with Open-Meteo's geocoding
+pub(super) async fn search(search: &str, limit: usize) -> Result<Vec<WeatherLocation>> {
+    let (name, country) = match search.split_once(',') {
+        Some((name, country)) => (name.trim(), Some(country.trim())),
+        None => (search, None),
+    };
+
+    Ok(geocode(name, country, limit)
+        .await?
+        .into_iter()
+        .map(|place| WeatherLocation {
+            name: place.name,
+            state: place.admin1,
+            country: place.country_code.unwrap_or_default(),
+            lat: place.latitude,
+            lon: place.longitude,
+        })
+        .collect())
+}
+
+/// Look up a place or postal code, optionally within an ISO country
+async fn geocode(name: &str, country: Option<&str>, count: usize) -> Result<Vec<GeocodingResult>> {
+    let mut params = vec![
+        ("name", name.to_string()),
+        ("count", count.to_string()),
+        ("language", "en".to_string()),
+        ("format", "json".to_string()),
+    ];
+    if let Some(country) = country.filter(|country| country.len() == 2) {
+        params.push(("countryCode", country.to_uppercase()));
+    }
+
+    let response = reqwest::Client::new()
+        .get(GEOCODING_API_URL)
+        .query(&params)
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalApi(format!("Open-Meteo geocoding error: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(
+            format!("Open-Meteo geocoding returned status: {}", response.status())
+        ));
+    }
+
+    let geocoding: GeocodingResponse = response.json().await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse geocoding response: {}", e)))?;
+
+    Ok(geocoding.results)
+}
+
+/// The next day in 3-hour steps, matching the OpenWeather forecast
+fn hourly_forecast(hourly: &JsonValue, temperature: &impl Fn(f64) -> f64) -> Vec<HourlyForecast> {
+    let times = hourly["time"].as_array().cloned().unwrap_or_default();
+    let now = Utc::now();
+
+    times
+        .iter()
+        .enumerate()
+        .filter_map(|(index, time)| Some((index, DateTime::from_timestamp(time.as_i64()?, 0)?)))
+        .skip_while(|(_, time)| *time + Duration::hours(1) <= now)
+        .step_by(3)
+        .take(HOURLY_ENTRIES)
+        .map(|(index, time)| {
+            let (description, icon) = describe(
+                hourly["weather_code"][index].as_i64().unwrap_or(0),
+                hourly["is_day"][index].as_i64() != Some(0),
+            );
+            HourlyForecast {
+                time,
+                temp: temperature(hourly["temperature_2m"][index].as_f64().unwrap_or(0.0)),
+                feels_like: temperature(hourly["apparent_temperature"][index].as_f64().unwrap_or(0.0)),
+                humidity: hourly["relative_humidity_2m"][index].as_i64().unwrap_or(0) as i32,
+                description: description.to_string(),
+                icon,
+                precipitation_probability: hourly["precipitation_probability"][index].as_f64().unwrap_or(0.0) / 100.0,
+            }
+        })
+        .collect()
+}
+
+/// Daily highs and lows; times are local midnights as Unix timestamps
+fn daily_forecast(daily: &JsonValue, offset: Duration, temperature: &impl Fn(f64) -> f64) -> Vec<DailyForecast> {
+    let times = daily["time"].as_array().cloned().unwrap_or_default();
+
+    times
+        .iter()
+        .enumerate()
+        .filter_map(|(index, time)| {
+            let midnight = DateTime::from_timestamp(time.as_i64()?, 0)?;
+            let (description, icon) = describe(daily["weather_code"][index].as_i64().unwrap_or(0), true);
+            Some(DailyForecast {
+                date: (midnight + offset).date_naive(),
+                temp_min: temperature(daily["temperature_2m_min"][index].as_f64()?),
+                temp_max: temperature(daily["temperature_2m_max"][index].as_f64()?),
+                description: description.to_string(),
+                icon,
+                precipitation_probability: daily["precipitation_probability_max"][index].as_f64().unwrap_or(0.0) / 100.0,
+            })
+        })
+        .collect()
+}
+
+/// Describe a WMO weather code, with the OpenWeather icon that matches it
+fn describe(code: i64, is_day: bool) -> (&'static str, String) {
+    let (description, icon) = match code {
+        0 => ("clear sky", "01"),
+        1 => ("mainly clear", "02"),
+        2 => ("partly cloudy", "03"),
+        3 => ("overcast", "04"),
+        45 | 48 => ("fog", "50"),
+        51 | 53 | 55 => ("drizzle", "09"),
+        56 | 57 => ("freezing drizzle", "09"),
+        61 => ("light rain", "10"),
+        63 => ("moderate rain", "10"),
+        65 => ("heavy rain", "10"),
+        66 | 67 => ("freezing rain", "13"),
+        71 => ("light snow", "13"),
+        73 => ("moderate snow", "13"),
+        75 => ("heavy snow", "13"),
+        77 => ("snow grains", "13"),
+        80..=82 => ("rain showers", "09"),
+        85 | 86 => ("snow showers", "13"),
+        95 => ("thunderstorm", "11"),
+        96 | 99 => ("thunderstorm with hail", "11"),
+        _ => ("unknown", "03"),
+    };
+
+    (description, format!("{}{}", icon, if is_day { "d" } else { "n" }))
+}
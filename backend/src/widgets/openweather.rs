@@ -0,0 +1,217 @@
+use chrono::{DateTime, Duration, NaiveDate, Timelike};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+use crate::{
+    error::{AppError, Result},
+    widgets::weather::{
+        DailyForecast, HourlyForecast, Location, WeatherAlert, WeatherData, WeatherLocation, WeatherProvider,
+        WeatherQuery, WeatherUnits, HOURLY_ENTRIES,
+    },
+};
+
+/// OpenWeather API root
+const OPENWEATHER_API_URL: &str = "https://api.openweathermap.org";
+
+/// Current weather, forecasts and alerts from OpenWeather
+pub(super) async fn fetch(api_key: &str, query: &WeatherQuery) -> Result<WeatherData> {
+    let location = match query.location()? {
+        Location::City(city) => vec![("q", city.to_string())],
+        Location::Coordinates(lat, lon) => vec![("lat", lat.to_string()), ("lon", lon.to_string())],
+        Location::Zip(zip) => vec![("zip", zip.to_string())],
+    };
+
+    let current = openweather_get(api_key, "/data/2.5/weather", &location, query.units).await?;
+
+    let lat = current["coord"]["lat"].as_f64().unwrap_or_default();
+    let lon = current["coord"]["lon"].as_f64().unwrap_or_default();
+
+    let mut weather_data = WeatherData {
+        temp: current["main"]["temp"].as_f64().unwrap_or(0.0),
+        feels_like: current["main"]["feels_like"].as_f64().unwrap_or(0.0),
+        humidity: current["main"]["humidity"].as_i64().unwrap_or(0) as i32,
+        description: current["weather"][0]["description"].as_str().unwrap_or("").to_string(),
+        icon: current["weather"][0]["icon"].as_str().unwrap_or("").to_string(),
+        city_name: current["name"].as_str().map(str::to_string).unwrap_or_else(|| query.series()),
+        country: current["sys"]["country"].as_str().map(str::to_string),
+        lat,
+        lon,
+        wind_speed: current["wind"]["speed"].as_f64().unwrap_or(0.0),
+        units: query.units,
+        provider: WeatherProvider::OpenWeather,
+        hourly: None,
+        daily: None,
+        alerts: None,
+    };
+
+    if query.hourly || query.daily {
+        let forecast = openweather_get(api_key, "/data/2.5/forecast", &location, query.units).await?;
+        if query.hourly {
+            weather_data.hourly = Some(hourly_forecast(&forecast));
+        }
+        if query.daily {
+            weather_data.daily = Some(daily_forecast(&forecast));
+        }
+    }
+
+    if query.alerts {
+        weather_data.alerts = Some(fetch_alerts(api_key, lat, lon).await);
+    }
+
+    Ok(weather_data)
+}
+
+/// Search locations with OpenWeather's direct geocoding
+pub(super) async fn search(api_key: &str, search: &str, limit: usize) -> Result<Vec<WeatherLocation>> {
+    let response = reqwest::Client::new()
+        .get(format!("{}/geo/1.0/direct", OPENWEATHER_API_URL))
+        .query(&[("q", search), ("limit", &limit.to_string()), ("appid", api_key)])
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalApi(format!("OpenWeather API error: {}", e)))?;
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(
+            format!("OpenWeather geocoding returned status: {}", response.status())
+        ));
+    }
+
+    response.json().await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse geocoding response: {}", e)))
+}
+
+async fn openweather_get(
+    api_key: &str,
+    path: &str,
+    location: &[(&'static str, String)],
+    units: WeatherUnits,
+) -> Result<JsonValue> {
+    let response = reqwest::Client::new()
+        .get(format!("{}{}", OPENWEATHER_API_URL, path))
+        .query(location)
+        .query(&[("units", units.as_str()), ("appid", api_key)])
+        .send()
+        .await
+        .map_err(|e| AppError::ExternalApi(format!("OpenWeather API error: {}", e)))?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Err(AppError::NotFound("Weather location not found".to_string()));
+    }
+
+    if !response.status().is_success() {
+        return Err(AppError::ExternalApi(
+            format!("OpenWeather API returned status: {}", response.status())
+        ));
+    }
+
+    response.json().await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse weather response: {}", e)))
+}
+
+/// The next day of the 5-day/3-hour forecast
+fn hourly_forecast(forecast: &JsonValue) -> Vec<HourlyForecast> {
+    forecast_entries(forecast)
+        .take(HOURLY_ENTRIES)
+        .filter_map(|entry| {
+            Some(HourlyForecast {
+                time: DateTime::from_timestamp(entry["dt"].as_i64()?, 0)?,
+                temp: entry["main"]["temp"].as_f64()?,
+                feels_like: entry["main"]["feels_like"].as_f64().unwrap_or(0.0),
+                humidity: entry["main"]["humidity"].as_i64().unwrap_or(0) as i32,
+                description: entry["weather"][0]["description"].as_str().unwrap_or("").to_string(),
+                icon: entry["weather"][0]["icon"].as_str().unwrap_or("").to_string(),
+                precipitation_probability: entry["pop"].as_f64().unwrap_or(0.0),
+            })
+        })
+        .collect()
+}
+
+/// Fold the 3-hour forecast into days at the location, described by the entry nearest midday
+fn daily_forecast(forecast: &JsonValue) -> Vec<DailyForecast> {
+    let offset = Duration::seconds(forecast["city"]["timezone"].as_i64().unwrap_or(0));
+
+    let mut days: BTreeMap<NaiveDate, (DailyForecast, u32)> = BTreeMap::new();
+    for entry in forecast_entries(forecast) {
+        let Some(time) = entry["dt"].as_i64().and_then(|dt| DateTime::from_timestamp(dt, 0)) else {
+            continue;
+        };
+        let local = (time + offset).naive_utc();
+        let temp_min = entry["main"]["temp_min"].as_f64().unwrap_or(0.0);
+        let temp_max = entry["main"]["temp_max"].as_f64().unwrap_or(0.0);
+        let pop = entry["pop"].as_f64().unwrap_or(0.0);
+        let distance_from_noon = local.hour().abs_diff(12);
+
+        let (day, best_distance) = days.entry(local.date()).or_insert_with(|| {
+            (
+                DailyForecast {
+                    date: local.date(),
+                    temp_min,
+                    temp_max,
+                    description: String::new(),
+                    icon: String::new(),
+                    precipitation_probability: 0.0,
+                },
+                u32::MAX,
+            )
+        });
+
+        day.temp_min = day.temp_min.min(temp_min);
+        day.temp_max = day.temp_max.max(temp_max);
+        day.precipitation_probability = day.precipitation_probability.max(pop);
+        if distance_from_noon < *best_distance {
+            *best_distance = distance_from_noon;
+            day.description = entry["weather"][0]["description"].as_str().unwrap_or("").to_string();
+            day.icon = entry["weather"][0]["icon"].as_str().unwrap_or("").to_string();
+        }
+    }
+
+    days.into_values().map(|(day, _)| day).collect()
+}
+
+fn forecast_entries(forecast: &JsonValue) -> impl Iterator<Item = &JsonValue> {
+    forecast["list"].as_array().into_iter().flatten()
+}
+
+/// Alerts come from One Call 3.0, which needs its own subscription; without it there are none
+async fn fetch_alerts(api_key: &str, lat: f64, lon: f64) -> Vec<WeatherAlert> {
+    let location = [("lat", lat.to_string()), ("lon", lon.to_string())];
+    let response = reqwest::Client::new()
+        .get(format!("{}/data/3.0/onecall", OPENWEATHER_API_URL))
+        .query(&location)
+        .query(&[("exclude", "current,minutely,hourly,daily"), ("appid", api_key)])
+        .send()
+        .await;
+
+    let json: JsonValue = match response {
+        Ok(response) if response.status().is_success() => match response.json().await {
+            Ok(json) => json,
+            Err(e) => {
+                tracing::warn!("Failed to parse weather alerts: {}", e);
+                return Vec::new();
+            }
+        },
+        Ok(response) => {
+            tracing::debug!("Weather alerts unavailable: status {}", response.status());
+            return Vec::new();
+        }
+        Err(e) => {
+            tracing::warn!("Weather alerts request failed: {}", e);
+            return Vec::new();
+        }
+    };
+
+    json["alerts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|alert| {
+            Some(WeatherAlert {
+                event: alert["event"].as_str()?.to_string(),
+                sender: alert["sender_name"].as_str().unwrap_or("").to_string(),
+                start: DateTime::from_timestamp(alert["start"].as_i64()?, 0)?,
+                end: DateTime::from_timestamp(alert["end"].as_i64()?, 0)?,
+                description: alert["description"].as_str().unwrap_or("").to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,483 @@
+use axum::{extract::{Query, State}, response::IntoResponse, Json};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, FieldError, Result},
+    widgets::WidgetConfig,
+    AppState,
+};
+
+/// Timeout for each Prometheus API request
+const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Range used when a range query doesn't give one
+const DEFAULT_RANGE: &str = "1h";
+
+/// Longest range query
+const MAX_RANGE_DAYS: i64 = 90;
+
+/// Points per series aimed for when a range query doesn't give a step
+const TARGET_POINTS: i64 = 250;
+
+/// Prometheus rejects range queries with more points per series than this
+const MAX_POINTS: i64 = 11_000;
+
+/// Smallest step chosen automatically
+const MIN_AUTO_STEP_SECONDS: i64 = 15;
+
+/// How long a query result is cached
+const CACHE_TTL_SECONDS: usize = 30;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromMode {
+    /// Evaluate the expression at one instant (`/api/v1/query`)
+    #[default]
+    Instant,
+    /// Evaluate the expression over a time range (`/api/v1/query_range`)
+    Range,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromQuery {
+    /// PromQL expression
+    pub query: String,
+    #[serde(default)]
+    pub mode: PromMode,
+    /// How far back a range query reaches, e.g. `30m`, `6h` or `7d`
+    pub range: Option<String>,
+    /// Resolution of a range query, e.g. `30s` or `5m` (chosen from the range when omitted)
+    pub step: Option<String>,
+    /// Evaluation time of an instant query, or end of a range query (now when omitted)
+    pub time: Option<DateTime<Utc>>,
+}
+
+/// A sample; `value` is null for NaN and infinities, which JSON can't carry
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PromPoint {
+    pub timestamp: DateTime<Utc>,
+    pub value: Option<f64>,
+}
+
+/// One labelled series of a vector (a single point) or matrix (many points)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PromSeries {
+    pub labels: BTreeMap<String, String>,
+    pub points: Vec<PromPoint>,
+}
+
+/// A query result normalised for charts
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "result_type", rename_all = "lowercase")]
+pub enum PromResult {
+    Scalar { point: PromPoint },
+    Vector { series: Vec<PromSeries> },
+    Matrix { series: Vec<PromSeries> },
+}
+
+pub async fn fetch_prometheus_data(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Query(query): Query<PromQuery>,
+) -> Result<impl IntoResponse> {
+    Ok(Json(fetch_prometheus(&state, &query).await?))
+}
+
+/// Run a PromQL instant or range query against the configured Prometheus (cached)
+pub async fn fetch_prometheus(state: &AppState, query: &PromQuery) -> Result<PromResult> {
+    let base_url = state.config.prometheus_url.as_ref()
+        .ok_or_else(|| AppError::Internal("Prometheus URL not configured".to_string()))?;
+
+    let (endpoint, params) = request_params(query, Utc::now())?;
+
+    let cache_key = format!(
+        "prometheus:{}:{}",
+        endpoint,
+        params.iter().map(|(key, value)| format!("{}={}", key, value)).collect::<Vec<_>>().join("&")
+    );
+
+    // Check cache first
+    if let Some(cached) = state.cache.get::<PromResult>(&cache_key).await.ok().flatten() {
+        tracing::debug!("Cache hit for Prometheus query: {}", query.query);
+        return Ok(cached);
+    }
+
+    let result = query_prometheus(base_url, state.config.prometheus_token.as_deref(), endpoint, &params).await?;
+
+    let _ = state.cache.set(&cache_key, &result, CACHE_TTL_SECONDS).await;
+
+    Ok(result)
+}
+
+/// Pick the API endpoint and form parameters for a query evaluated at `now`
+fn request_params(query: &PromQuery, now: DateTime<Utc>) -> Result<(&'static str, Vec<(&'static str, String)>)> {
+    match query.mode {
+        PromMode::Instant => {
+            let mut params = vec![("query", query.query.clone())];
+            // Without a time Prometheus evaluates at its own now, which keeps the cache key stable
+            if let Some(time) = query.time {
+                params.push(("time", time.timestamp().to_string()));
+            }
+            Ok(("query", params))
+        }
+        PromMode::Range => {
+            let (range, step) = range_and_step(query).map_err(AppError::Validation)?;
+            // Align the window to the step so repeated refreshes hit the same cache entry
+            let end = query.time.unwrap_or(now).timestamp();
+            let end = end - end % step.num_seconds();
+            Ok((
+                "query_range",
+                vec![
+                    ("query", query.query.clone()),
+                    ("start", (end - range.num_seconds()).to_string()),
+                    ("end", end.to_string()),
+                    ("step", step.num_seconds().to_string()),
+                ],
+            ))
+        }
+    }
+}
+
+/// POST a query to a Prometheus API endpoint and normalise its result
+async fn query_prometheus(
+    base_url: &str,
+    token: Option<&str>,
+    endpoint: &str,
+    params: &[(&str, String)],
+) -> Result<PromResult> {
+    let url = format!("{}/api/v1/{}", base_url.trim_end_matches('/'), endpoint);
+    let mut request = reqwest::Client::new()
+        .post(&url)
+        .timeout(QUERY_TIMEOUT)
+        .form(params);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+
+    let response = request.send().await
+        .map_err(|e| AppError::ExternalApi(format!("Prometheus API error: {}", e)))?;
+
+    // Prometheus answers bad queries with 400/422 and an error body, so read it either way
+    let status = response.status();
+    let json: JsonValue = response.json().await
+        .map_err(|e| AppError::ExternalApi(format!("Failed to parse Prometheus response ({}): {}", status, e)))?;
+
+    if json["status"] != "success" {
+        let message = json["error"].as_str().unwrap_or("unknown error");
+        return Err(if status.is_client_error() {
+            AppError::Validation(format!("Prometheus rejected the query: {}", message))
+        } else {
+            AppError::ExternalApi(format!("Prometheus returned status {}: {}", status, message))
+        });
+    }
+
+    normalize(&json["data"])
+}
+
+/// Turn a Prometheus `data` object into a `PromResult`
+fn normalize(data: &JsonValue) -> Result<PromResult> {
+    let result = &data["result"];
+    let series = |key: &str| -> Result<Vec<PromSeries>> {
+        result
+            .as_array()
+            .ok_or_else(|| malformed("result is not an array"))?
+            .iter()
+            .map(|series| {
+                let labels = serde_json::from_value(series["metric"].clone()).unwrap_or_default();
+                let points = match key {
+                    "value" => vec![parse_point(&series["value"])?],
+                    _ => series[key]
+                        .as_array()
+                        .ok_or_else(|| malformed("values is not an array"))?
+                        .iter()
+                        .map(parse_point)
+                        .collect::<Result<_>>()?,
+                };
+                Ok(PromSeries { labels, points })
+            })
+            .collect()
+    };
+
+    match data["resultType"].as_str() {
+        Some("scalar") => Ok(PromResult::Scalar { point: parse_point(result)? }),
+        Some("vector") => Ok(PromResult::Vector { series: series("value")? }),
+        Some("matrix") => Ok(PromResult::Matrix { series: series("values")? }),
+        Some(other) => Err(AppError::ExternalApi(format!("Unsupported Prometheus result type: {}", other))),
+        None => Err(malformed("missing resultType")),
+    }
+}
+
+/// Parse a `[<unix seconds>, "<value>"]` pair
+fn parse_point(pair: &JsonValue) -> Result<PromPoint> {
+    let seconds = pair[0].as_f64().ok_or_else(|| malformed("sample has no timestamp"))?;
+    let value = pair[1]
+        .as_str()
+        .ok_or_else(|| malformed("sample has no value"))?
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite());
+
+    let timestamp = Utc
+        .timestamp_millis_opt((seconds * 1000.0).round() as i64)
+        .single()
+        .ok_or_else(|| malformed("sample timestamp is out of range"))?;
+
+    Ok(PromPoint { timestamp, value })
+}
+
+fn malformed(reason: &str) -> AppError {
+    AppError::ExternalApi(format!("Malformed Prometheus response: {}", reason))
+}
+
+/// Resolve a range query's range and step, checking Prometheus' point limit
+fn range_and_step(query: &PromQuery) -> std::result::Result<(Duration, Duration), String> {
+    let range = parse_duration(query.range.as_deref().unwrap_or(DEFAULT_RANGE))?;
+    if range > Duration::days(MAX_RANGE_DAYS) {
+        return Err(format!("Ranges are limited to {} days", MAX_RANGE_DAYS));
+    }
+
+    let step = match &query.step {
+        Some(step) => parse_duration(step)?,
+        None => Duration::seconds((range.num_seconds() / TARGET_POINTS).max(MIN_AUTO_STEP_SECONDS)),
+    };
+    if range.num_seconds() / step.num_seconds() > MAX_POINTS {
+        return Err(format!("Step is too small: a range query may return at most {} points", MAX_POINTS));
+    }
+
+    Ok((range, step))
+}
+
+/// Parse a Prometheus-style duration like `30s`, `5m`, `6h`, `7d` or `1w`
+fn parse_duration(text: &str) -> std::result::Result<Duration, String> {
+    let text = text.trim();
+    let invalid = || format!("Invalid duration '{}', expected e.g. 30s, 5m or 6h", text);
+
+    let unit = text.chars().last().ok_or_else(invalid)?;
+    let amount: i64 = text[..text.len() - unit.len_utf8()].parse().map_err(|_| invalid())?;
+    if amount <= 0 || amount > 100_000 {
+        return Err(invalid());
+    }
+
+    match unit {
+        's' => Ok(Duration::seconds(amount)),
+        'm' => Ok(Duration::minutes(amount)),
+        'h' => Ok(Duration::hours(amount)),
+        'd' => Ok(Duration::days(amount)),
+        'w' => Ok(Duration::weeks(amount)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Validate a dashboard widget's config for the Prometheus provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    let query = match serde_path_to_error::deserialize::<_, PromQuery>(JsonValue::Object(config.clone())) {
+        Ok(query) => query,
+        Err(e) => {
+            let path = e.path().to_string();
+            let field = if path == "." { "config".to_string() } else { path };
+            return vec![FieldError::new(field, e.inner().to_string())];
+        }
+    };
+
+    let mut errors = Vec::new();
+
+    if query.query.trim().is_empty() {
+        errors.push(FieldError::new("query", "query is required"));
+    }
+
+    match query.mode {
+        PromMode::Instant => {
+            if query.range.is_some() {
+                errors.push(FieldError::new("range", "Only range queries take a range"));
+            }
+            if query.step.is_some() {
+                errors.push(FieldError::new("step", "Only range queries take a step"));
+            }
+        }
+        PromMode::Range => {
+            if let Some(Err(message)) = query.range.as_deref().map(parse_duration) {
+                errors.push(FieldError::new("range", message));
+            } else if let Some(Err(message)) = query.step.as_deref().map(parse_duration) {
+                errors.push(FieldError::new("step", message));
+            } else if let Err(message) = range_and_step(&query) {
+                errors.push(FieldError::new("step", message));
+            }
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+    use serde_json::json;
+
+    fn query(mode: PromMode, range: Option<&str>, step: Option<&str>) -> PromQuery {
+        PromQuery {
+            query: "up".to_string(),
+            mode,
+            range: range.map(str::to_string),
+            step: step.map(str::to_string),
+            time: None,
+        }
+    }
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(seconds, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn instant_query_normalises_vector() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/query")
+            .match_header("authorization", "Bearer secret")
+            .match_body(Matcher::UrlEncoded("query".to_string(), "up".to_string()))
+            .with_body(json!({
+                "status": "success",
+                "data": {
+                    "resultType": "vector",
+                    "result": [
+                        { "metric": { "job": "api" }, "value": [1700000000.5, "1"] },
+                        { "metric": { "job": "db" }, "value": [1700000000.5, "NaN"] }
+                    ]
+                }
+            }).to_string())
+            .create_async()
+            .await;
+
+        let (endpoint, params) = request_params(&query(PromMode::Instant, None, None), at(1_700_000_000)).unwrap();
+        let result = query_prometheus(&server.url(), Some("secret"), endpoint, &params).await.unwrap();
+        mock.assert_async().await;
+
+        let PromResult::Vector { series } = result else { panic!("expected a vector") };
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].labels["job"], "api");
+        assert_eq!(series[0].points[0].value, Some(1.0));
+        assert_eq!(series[0].points[0].timestamp.timestamp_millis(), 1_700_000_000_500);
+        assert_eq!(series[1].points[0].value, None);
+    }
+
+    #[tokio::test]
+    async fn range_query_normalises_matrix() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/api/v1/query_range")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("start".to_string(), "1699996380".to_string()),
+                Matcher::UrlEncoded("end".to_string(), "1699999980".to_string()),
+                Matcher::UrlEncoded("step".to_string(), "60".to_string()),
+            ]))
+            .with_body(json!({
+                "status": "success",
+                "data": {
+                    "resultType": "matrix",
+                    "result": [{
+                        "metric": { "job": "api" },
+                        "values": [[1699999920, "0.5"], [1699999980, "+Inf"]]
+                    }]
+                }
+            }).to_string())
+            .create_async()
+            .await;
+
+        let (endpoint, params) =
+            request_params(&query(PromMode::Range, Some("1h"), Some("1m")), at(1_700_000_030)).unwrap();
+        let result = query_prometheus(&server.url(), None, endpoint, &params).await.unwrap();
+        mock.assert_async().await;
+
+        let PromResult::Matrix { series } = result else { panic!("expected a matrix") };
+        let values: Vec<_> = series[0].points.iter().map(|point| point.value).collect();
+        assert_eq!(values, vec![Some(0.5), None]);
+    }
+
+    #[tokio::test]
+    async fn scalar_result_is_a_single_point() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/v1/query")
+            .with_body(json!({
+                "status": "success",
+                "data": { "resultType": "scalar", "result": [1700000000, "42"] }
+            }).to_string())
+            .create_async()
+            .await;
+
+        let result = query_prometheus(&server.url(), None, "query", &[("query", "42".to_string())]).await.unwrap();
+
+        let PromResult::Scalar { point } = result else { panic!("expected a scalar") };
+        assert_eq!(point.value, Some(42.0));
+        assert_eq!(point.timestamp, at(1_700_000_000));
+    }
+
+    #[tokio::test]
+    async fn bad_query_is_a_validation_error() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/api/v1/query")
+            .with_status(400)
+            .with_body(json!({
+                "status": "error",
+                "errorType": "bad_data",
+                "error": "parse error: unexpected end of input"
+            }).to_string())
+            .create_async()
+            .await;
+
+        let result = query_prometheus(&server.url(), None, "query", &[("query", "sum(".to_string())]).await;
+
+        match result {
+            Err(AppError::Validation(message)) => assert!(message.contains("unexpected end of input")),
+            other => panic!("expected a validation error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn instant_query_without_time_has_a_stable_key() {
+        let instant = query(PromMode::Instant, None, None);
+        let first = request_params(&instant, at(1_700_000_000)).unwrap();
+        let second = request_params(&instant, at(1_700_000_017)).unwrap();
+        assert_eq!(first, second);
+
+        let pinned = PromQuery { time: Some(at(1_700_000_000)), ..query(PromMode::Instant, None, None) };
+        let (_, params) = request_params(&pinned, at(1_700_000_017)).unwrap();
+        assert!(params.contains(&("time", "1700000000".to_string())));
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("30s"), Ok(Duration::seconds(30)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::minutes(5)));
+        assert_eq!(parse_duration(" 6h "), Ok(Duration::hours(6)));
+        assert_eq!(parse_duration("7d"), Ok(Duration::days(7)));
+        assert_eq!(parse_duration("1w"), Ok(Duration::weeks(1)));
+
+        for invalid in ["", "h", "0m", "-5m", "5y", "1.5h", "100001s", "5é"] {
+            assert!(parse_duration(invalid).is_err(), "{:?} should be rejected", invalid);
+        }
+    }
+
+    #[test]
+    fn picks_range_and_step() {
+        let (range, step) = range_and_step(&query(PromMode::Range, None, None)).unwrap();
+        assert_eq!(range, Duration::hours(1));
+        assert_eq!(step, Duration::seconds(MIN_AUTO_STEP_SECONDS));
+
+        let (range, step) = range_and_step(&query(PromMode::Range, Some("7d"), None)).unwrap();
+        assert_eq!(range, Duration::days(7));
+        assert_eq!(step, Duration::seconds(7 * 86_400 / TARGET_POINTS));
+
+        let (_, step) = range_and_step(&query(PromMode::Range, Some("6h"), Some("5m"))).unwrap();
+        assert_eq!(step, Duration::minutes(5));
+
+        assert!(range_and_step(&query(PromMode::Range, Some("91d"), None)).is_err());
+        assert!(range_and_step(&query(PromMode::Range, Some("7d"), Some("1s"))).is_err());
+    }
+}
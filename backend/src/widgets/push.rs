@@ -0,0 +1,94 @@
+use axum::{extract::{Query, State}, response::IntoResponse, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, FieldError, Result},
+    models::PushPoint,
+    widgets::{required_str, WidgetConfig},
+    AppState,
+};
+
+/// History points returned when the widget doesn't ask for a number
+const DEFAULT_HISTORY_POINTS: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+pub struct PushQuery {
+    pub source_id: Uuid,
+    /// Number of history points to return, newest last
+    pub history: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushData {
+    pub name: String,
+    pub latest: Option<JsonValue>,
+    pub latest_at: Option<DateTime<Utc>>,
+    pub history: Vec<PushPoint>,
+}
+
+pub async fn fetch_push_data(
+    user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Query(query): Query<PushQuery>,
+) -> Result<impl IntoResponse> {
+    Ok(Json(fetch_push(&state, user_ctx.user_id, &query).await?))
+}
+
+/// Read the latest pushed value (and recent history) of one of the user's push sources
+pub async fn fetch_push(state: &AppState, user_id: Uuid, query: &PushQuery) -> Result<PushData> {
+    let source: Option<(String, Option<JsonValue>, Option<DateTime<Utc>>)> = sqlx::query_as(
+        "SELECT name, latest, latest_at FROM push_sources WHERE id = $1 AND user_id = $2"
+    )
+    .bind(query.source_id)
+    .bind(user_id)
+    .fetch_optional(state.db.pool())
+    .await?;
+
+    let (name, latest, latest_at) =
+        source.ok_or_else(|| AppError::NotFound("Push source not found".to_string()))?;
+
+    let limit = query.history.unwrap_or(DEFAULT_HISTORY_POINTS).clamp(0, DEFAULT_HISTORY_POINTS * 10);
+
+    let mut history: Vec<PushPoint> = sqlx::query_as(
+        "SELECT recorded_at AS timestamp, value
+         FROM push_points
+         WHERE source_id = $1
+         ORDER BY recorded_at DESC
+         LIMIT $2"
+    )
+    .bind(query.source_id)
+    .bind(limit)
+    .fetch_all(state.db.pool())
+    .await?;
+    history.reverse();
+
+    Ok(PushData {
+        name,
+        latest,
+        latest_at,
+        history,
+    })
+}
+
+/// Validate a dashboard widget's config for the push provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if let Some(source_id) = required_str(config, "source_id", &mut errors) {
+        if Uuid::parse_str(source_id).is_err() {
+            errors.push(FieldError::new("source_id", "Must be a push source ID"));
+        }
+    }
+
+    if let Some(history) = config.get("history") {
+        if !history.is_u64() {
+            errors.push(FieldError::new("history", "Must be a non-negative integer"));
+        }
+    }
+
+    errors
+}
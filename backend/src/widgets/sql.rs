@@ -0,0 +1,359 @@
+use axum::{extract::State, response::IntoResponse, Json};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use sqlparser::{
+    ast::{Query, SetExpr, Statement},
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+    tokenizer::{Token, Tokenizer, Whitespace},
+};
+use sqlx::{postgres::PgArguments, Column, Connection, Executor, PgConnection, Postgres, Statement as _, TypeInfo};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::{
+    auth::UserCtx,
+    error::{AppError, FieldError, Result},
+    handlers::data_source::find_data_source,
+    models::{validate_connection_string, DataSource},
+    secrets,
+    widgets::WidgetConfig,
+    AppState,
+};
+
+/// Timeout for connecting to a data source
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default and largest number of rows returned
+const DEFAULT_MAX_ROWS: usize = 1_000;
+const MAX_ROWS: usize = 10_000;
+
+/// Default and largest statement timeouts
+const DEFAULT_TIMEOUT_MS: u64 = 5_000;
+const MAX_TIMEOUT_MS: u64 = 30_000;
+
+/// Default, smallest and largest cache lifetimes
+const DEFAULT_CACHE_TTL: usize = 60;
+const MIN_CACHE_TTL: usize = 10;
+const MAX_CACHE_TTL: usize = 3_600;
+
+/// A parameterised SELECT against a registered data source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SqlQuery {
+    pub data_source_id: Uuid,
+    /// A single read-only statement; `$1`, `$2`, ... refer to `params`
+    pub query: String,
+    /// Parameter values; numbers bind as bigint/double, strings as text (cast with `$1::date`)
+    #[serde(default)]
+    pub params: Vec<JsonValue>,
+    pub max_rows: Option<usize>,
+    pub timeout_ms: Option<u64>,
+    pub cache_ttl: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SqlColumn {
+    pub name: String,
+    /// Postgres type name, e.g. `int8`, `numeric`, `text` or `timestamptz`
+    #[serde(rename = "type")]
+    pub column_type: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SqlData {
+    pub columns: Vec<SqlColumn>,
+    pub rows: Vec<Vec<JsonValue>>,
+    /// Whether rows past `max_rows` were dropped
+    pub truncated: bool,
+}
+
+/// The config carries a query and its parameters, so this endpoint takes it as a JSON body
+pub async fn fetch_sql_data(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Json(query): Json<SqlQuery>,
+) -> Result<impl IntoResponse> {
+    let errors = check_query(&query);
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    Ok(Json(fetch_sql(&state, &query).await?))
+}
+
+/// Run a SQL widget's query in a read-only transaction (cached)
+pub async fn fetch_sql(state: &AppState, query: &SqlQuery) -> Result<SqlData> {
+    let sql = read_only_statement(&query.query).map_err(AppError::Validation)?;
+
+    let serialized = serde_json::to_vec(query)
+        .map_err(|e| AppError::Internal(format!("Failed to serialize SQL widget query: {}", e)))?;
+    let cache_key = format!("sql:{}", hex::encode(Sha256::digest(&serialized)));
+
+    // Check cache first
+    if let Some(cached) = state.cache.get::<SqlData>(&cache_key).await.ok().flatten() {
+        tracing::debug!("Cache hit for SQL widget on data source {}", query.data_source_id);
+        return Ok(cached);
+    }
+
+    let source = find_data_source(state, query.data_source_id).await?;
+    let mut conn = connect(state, &source).await?;
+    let result = run_query(&mut conn, sql, query).await;
+    let _ = conn.close().await;
+    let data = result?;
+
+    let ttl = query.cache_ttl.unwrap_or(DEFAULT_CACHE_TTL);
+    let _ = state.cache.set(&cache_key, &data, ttl).await;
+
+    Ok(data)
+}
+
+/// Open a connection to a data source
+pub(crate) async fn connect(state: &AppState, source: &DataSource) -> Result<PgConnection> {
+    let connection_string = secrets::decrypt(&state.config, &source.connection_encrypted)?;
+    let options = validate_connection_string(&connection_string)?.application_name("insightboard");
+
+    tokio::time::timeout(CONNECT_TIMEOUT, PgConnection::connect_with(&options))
+        .await
+        .map_err(|_| AppError::ExternalApi(format!("Timed out connecting to data source '{}'", source.name)))?
+        .map_err(|e| AppError::ExternalApi(format!("Failed to connect to data source '{}': {}", source.name, e)))
+}
+
+async fn run_query(conn: &mut PgConnection, sql: &str, query: &SqlQuery) -> Result<SqlData> {
+    let max_rows = query.max_rows.unwrap_or(DEFAULT_MAX_ROWS);
+    let timeout_ms = query.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS);
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("SET TRANSACTION READ ONLY").execute(&mut *tx).await?;
+    sqlx::query(&format!("SET LOCAL statement_timeout = {}", timeout_ms))
+        .execute(&mut *tx)
+        .await?;
+
+    // Preparing the statement yields its columns without running it
+    let statement = (&mut *tx).prepare(sql).await.map_err(query_error)?;
+    let columns: Vec<SqlColumn> = statement
+        .columns()
+        .iter()
+        .map(|column| SqlColumn {
+            name: column.name().to_string(),
+            column_type: column.type_info().name().to_lowercase(),
+        })
+        .collect();
+
+    if columns.is_empty() {
+        return Err(AppError::Validation("The query returns no columns".to_string()));
+    }
+
+    // Postgres converts every column type to JSON; positional aliases keep duplicate names apart
+    let aliases: Vec<String> = (0..columns.len()).map(|index| format!("c{}", index)).collect();
+    let wrapped = format!(
+        "SELECT row_to_json(q) FROM (\n{}\n) AS q({})",
+        sql,
+        aliases.join(", ")
+    );
+
+    let mut wrapped_query = sqlx::query_scalar::<_, sqlx::types::Json<serde_json::Map<String, JsonValue>>>(&wrapped);
+    for param in &query.params {
+        wrapped_query = bind_param(wrapped_query, param);
+    }
+
+    let mut rows = Vec::new();
+    let mut truncated = false;
+    {
+        let mut stream = wrapped_query.fetch(&mut *tx);
+        while let Some(sqlx::types::Json(mut row)) = stream.try_next().await.map_err(query_error)? {
+            if rows.len() == max_rows {
+                truncated = true;
+                break;
+            }
+            rows.push(
+                aliases
+                    .iter()
+                    .map(|alias| row.remove(alias).unwrap_or(JsonValue::Null))
+                    .collect::<Vec<_>>(),
+            );
+        }
+    }
+
+    tx.rollback().await?;
+
+    Ok(SqlData { columns, rows, truncated })
+}
+
+type WidgetQuery<'q> = sqlx::query::QueryScalar<
+    'q,
+    Postgres,
+    sqlx::types::Json<serde_json::Map<String, JsonValue>>,
+    PgArguments,
+>;
+
+/// Bind a JSON parameter with the closest Postgres type
+fn bind_param<'q>(query: WidgetQuery<'q>, param: &JsonValue) -> WidgetQuery<'q> {
+    match param {
+        JsonValue::Null => query.bind(None::<String>),
+        JsonValue::Bool(value) => query.bind(*value),
+        JsonValue::Number(number) => match number.as_i64() {
+            Some(value) => query.bind(value),
+            None => query.bind(number.as_f64()),
+        },
+        JsonValue::String(value) => query.bind(value.clone()),
+        value => query.bind(sqlx::types::Json(value.clone())),
+    }
+}
+
+/// Errors raised by the user's query are reported back to them; anything else is internal
+fn query_error(error: sqlx::Error) -> AppError {
+    match error {
+        sqlx::Error::Database(e) => AppError::Validation(format!("Query failed: {}", e.message())),
+        e => AppError::ExternalApi(format!("Data source query failed: {}", e)),
+    }
+}
+
+/// Parse the query and return it (without trailing semicolons) if it is one read-only statement
+fn read_only_statement(sql: &str) -> std::result::Result<&str, String> {
+    let statements = Parser::parse_sql(&PostgreSqlDialect {}, sql)
+        .map_err(|e| format!("Invalid SQL: {}", e))?;
+
+    // The parser skips comments, so one statement can still carry text after its semicolon
+    let tokens = Tokenizer::new(&PostgreSqlDialect {}, sql)
+        .tokenize()
+        .map_err(|e| format!("Invalid SQL: {}", e))?;
+    let text_after_semicolon = tokens
+        .iter()
+        .skip_while(|token| **token != Token::SemiColon)
+        .any(|token| {
+            !matches!(
+                token,
+                Token::SemiColon | Token::Whitespace(Whitespace::Space | Whitespace::Newline | Whitespace::Tab)
+            )
+        });
+    if text_after_semicolon {
+        return Err("Exactly one statement is allowed".to_string());
+    }
+
+    match statements.as_slice() {
+        [Statement::Query(query)] if is_read_only(query) => {
+            Ok(sql.trim().trim_end_matches(|c: char| c == ';' || c.is_whitespace()))
+        }
+        [_] => Err("Only read-only SELECT queries are allowed".to_string()),
+        _ => Err("Exactly one statement is allowed".to_string()),
+    }
+}
+
+/// A query without data-modifying CTEs, SELECT INTO or row locks
+fn is_read_only(query: &Query) -> bool {
+    fn body_is_read_only(body: &SetExpr) -> bool {
+        match body {
+            SetExpr::Select(select) => select.into.is_none(),
+            SetExpr::Query(query) => is_read_only(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                body_is_read_only(left) && body_is_read_only(right)
+            }
+            SetExpr::Values(_) | SetExpr::Table(_) => true,
+            _ => false,
+        }
+    }
+
+    query.locks.is_empty()
+        && query.for_clause.is_none()
+        && query
+            .with
+            .as_ref()
+            .is_none_or(|with| with.cte_tables.iter().all(|cte| is_read_only(&cte.query)))
+        && body_is_read_only(&query.body)
+}
+
+/// Validate a dashboard widget's config for the SQL provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    match serde_path_to_error::deserialize::<_, SqlQuery>(JsonValue::Object(config.clone())) {
+        Ok(query) => check_query(&query),
+        Err(e) => {
+            let path = e.path().to_string();
+            let field = if path == "." { "config".to_string() } else { path };
+            vec![FieldError::new(field, e.inner().to_string())]
+        }
+    }
+}
+
+fn check_query(query: &SqlQuery) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if let Err(message) = read_only_statement(&query.query) {
+        errors.push(FieldError::new("query", message));
+    }
+
+    if query.max_rows.is_some_and(|rows| rows == 0 || rows > MAX_ROWS) {
+        errors.push(FieldError::new("max_rows", format!("Must be between 1 and {}", MAX_ROWS)));
+    }
+
+    if query.timeout_ms.is_some_and(|timeout| timeout == 0 || timeout > MAX_TIMEOUT_MS) {
+        errors.push(FieldError::new(
+            "timeout_ms",
+            format!("Must be between 1 and {}", MAX_TIMEOUT_MS),
+        ));
+    }
+
+    if query.cache_ttl.is_some_and(|ttl| !(MIN_CACHE_TTL..=MAX_CACHE_TTL).contains(&ttl)) {
+        errors.push(FieldError::new(
+            "cache_ttl",
+            format!("Must be between {} and {}", MIN_CACHE_TTL, MAX_CACHE_TTL),
+        ));
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_only_queries_are_accepted() {
+        for sql in [
+            "SELECT id, name FROM users WHERE id = $1",
+            "select 1;",
+            "VALUES (1, 'a'), (2, 'b')",
+            "SELECT 1 UNION ALL SELECT 2 EXCEPT SELECT 3",
+            "(SELECT 1) INTERSECT (SELECT 1)",
+            "WITH recent AS (SELECT * FROM events) SELECT count(*) FROM recent",
+            "SELECT ';' AS semicolon -- trailing note",
+        ] {
+            assert!(read_only_statement(sql).is_ok(), "{} should be accepted", sql);
+        }
+
+        assert_eq!(read_only_statement("SELECT 1 ;; \n").unwrap(), "SELECT 1");
+    }
+
+    #[test]
+    fn writes_and_locks_are_rejected() {
+        for sql in [
+            "INSERT INTO users (email) VALUES ('a@example.com')",
+            "UPDATE users SET email = 'a@example.com'",
+            "DELETE FROM users",
+            "WITH gone AS (DELETE FROM users RETURNING *) SELECT * FROM gone",
+            "SELECT * INTO backup FROM users",
+            "SELECT * FROM users FOR UPDATE",
+            "SELECT * FROM users FOR SHARE",
+            "SELECT 1 UNION SELECT * FROM (SELECT 1 FOR UPDATE) AS locked",
+            "TRUNCATE users",
+        ] {
+            assert!(read_only_statement(sql).is_err(), "{} should be rejected", sql);
+        }
+    }
+
+    #[test]
+    fn extra_statements_are_rejected() {
+        for sql in [
+            "SELECT 1; DELETE FROM users",
+            "SELECT 1; SELECT 2",
+            "SELECT 1; -- DELETE FROM users",
+            "SELECT 1; /* hidden */",
+            "SELECT 1 /* unterminated",
+        ] {
+            assert!(read_only_statement(sql).is_err(), "{} should be rejected", sql);
+        }
+    }
+}
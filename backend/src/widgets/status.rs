@@ -2,7 +2,18 @@ use axum::{extract::{Query, State}, response::IntoResponse, Json};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
-use crate::{auth::UserCtx, error::Result, AppState};
+use crate::{
+    auth::UserCtx,
+    config::Config,
+    egress::guarded_client,
+    error::{AppError, FieldError, Result},
+    models::MetricPoint,
+    widgets::{history::record_metrics, required_str, WidgetConfig, WidgetType},
+    AppState,
+};
+
+/// Timeout for each URL checked by the status widget
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Deserialize)]
 pub struct StatusQuery {
@@ -17,24 +28,32 @@ pub struct StatusCheck {
     pub response_time_ms: Option<u64>,
 }
 
+impl StatusCheck {
+    /// Whether the URL answered with a success status
+    pub fn is_up(&self) -> bool {
+        self.status_code.is_some_and(|code| (200..300).contains(&code))
+    }
+}
+
 pub async fn fetch_status_data(
     _user_ctx: UserCtx,
     State(state): State<AppState>,
     Query(query): Query<StatusQuery>,
 ) -> Result<impl IntoResponse> {
+    Ok(Json(fetch_status_checks(&state, &query).await?))
+}
+
+/// Check a list of URLs (cached)
+pub async fn fetch_status_checks(state: &AppState, query: &StatusQuery) -> Result<Vec<StatusCheck>> {
     let cache_key = format!("status:{}", query.urls);
     
     // Check cache first
     if let Some(cached) = state.cache.get::<Vec<StatusCheck>>(&cache_key).await.ok().flatten() {
         tracing::debug!("Cache hit for status data");
-        return Ok(Json(cached));
+        return Ok(cached);
     }
     
     let urls: Vec<&str> = query.urls.split(',').collect();
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
-        .build()
-        .unwrap();
     
     let mut checks = Vec::new();
     
@@ -44,31 +63,129 @@ pub async fn fetch_status_data(
             continue;
         }
         
-        let start = std::time::Instant::now();
-        
-        match client.get(url).send().await {
-            Ok(response) => {
-                let elapsed = start.elapsed().as_millis() as u64;
-                checks.push(StatusCheck {
-                    url: url.to_string(),
-                    status: "up".to_string(),
-                    status_code: Some(response.status().as_u16()),
-                    response_time_ms: Some(elapsed),
-                });
-            }
-            Err(e) => {
-                checks.push(StatusCheck {
-                    url: url.to_string(),
-                    status: format!("down: {}", e),
-                    status_code: None,
-                    response_time_ms: None,
-                });
-            }
-        }
+        checks.push(check_url(&state.config, url, CHECK_TIMEOUT).await);
     }
     
+    let points = checks
+        .iter()
+        .filter_map(|check| {
+            check
+                .response_time_ms
+                .map(|ms| MetricPoint::new(&check.url, "response_time_ms", ms as f64))
+        })
+        .collect();
+    record_metrics(state, WidgetType::Status, points).await;
+    
     // Cache for 2 minutes
     let _ = state.cache.set(&cache_key, &checks, 120).await;
     
-    Ok(Json(checks))
+    Ok(checks)
+}
+
+/// Request a URL once through the egress guard and report whether it answered with a success status
+pub async fn check_url(config: &Config, url: &str, timeout: Duration) -> StatusCheck {
+    let start = std::time::Instant::now();
+    
+    match guarded_request(config, url, timeout).await {
+        Ok(response) => {
+            let elapsed = start.elapsed().as_millis() as u64;
+            StatusCheck {
+                url: url.to_string(),
+                status: if response.status().is_success() {
+                    "up".to_string()
+                } else {
+                    format!("down: HTTP {}", response.status().as_u16())
+                },
+                status_code: Some(response.status().as_u16()),
+                response_time_ms: Some(elapsed),
+            }
+        }
+        Err(message) => StatusCheck {
+            url: url.to_string(),
+            status: format!("down: {}", message),
+            status_code: None,
+            response_time_ms: None,
+        },
+    }
+}
+
+/// GET a URL without following redirects, refusing private or reserved addresses
+async fn guarded_request(config: &Config, url: &str, timeout: Duration) -> std::result::Result<reqwest::Response, String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+    let client = guarded_client(config, &parsed, timeout)
+        .await
+        .map_err(|e| match e {
+            AppError::Validation(message) | AppError::ExternalApi(message) => message,
+            other => other.client_message(),
+        })?;
+
+    client.get(parsed).send().await.map_err(|e| e.to_string())
+}
+
+/// Validate a dashboard widget's config for the status provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    if let Some(urls) = required_str(config, "urls", &mut errors) {
+        for url in urls.split(',').map(str::trim).filter(|url| !url.is_empty()) {
+            match reqwest::Url::parse(url) {
+                Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => {}
+                _ => errors.push(FieldError::new("urls", format!("Invalid URL: {}", url))),
+            }
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config { egress_allowed_hosts: vec!["127.0.0.1".to_string()], ..Config::for_tests() }
+    }
+
+    #[tokio::test]
+    async fn only_success_statuses_are_up() {
+        let mut server = mockito::Server::new_async().await;
+        server.mock("GET", "/ok").with_status(204).create_async().await;
+        server.mock("GET", "/broken").with_status(503).create_async().await;
+
+        let check = check_url(&config(), &format!("{}/ok", server.url()), CHECK_TIMEOUT).await;
+        assert!(check.is_up());
+        assert_eq!(check.status, "up");
+
+        let check = check_url(&config(), &format!("{}/broken", server.url()), CHECK_TIMEOUT).await;
+        assert!(!check.is_up());
+        assert_eq!(check.status, "down: HTTP 503");
+        assert_eq!(check.status_code, Some(503));
+    }
+
+    #[tokio::test]
+    async fn private_addresses_are_not_requested() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/").expect(0).create_async().await;
+
+        let check = check_url(&Config::for_tests(), &server.url(), CHECK_TIMEOUT).await;
+        assert!(!check.is_up());
+        assert_eq!(check.status_code, None);
+        assert!(check.status.contains("private or reserved"), "{}", check.status);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn redirects_are_not_followed() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/moved")
+            .with_status(302)
+            .with_header("location", "http://169.254.169.254/")
+            .create_async()
+            .await;
+
+        let check = check_url(&config(), &format!("{}/moved", server.url()), CHECK_TIMEOUT).await;
+        assert!(!check.is_up());
+        assert_eq!(check.status, "down: HTTP 302");
+    }
 }
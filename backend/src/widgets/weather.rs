@@ -1,11 +1,162 @@
 use axum::{extract::{Query, State}, response::IntoResponse, Json};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 
-use crate::{auth::UserCtx, error::{AppError, Result}, AppState};
+use crate::{
+    auth::UserCtx,
+    error::{AppError, FieldError, Result},
+    models::MetricPoint,
+    widgets::{history::record_metrics, open_meteo, openweather, WidgetConfig, WidgetType},
+    AppState,
+};
 
-#[derive(Debug, Deserialize)]
+/// How long weather and forecasts are cached
+const WEATHER_CACHE_TTL: usize = 600;
+
+/// How long location searches are cached
+const GEOCODE_CACHE_TTL: usize = 86_400;
+
+/// Hourly forecast entries returned (3-hour steps, so one day)
+pub(super) const HOURLY_ENTRIES: usize = 8;
+
+/// Default and largest number of location search results
+const DEFAULT_SEARCH_LIMIT: usize = 5;
+const MAX_SEARCH_LIMIT: usize = 10;
+
+/// Unit system for temperatures and wind speed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeatherUnits {
+    /// °C and m/s
+    #[default]
+    Metric,
+    /// °F and mph
+    Imperial,
+    /// Kelvin and m/s
+    Standard,
+}
+
+impl WeatherUnits {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeatherUnits::Metric => "metric",
+            WeatherUnits::Imperial => "imperial",
+            WeatherUnits::Standard => "standard",
+        }
+    }
+
+    /// Symbol shown after a temperature
+    pub fn temperature_symbol(&self) -> &'static str {
+        match self {
+            WeatherUnits::Metric => "°C",
+            WeatherUnits::Imperial => "°F",
+            WeatherUnits::Standard => "K",
+        }
+    }
+
+    /// A temperature in these units converted to °C
+    pub fn celsius(&self, temp: f64) -> f64 {
+        match self {
+            WeatherUnits::Metric => temp,
+            WeatherUnits::Imperial => (temp - 32.0) * 5.0 / 9.0,
+            WeatherUnits::Standard => temp - 273.15,
+        }
+    }
+}
+
+/// Upstream weather API
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WeatherProvider {
+    #[default]
+    #[serde(rename = "openweather")]
+    OpenWeather,
+    /// Keyless Open-Meteo APIs
+    #[serde(rename = "open-meteo")]
+    OpenMeteo,
+}
+
+impl WeatherProvider {
+    /// Every provider, in fallback order
+    const ALL: [WeatherProvider; 2] = [WeatherProvider::OpenWeather, WeatherProvider::OpenMeteo];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeatherProvider::OpenWeather => "openweather",
+            WeatherProvider::OpenMeteo => "open-meteo",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|provider| provider.as_str().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Location is one of `city`, `lat` + `lon` or `zip`
+#[derive(Debug, Default, Deserialize)]
 pub struct WeatherQuery {
-    pub city: String,
+    pub city: Option<String>,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    pub zip: Option<String>, // ZIP or postal code with an optional country, e.g. `94040,us`
+    #[serde(default)]
+    pub units: WeatherUnits,
+    /// Provider to ask first; defaults to the deployment's choice
+    pub provider: Option<WeatherProvider>,
+    /// Include the next 24 hours in 3-hour steps
+    #[serde(default)]
+    pub hourly: bool,
+    /// Include a 5-day forecast
+    #[serde(default)]
+    pub daily: bool,
+    /// Include weather alerts, where the provider offers them for the location
+    #[serde(default)]
+    pub alerts: bool,
+}
+
+impl WeatherQuery {
+    /// Current metric conditions for a history/alert series
+    pub fn for_series(series: &str) -> Self {
+        if let Some(zip) = series.strip_prefix("zip:") {
+            return Self { zip: Some(zip.to_string()), ..Self::default() };
+        }
+
+        match series.split_once(',').map(|(lat, lon)| (lat.trim().parse(), lon.trim().parse())) {
+            Some((Ok(lat), Ok(lon))) => Self { lat: Some(lat), lon: Some(lon), ..Self::default() },
+            _ => Self { city: Some(series.to_string()), ..Self::default() },
+        }
+    }
+
+    /// Series name under which history is recorded: the city, `lat,lon` or `zip:<code>`
+    pub fn series(&self) -> String {
+        match (&self.city, self.lat.zip(self.lon), &self.zip) {
+            (Some(city), _, _) => city.trim().to_string(),
+            (None, Some((lat, lon)), _) => format!("{},{}", lat, lon),
+            (None, None, Some(zip)) => format!("zip:{}", zip.trim()),
+            (None, None, None) => String::new(),
+        }
+    }
+
+    /// The single location the query names
+    pub(super) fn location(&self) -> Result<Location<'_>> {
+        match (self.city.as_deref().map(str::trim), self.lat.zip(self.lon), self.zip.as_deref().map(str::trim)) {
+            (Some(city), None, None) if !city.is_empty() => Ok(Location::City(city)),
+            (None, Some((lat, lon)), None) => Ok(Location::Coordinates(lat, lon)),
+            (None, None, Some(zip)) if !zip.is_empty() => Ok(Location::Zip(zip)),
+            _ => Err(AppError::Validation(
+                "Exactly one of city, lat and lon, or zip is required".to_string(),
+            )),
+        }
+    }
+}
+
+/// Where to get the weather for
+pub(super) enum Location<'a> {
+    /// City name, optionally with a country code (`London,uk`)
+    City(&'a str),
+    Coordinates(f64, f64),
+    /// ZIP or postal code, optionally with a country code (`94040,us`)
+    Zip(&'a str),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -16,6 +167,74 @@ pub struct WeatherData {
     pub description: String,
     pub icon: String,
     pub city_name: String,
+    #[serde(default)]
+    pub country: Option<String>,
+    #[serde(default)]
+    pub lat: f64,
+    #[serde(default)]
+    pub lon: f64,
+    #[serde(default)]
+    pub wind_speed: f64,
+    #[serde(default)]
+    pub units: WeatherUnits,
+    /// Provider that answered
+    #[serde(default)]
+    pub provider: WeatherProvider,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hourly: Option<Vec<HourlyForecast>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<Vec<DailyForecast>>,
+    /// `None` when alerts weren't requested or the provider doesn't offer them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alerts: Option<Vec<WeatherAlert>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HourlyForecast {
+    pub time: DateTime<Utc>,
+    pub temp: f64,
+    pub feels_like: f64,
+    pub humidity: i32,
+    pub description: String,
+    pub icon: String,
+    /// Probability of precipitation, 0-1
+    pub precipitation_probability: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DailyForecast {
+    /// Date at the location
+    pub date: NaiveDate,
+    pub temp_min: f64,
+    pub temp_max: f64,
+    pub description: String,
+    pub icon: String,
+    pub precipitation_probability: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeatherAlert {
+    pub event: String,
+    pub sender: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub description: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LocationSearchQuery {
+    pub q: String,
+    pub limit: Option<usize>,
+}
+
+/// A geocoding match for the widget config modal
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WeatherLocation {
+    pub name: String,
+    pub state: Option<String>,
+    pub country: String,
+    pub lat: f64,
+    pub lon: f64,
 }
 
 pub async fn fetch_weather_data(
@@ -23,46 +242,190 @@ pub async fn fetch_weather_data(
     State(state): State<AppState>,
     Query(query): Query<WeatherQuery>,
 ) -> Result<impl IntoResponse> {
-    let api_key = state.config.openweather_api_key.as_ref()
-        .ok_or_else(|| AppError::Internal("OpenWeather API key not configured".to_string()))?;
-    
-    let cache_key = format!("weather:{}", query.city);
-    
+    let errors = check_query(&query);
+    if !errors.is_empty() {
+        return Err(AppError::InvalidFields(errors));
+    }
+
+    Ok(Json(fetch_weather(&state, &query).await?))
+}
+
+/// Fetch current weather, and optionally forecasts and alerts, for a location (cached).
+///
+/// Providers are tried in order, so an outage or missing key falls back to the next one.
+pub async fn fetch_weather(state: &AppState, query: &WeatherQuery) -> Result<WeatherData> {
+    query.location()?;
+
+    let cache_key = format!(
+        "weather:{}:{}:{}:{}:{}:{}",
+        query.series().to_lowercase(),
+        query.units.as_str(),
+        query.provider.map_or("auto", |provider| provider.as_str()),
+        query.hourly,
+        query.daily,
+        query.alerts
+    );
+
     // Check cache first
     if let Some(cached) = state.cache.get::<WeatherData>(&cache_key).await.ok().flatten() {
-        tracing::debug!("Cache hit for weather data: {}", query.city);
+        tracing::debug!("Cache hit for weather data: {}", query.series());
+        return Ok(cached);
+    }
+
+    let mut first_error = None;
+    let mut weather = None;
+    for provider in provider_order(state, query.provider) {
+        let result = match provider {
+            WeatherProvider::OpenWeather => match state.config.openweather_api_key.as_deref() {
+                Some(api_key) => openweather::fetch(api_key, query).await,
+                None => Err(AppError::Internal("OpenWeather API key not configured".to_string())),
+            },
+            WeatherProvider::OpenMeteo => open_meteo::fetch(query).await,
+        };
+
+        match result {
+            Ok(data) => {
+                weather = Some(data);
+                break;
+            }
+            Err(e) => {
+                tracing::warn!("Weather provider {} failed for {}: {}", provider.as_str(), query.series(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    let Some(weather_data) = weather else {
+        return Err(first_error.unwrap_or_else(|| AppError::Internal("No weather provider available".to_string())));
+    };
+
+    // History is always kept in °C so unit choices don't mix in one series
+    let point = MetricPoint::new(query.series(), "temp", query.units.celsius(weather_data.temp));
+    record_metrics(state, WidgetType::Weather, vec![point]).await;
+
+    let _ = state.cache.set(&cache_key, &weather_data, WEATHER_CACHE_TTL).await;
+
+    Ok(weather_data)
+}
+
+/// Search locations by name for the widget config modal (cached)
+pub async fn search_weather_locations(
+    _user_ctx: UserCtx,
+    State(state): State<AppState>,
+    Query(query): Query<LocationSearchQuery>,
+) -> Result<impl IntoResponse> {
+    let search = query.q.trim();
+    if search.is_empty() {
+        return Err(AppError::Validation("Search text is required".to_string()));
+    }
+    let limit = query.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_SEARCH_LIMIT);
+
+    let cache_key = format!("weather:geocode:{}:{}", search.to_lowercase(), limit);
+    if let Some(cached) = state.cache.get::<Vec<WeatherLocation>>(&cache_key).await.ok().flatten() {
         return Ok(Json(cached));
     }
-    
-    // Fetch from OpenWeather API
-    let url = format!(
-        "https://api.openweathermap.org/data/2.5/weather?q={}&appid={}&units=metric",
-        query.city, api_key
-    );
-    
-    let response = reqwest::get(&url).await
-        .map_err(|e| AppError::ExternalApi(format!("OpenWeather API error: {}", e)))?;
-    
-    if !response.status().is_success() {
-        return Err(AppError::ExternalApi(
-            format!("OpenWeather API returned status: {}", response.status())
-        ));
-    }
-    
-    let json: serde_json::Value = response.json().await
-        .map_err(|e| AppError::ExternalApi(format!("Failed to parse weather response: {}", e)))?;
-    
-    let weather_data = WeatherData {
-        temp: json["main"]["temp"].as_f64().unwrap_or(0.0),
-        feels_like: json["main"]["feels_like"].as_f64().unwrap_or(0.0),
-        humidity: json["main"]["humidity"].as_i64().unwrap_or(0) as i32,
-        description: json["weather"][0]["description"].as_str().unwrap_or("").to_string(),
-        icon: json["weather"][0]["icon"].as_str().unwrap_or("").to_string(),
-        city_name: json["name"].as_str().unwrap_or(&query.city).to_string(),
+
+    let mut first_error = None;
+    let mut locations = None;
+    for provider in provider_order(&state, None) {
+        let result = match provider {
+            WeatherProvider::OpenWeather => match state.config.openweather_api_key.as_deref() {
+                Some(api_key) => openweather::search(api_key, search, limit).await,
+                None => continue,
+            },
+            WeatherProvider::OpenMeteo => open_meteo::search(search, limit).await,
+        };
+
+        match result {
+            Ok(found) => {
+                locations = Some(found);
+                break;
+            }
+            Err(e) => {
+                tracing::warn!("Location search with {} failed: {}", provider.as_str(), e);
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    let Some(locations) = locations else {
+        return Err(first_error.unwrap_or_else(|| AppError::Internal("No weather provider available".to_string())));
     };
-    
-    // Cache for 10 minutes
-    let _ = state.cache.set(&cache_key, &weather_data, 600).await;
-    
-    Ok(Json(weather_data))
+
+    let _ = state.cache.set(&cache_key, &locations, GEOCODE_CACHE_TTL).await;
+
+    Ok(Json(locations))
+}
+
+/// The requested (or deployment default) provider first, then the rest as fallbacks
+fn provider_order(state: &AppState, requested: Option<WeatherProvider>) -> Vec<WeatherProvider> {
+    let primary = requested.unwrap_or_else(|| default_provider(state));
+    let mut order = vec![primary];
+    order.extend(WeatherProvider::ALL.iter().copied().filter(|provider| *provider != primary));
+    order
+}
+
+/// `WEATHER_PROVIDER` if set, else OpenWeather when it has a key and Open-Meteo otherwise
+fn default_provider(state: &AppState) -> WeatherProvider {
+    if let Some(name) = state.config.weather_provider.as_deref() {
+        match WeatherProvider::parse(name) {
+            Some(provider) => return provider,
+            None => tracing::warn!("Unknown WEATHER_PROVIDER '{}', choosing automatically", name),
+        }
+    }
+
+    if state.config.openweather_api_key.is_some() {
+        WeatherProvider::OpenWeather
+    } else {
+        WeatherProvider::OpenMeteo
+    }
+}
+
+/// Validate a dashboard widget's config for the weather provider
+pub fn validate_config(config: &WidgetConfig) -> Vec<FieldError> {
+    match serde_path_to_error::deserialize::<_, WeatherQuery>(JsonValue::Object(config.clone())) {
+        Ok(query) => check_query(&query),
+        Err(e) => {
+            let path = e.path().to_string();
+            let field = if path == "." { "config".to_string() } else { path };
+            vec![FieldError::new(field, e.inner().to_string())]
+        }
+    }
+}
+
+fn check_query(query: &WeatherQuery) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    let locations = [
+        query.city.is_some(),
+        query.lat.is_some() || query.lon.is_some(),
+        query.zip.is_some(),
+    ];
+    match locations.iter().filter(|given| **given).count() {
+        0 => errors.push(FieldError::new("city", "A city, lat and lon, or zip is required")),
+        1 => {}
+        _ => errors.push(FieldError::new("city", "Only one of city, lat and lon, or zip may be given")),
+    }
+
+    if query.city.as_deref().is_some_and(|city| city.trim().is_empty()) {
+        errors.push(FieldError::new("city", "city must not be empty"));
+    }
+
+    if query.zip.as_deref().is_some_and(|zip| zip.trim().is_empty()) {
+        errors.push(FieldError::new("zip", "zip must not be empty"));
+    }
+
+    match (query.lat, query.lon) {
+        (Some(lat), _) if !(-90.0..=90.0).contains(&lat) => {
+            errors.push(FieldError::new("lat", "Must be between -90 and 90"));
+        }
+        (_, Some(lon)) if !(-180.0..=180.0).contains(&lon) => {
+            errors.push(FieldError::new("lon", "Must be between -180 and 180"));
+        }
+        (Some(_), None) => errors.push(FieldError::new("lon", "lon is required with lat")),
+        (None, Some(_)) => errors.push(FieldError::new("lat", "lat is required with lon")),
+        _ => {}
+    }
+
+    errors
 }
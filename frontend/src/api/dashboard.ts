@@ -29,8 +29,17 @@ export const dashboardApi = {
 	 * Get all dashboards for the current user
 	 */
 	async list(): Promise<Dashboard[]> {
-		const response = await apiClient.get<any[]>('/dashboards');
-		return response.data.map(mapServerToClient);
+		// Backend returns pages of { items, total, next_cursor }; follow the cursor to the end
+		const dashboards: Dashboard[] = [];
+		let cursor: string | null = null;
+		do {
+			const params: Record<string, unknown> = { limit: 100 };
+			if (cursor) params.cursor = cursor;
+			const response = await apiClient.get<any>('/dashboards', { params });
+			dashboards.push(...response.data.items.map(mapServerToClient));
+			cursor = response.data.next_cursor ?? null;
+		} while (cursor);
+		return dashboards;
 	},
 
 	/**